@@ -7,27 +7,33 @@
 //!    with safe, type-checked process chaining.
 //! 2. **State Loop:** Implements a refresh loop so deleting an item (Ctrl+Del) immediately 
 //!    re-opens the menu without the app closing.
+//! 3. **Image Previews:** Binary image entries are decoded into cached thumbnails and shown
+//!    as rofi icons instead of the raw "[[ binary data ]]" placeholder.
+//! 4. **Pins:** Ctrl+P saves an entry to `~/.config/rust-dotfiles/clip_pins.json`. Pinned items
+//!    are listed first and are untouched by Delete/Wipe (Ctrl+P on a pin removes it).
+//! 5. **Filters:** Ctrl+F cycles the list between all entries, text only, images only and URLs only.
 
 use anyhow::{anyhow, Context, Result};
-use serde::Deserialize;
-use std::path::PathBuf;
+use image::imageops::FilterType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::fs;
 use std::io::Write; 
 use std::process::{Command, Stdio};
-
-fn expand_path(path: &str) -> PathBuf {
-    if let Some(stripped) = path.strip_prefix("~/")
-        && let Some(home) = dirs::home_dir() {
-            return home.join(stripped);
-        }
-    PathBuf::from(path)
-}
+use common::{expand_path, load_config};
 
 // --- Config Models ---
 #[derive(Deserialize, Debug)]
 struct ClipConfig {
     rofi_config: String,
     message: String,
+    // Only the newest `max_entries` history items are listed. Unset = unlimited.
+    #[serde(default)]
+    max_entries: Option<usize>,
+    // Also delete the entries beyond the cap from cliphist, not just hide them.
+    #[serde(default)]
+    trim_history: bool,
 }
 
 #[derive(Deserialize, Debug)]
@@ -35,15 +41,133 @@ struct GlobalConfig {
     clip_manager: ClipConfig,
 }
 
-fn load_config() -> Result<GlobalConfig> {
-    let config_path = dirs::home_dir()
+// --- Pinned Store ---
+
+const PIN_PREFIX: &str = "📌 ";
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct Pins {
+    entries: Vec<String>,
+}
+
+fn get_pins_path() -> Result<PathBuf> {
+    Ok(dirs::home_dir()
         .context("Cannot find home dir")?
-        .join(".config/rust-dotfiles/config.toml");
-    let config_str = fs::read_to_string(&config_path)
-        .with_context(|| format!("Failed to read config file from path {}", config_path.display()))?;
-    let config: GlobalConfig = toml::from_str(&config_str)
-        .context("Failed to parse config.toml. Check for syntax errors.")?;
-    Ok(config)
+        .join(".config/rust-dotfiles/clip_pins.json"))
+}
+
+fn load_pins() -> Pins {
+    get_pins_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_pins(pins: &Pins) -> Result<()> {
+    let path = get_pins_path()?;
+    let json = serde_json::to_string_pretty(pins)?;
+    fs::write(&path, json)
+        .with_context(|| format!("Failed to write pins to {}", path.display()))
+}
+
+/// Single-line rofi row for a pinned entry (newlines shown as ↵, long text truncated).
+fn pin_row(text: &str) -> String {
+    let flat = text.trim().replace('\n', " ↵ ");
+    let preview: String = flat.chars().take(100).collect();
+    format!("{}{}", PIN_PREFIX, preview)
+}
+
+/// Maps a selected rofi row back to its pin index.
+fn find_pin(pins: &Pins, selection: &str) -> Option<usize> {
+    if !selection.starts_with(PIN_PREFIX) {
+        return None;
+    }
+    pins.entries.iter().position(|p| pin_row(p) == selection)
+}
+
+/// Ctrl+P: pins a history entry, or unpins it if it is already pinned.
+fn toggle_pin(selection: &str) -> Result<()> {
+    let mut pins = load_pins();
+    if let Some(idx) = find_pin(&pins, selection) {
+        pins.entries.remove(idx);
+        return save_pins(&pins);
+    }
+
+    let bytes = decode_bytes(selection)?;
+    let text = String::from_utf8(bytes).map_err(|_| anyhow!("Only text entries can be pinned"))?;
+    if !pins.entries.contains(&text) {
+        pins.entries.push(text);
+    }
+    save_pins(&pins)
+}
+
+/// Copies a pinned entry straight to the clipboard (it may no longer exist in cliphist).
+fn copy_text(text: &str) -> Result<()> {
+    let mut child = Command::new("wl-copy")
+        .stdin(Stdio::piped())
+        .spawn()
+        .context("Failed to spawn 'wl-copy'")?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())
+             .context("Failed to write to wl-copy stdin")?;
+    }
+
+    if !child.wait()?.success() {
+        return Err(anyhow!("wl-copy failed"));
+    }
+    Ok(())
+}
+
+// --- Content Filter ---
+
+/// cliphist's preview for anything that isn't text (images included).
+const BINARY_MARKER: &str = "[[ binary data";
+
+/// Which entries the list shows. Cycled with Ctrl+F; resets to `All` on the next launch.
+#[derive(Clone, Copy)]
+enum Filter {
+    All,
+    Text,
+    Images,
+    Urls,
+}
+
+impl Filter {
+    fn next(self) -> Self {
+        match self {
+            Filter::All => Filter::Text,
+            Filter::Text => Filter::Images,
+            Filter::Images => Filter::Urls,
+            Filter::Urls => Filter::All,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Filter::All => "All",
+            Filter::Text => "Text only",
+            Filter::Images => "Images only",
+            Filter::Urls => "URLs only",
+        }
+    }
+
+    /// Matches a cliphist preview or a pinned text.
+    fn matches(self, preview: &str) -> bool {
+        let is_binary = preview.trim_start().starts_with(BINARY_MARKER);
+        match self {
+            Filter::All => true,
+            Filter::Text => !is_binary,
+            Filter::Images => is_binary,
+            Filter::Urls => !is_binary && (preview.contains("http://") || preview.contains("https://")),
+        }
+    }
+
+    /// Matches a history line (`id\tpreview`).
+    fn matches_line(self, line: &str) -> bool {
+        self.matches(line.split_once('\t').map_or(line, |(_, preview)| preview))
+    }
 }
 
 // --- Core Process Wrappers ---
@@ -103,6 +227,109 @@ fn decode_and_copy(selection: &str) -> Result<()> {
     Ok(())
 }
 
+// --- Image Previews ---
+
+const THUMB_WIDTH: u32 = 128;
+
+/// Thumbnails live in ~/.cache/clip-manager/thumbs, keyed by cliphist id.
+fn get_thumb_dir() -> Result<PathBuf> {
+    let dir = dirs::cache_dir()
+        .context("Cannot find cache dir")?
+        .join("clip-manager/thumbs");
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create {}", dir.display()))?;
+    Ok(dir)
+}
+
+/// Image entries look like: `42\t[[ binary data 120 KiB png 1920x1080 ]]`.
+/// Returns the cliphist id for those, None for text entries.
+fn image_entry_id(line: &str) -> Option<&str> {
+    let (id, preview) = line.split_once('\t')?;
+    let preview = preview.trim();
+    let is_image = preview.starts_with(BINARY_MARKER)
+        && ["png", "jpeg", "jpg", "gif", "bmp", "webp"]
+            .iter()
+            .any(|ext| preview.contains(&format!(" {} ", ext)));
+    is_image.then_some(id)
+}
+
+/// Decodes an entry via `cliphist decode` and returns the raw bytes.
+fn decode_bytes(selection: &str) -> Result<Vec<u8>> {
+    let mut child = Command::new("cliphist")
+        .arg("decode")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .context("Failed to spawn 'cliphist decode'")?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(selection.as_bytes())
+             .context("Failed to write to cliphist stdin")?;
+    }
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(anyhow!("cliphist decode failed"));
+    }
+    Ok(output.stdout)
+}
+
+/// Generates a thumbnail for an image entry if it doesn't exist.
+/// Returns the path to the thumbnail.
+fn ensure_thumbnail(line: &str, id: &str, thumb_dir: &Path) -> Option<PathBuf> {
+    let thumb_path = thumb_dir.join(format!("{}.png", id));
+    // Cache Hit: cliphist ids are never reused, so an existing thumb is always current.
+    if thumb_path.exists() {
+        return Some(thumb_path);
+    }
+    let bytes = decode_bytes(line).ok()?;
+    let img = image::load_from_memory(&bytes).ok()?; // Skip unreadable/corrupt entries
+    let thumb = img.resize(THUMB_WIDTH, THUMB_WIDTH, FilterType::Nearest);
+    if let Err(e) = thumb.save(&thumb_path) {
+        eprintln!("Failed to save thumb for entry {}: {}", id, e);
+        return None;
+    }
+    Some(thumb_path)
+}
+
+/// Formats the history for rofi, attaching thumbnails to image entries with the
+/// `\0icon\x1f` delimiter syntax. Thumbnails for entries no longer in the history are removed;
+/// entries hidden by `filter` keep theirs but aren't listed (or thumbnailed).
+fn build_rofi_list(history: &str, filter: Filter) -> String {
+    let Ok(thumb_dir) = get_thumb_dir() else {
+        return history.lines().filter(|line| filter.matches_line(line)).collect::<Vec<_>>().join("\n");
+    };
+
+    let mut live = HashSet::new();
+    let rows: Vec<String> = history
+        .lines()
+        .filter(|line| {
+            if let Some(id) = image_entry_id(line) {
+                live.insert(format!("{}.png", id));
+            }
+            filter.matches_line(line)
+        })
+        .map(|line| {
+            let thumb = image_entry_id(line).and_then(|id| ensure_thumbnail(line, id, &thumb_dir));
+            match thumb {
+                Some(path) => format!("{}\0icon\x1f{}", line, path.to_string_lossy()),
+                None => line.to_string(),
+            }
+        })
+        .collect();
+
+    // Prune thumbs for deleted/wiped entries.
+    if let Ok(entries) = fs::read_dir(&thumb_dir) {
+        for entry in entries.flatten() {
+            if !live.contains(entry.file_name().to_string_lossy().as_ref()) {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+
+    rows.join("\n")
+}
+
 // --- Modification Actions ---
 fn delete_entry(selection: &str) -> Result<()> {
     let mut child = Command::new("cliphist")
@@ -137,12 +364,64 @@ fn wipe_history() -> Result<()> {
     Ok(())
 }
 
+/// Keeps the newest `max_entries` lines (cliphist lists newest first). With `trim_history`
+/// the rest are deleted from cliphist in one call so the store stops growing too.
+fn cap_history(history: String, config: &ClipConfig) -> String {
+    let Some(max) = config.max_entries else {
+        return history;
+    };
+    let lines: Vec<&str> = history.lines().collect();
+    if lines.len() <= max {
+        return history;
+    }
+    let (kept, excess) = lines.split_at(max);
+    if config.trim_history
+        && let Err(e) = delete_entry(&excess.join("\n"))
+    {
+        eprintln!("Failed to trim clipboard history: {}", e);
+    }
+    kept.join("\n")
+}
+
 // --- UI Logic ---
 
+/// rofi's effective binding for `option` (e.g. `kb-row-up`) with `key` removed, so a custom
+/// keybind can take `key` over without dropping the other default or user bindings.
+/// Read from `rofi -dump-config`; `fallback` is used if that fails.
+fn binding_without(rofi_config: &Path, option: &str, key: &str, fallback: &str) -> String {
+    let dump = Command::new("rofi")
+        .arg("-config")
+        .arg(rofi_config)
+        .arg("-dump-config")
+        .stderr(Stdio::null())
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+        .unwrap_or_default();
+    // Lines look like `/*	kb-row-up: "Up,Control+p";*/` (or uncommented when set)
+    let prefix = format!("{}:", option);
+    let current = dump
+        .lines()
+        .map(|line| line.trim().trim_start_matches("/*").trim())
+        .find_map(|line| line.strip_prefix(&prefix))
+        .and_then(|value| value.split('"').nth(1));
+    let Some(current) = current else {
+        return fallback.to_string();
+    };
+    let kept: Vec<&str> = current
+        .split(',')
+        .map(str::trim)
+        .filter(|k| !k.is_empty() && !k.eq_ignore_ascii_case(key))
+        .collect();
+    if kept.is_empty() { fallback.to_string() } else { kept.join(",") }
+}
+
 /// Launches Rofi with custom keybindings.
 /// Returns the Exit Code (to detect special actions) and the selected string.
-fn show_rofi(list: &str, config: &ClipConfig) -> Result<(i32, String)> {
+fn show_rofi(list: &str, config: &ClipConfig, filter: Filter) -> Result<(i32, String)> {
     let rofi_config_path = expand_path(&config.rofi_config);
+    let row_up = binding_without(&rofi_config_path, "kb-row-up", "Control+p", "Up");
+    let char_forward = binding_without(&rofi_config_path, "kb-move-char-forward", "Control+f", "Right");
+    let message = format!("{}\n<b>Filter:</b> {}  |  <b>Ctrl+F:</b> Cycle", config.message, filter.label());
 
     let mut child = Command::new("rofi")
         .arg("-i") 
@@ -152,10 +431,18 @@ fn show_rofi(list: &str, config: &ClipConfig) -> Result<(i32, String)> {
         .arg("Control+Delete") // Exit Code 10
         .arg("-kb-custom-2")
         .arg("Alt+Delete")     // Exit Code 11
+        .arg("-kb-custom-3")
+        .arg("Control+p")      // Exit Code 12
+        .arg("-kb-row-up")
+        .arg(&row_up)          // Free Control+p from rofi's default row-up binding
+        .arg("-kb-custom-4")
+        .arg("Control+f")      // Exit Code 13
+        .arg("-kb-move-char-forward")
+        .arg(&char_forward)    // Free Control+f from rofi's default cursor binding
         .arg("-config")
         .arg(rofi_config_path)
         .arg("-mesg")
-        .arg(&config.message)
+        .arg(message)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .spawn()
@@ -176,13 +463,23 @@ fn show_rofi(list: &str, config: &ClipConfig) -> Result<(i32, String)> {
 fn main() -> Result<()> {
     // Main Event Loop
     // Allows the menu to persist after performing an action like Delete.
+    let mut filter = Filter::All;
     loop {
         //Refresh data
-        let config = load_config()?.clip_manager;
-        let history_list = get_cliphist_list()?;
+        let config = load_config::<GlobalConfig>()?.clip_manager;
+        let pins = load_pins();
+        let mut rows: Vec<String> = pins.entries.iter()
+            .filter(|p| filter.matches(p))
+            .map(|p| pin_row(p))
+            .collect();
+        let history = build_rofi_list(&cap_history(get_cliphist_list()?, &config), filter);
+        if !history.is_empty() {
+            rows.push(history);
+        }
+        let history_list = rows.join("\n");
 
         // User Interaction
-        let (exit_code, selection) = show_rofi(&history_list, &config)?;
+        let (exit_code, selection) = show_rofi(&history_list, &config, filter)?;
 
         // Action Dispatch based on Rofi Exit Code
         match exit_code {
@@ -190,18 +487,39 @@ fn main() -> Result<()> {
                 if selection.is_empty() {
                     continue;
                 }
-                decode_and_copy(&selection)?;
+                match find_pin(&pins, &selection) {
+                    Some(idx) => copy_text(&pins.entries[idx])?,
+                    None => decode_and_copy(&selection)?,
+                }
                 break;
             }
             1 => break, // 1 = Esc: exit loop
-            10 => { // 10 = Ctrl+Del: Delete Item
-                delete_entry(&selection)?;
+            10 => { // 10 = Ctrl+Del: Delete Item (pins are only removed via Ctrl+P)
+                if find_pin(&pins, &selection).is_none() {
+                    delete_entry(&selection)?;
+                }
                 continue; // Re-loop to show updated list 
             }
             11 => { // 11 = Alt+Del: Wipe All
                 wipe_history()?;
                 continue; 
             }
+            12 => { // 12 = Ctrl+P: Pin / Unpin Item
+                // A failed pin (e.g. an image entry) is reported, not fatal to the picker
+                if !selection.is_empty()
+                    && let Err(e) = toggle_pin(&selection)
+                {
+                    let _ = Command::new("notify-send")
+                        .arg("Clipboard")
+                        .arg(e.to_string())
+                        .spawn();
+                }
+                continue;
+            }
+            13 => { // 13 = Ctrl+F: Next Filter
+                filter = filter.next();
+                continue;
+            }
             _ => {
                 break;
             }
@@ -2,12 +2,16 @@
 //!
 //! A read-only utility to poll the status of the Cloudflare DNS service.
 //! Used by Waybar's `custom/script` module to display the current state.
+//! When `[cloudflare_toggle.providers]` is set, the provider matching cloudflared's
+//! configured upstreams is shown next to the icon and added as a CSS class.
 
+use std::collections::HashMap;
 use std::fs;
 use std::process::Command;
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use serde_json::json;
+use cloudflare_toggle::read_status_cache;
 
 #[derive(Deserialize, Debug)]
 #[allow(dead_code)]
@@ -21,8 +25,12 @@ struct Config {
     bar_process_name: String,
     bar_signal_num: i32,
     service_name: String,
+    #[serde(default)]
+    providers: HashMap<String, Vec<String>>,
 }
 
+const CLOUDFLARED_CONFIG: &str = "/etc/cloudflared/config.yml";
+
 #[derive(Deserialize, Debug)]
 struct GlobalConfig {
     cloudflare_toggle: Config,
@@ -39,17 +47,69 @@ fn load_config() -> Result<GlobalConfig> {
     Ok(config)
 }
 
+/// Reads the `proxy-dns-upstream` URLs from cloudflared's config, either inline
+/// (`proxy-dns-upstream: url`) or as the usual indented `- url` list.
+fn read_upstreams() -> Vec<String> {
+    let Ok(content) = fs::read_to_string(CLOUDFLARED_CONFIG) else {
+        return Vec::new();
+    };
+    let mut lines = content.lines();
+    let mut upstreams = Vec::new();
+    for line in lines.by_ref() {
+        if let Some(inline) = line.strip_prefix("proxy-dns-upstream:") {
+            let inline = inline.trim().trim_start_matches('[').trim_end_matches(']');
+            upstreams.extend(
+                inline
+                    .split(',')
+                    .map(|u| u.trim().trim_matches('"').to_string())
+                    .filter(|u| !u.is_empty()),
+            );
+            break;
+        }
+    }
+    for line in lines {
+        match line.trim_start().strip_prefix('-') {
+            Some(url) if line.starts_with(char::is_whitespace) => {
+                upstreams.push(url.trim().trim_matches('"').to_string())
+            }
+            _ => break,
+        }
+    }
+    upstreams
+}
+
+/// The configured provider whose upstream list matches cloudflared's (order-insensitive).
+fn active_provider(providers: &HashMap<String, Vec<String>>) -> Option<&str> {
+    let mut current = read_upstreams();
+    if current.is_empty() {
+        return None;
+    }
+    current.sort();
+    providers
+        .iter()
+        .find(|(_, urls)| {
+            let mut urls = (*urls).clone();
+            urls.sort();
+            urls == current
+        })
+        .map(|(name, _)| name.as_str())
+}
+
 fn main() -> Result<()> {
+    common::version::exit_if_requested(env!("CARGO_BIN_NAME"), env!("CARGO_PKG_VERSION"));
     let config = load_config().map(|gc| gc.cloudflare_toggle);
     
     // 1. Check Service State
+    // Prefer the fresh cache from a just-finished toggle; otherwise ask systemd.
     // systemctl is-active returns "active" (exit code 0) or "inactive" (exit code 3/4).
-    let service_active = Command::new("systemctl")
-        .arg("is-active")
-        .arg(config.as_ref().map_or("dnscrypt-proxy", |c| &c.service_name))
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false);
+    let service_active = read_status_cache().unwrap_or_else(|| {
+        Command::new("systemctl")
+            .arg("is-active")
+            .arg(config.as_ref().map_or("dnscrypt-proxy", |c| &c.service_name))
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    });
     // 2. Read DNS Configuration
     // We display the actual content of resolv.conf in the tooltip for verification.
     let resolv_conf = fs::read_to_string("/etc/resolv.conf")
@@ -69,7 +129,22 @@ fn main() -> Result<()> {
             format!("Cloudflared: Stopped\nresolv.conf: {}", resolv_conf.trim())
         )
     };
-    // 4. Output JSON
+    // 4. Tag with the active DoH provider, if one is configured and recognised
+    let provider = config.as_ref().ok().and_then(|c| active_provider(&c.providers));
+    let (text, class, tooltip) = match provider {
+        Some(name) if service_active => (
+            format!("{} {}", text, name),
+            json!([class, name]),
+            format!("{}\nProvider: {}", tooltip, name),
+        ),
+        Some(name) => (
+            text.to_string(),
+            json!(class),
+            format!("{}\nProvider: {} (inactive)", tooltip, name),
+        ),
+        None => (text.to_string(), json!(class), tooltip),
+    };
+    // 5. Output JSON
     println!("{}", json!({
         "text": text,
         "class": class,
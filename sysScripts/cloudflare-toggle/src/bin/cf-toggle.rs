@@ -9,12 +9,25 @@
 //!    and manages the `systemd` service.
 //!
 //! This design avoids needing `sudo` in scripts or storing passwords.
+//!
+//! After starting the service the root phase checks that the new nameserver actually answers.
+//! If it doesn't (e.g. cloudflared failed to bind port 53), the fallback DNS is restored and
+//! the exit code tells the user phase to send a notification.
+//!
+//! `cf-toggle --provider <name>` follows the same two phases, but instead of toggling it
+//! points cloudflared's `proxy-dns-upstream` at one of the `[cloudflare_toggle.providers]`.
 
+use std::collections::HashMap;
 use std::env;
 use std::fs;
-use std::process::Command;
+use std::net::UdpSocket;
+use std::process::{Command, ExitCode};
+use std::thread;
+use std::time::Duration;
 use anyhow::{Context, Result};
-use serde::Deserialize; 
+use notify_rust::{Notification, Urgency};
+use serde::Deserialize;
+use cloudflare_toggle::{now_secs, read_status_cache, write_status_cache};
 
 // --- Configuration ---
 // Deserialize the full config struct even if we don't use all fields in this binary,
@@ -33,8 +46,22 @@ struct Config {
     bar_process_name: String,    // "waybar"
     bar_signal_num: i32,         // Signal offset
     service_name: String,
+    // DoH providers for --provider: name -> upstream URLs
+    #[serde(default)]
+    providers: HashMap<String, Vec<String>>,
 }
 
+const CLOUDFLARED_CONFIG: &str = "/etc/cloudflared/config.yml";
+
+// --- DNS Check ---
+// cloudflared can take a moment to start answering, so the lookup is retried a few times.
+const VERIFY_DOMAIN: &str = "cloudflare.com";
+const VERIFY_ATTEMPTS: u32 = 5;
+const VERIFY_TIMEOUT: Duration = Duration::from_secs(2);
+const VERIFY_RETRY_DELAY: Duration = Duration::from_secs(1);
+/// Root phase exit code: DNS didn't resolve after starting, fallback restored.
+const EXIT_DNS_FAILED: u8 = 3;
+
 #[derive(Deserialize, Debug)]
 struct GlobalConfig {
     cloudflare_toggle: Config,
@@ -51,6 +78,59 @@ fn load_config() -> Result<GlobalConfig> {
     Ok(config)
 }
 
+/// The first `nameserver` in a resolv.conf body, defaulting to cloudflared's 127.0.0.1.
+fn nameserver(resolv_content: &str) -> &str {
+    resolv_content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("nameserver"))
+        .map(str::trim)
+        .filter(|ip| !ip.is_empty())
+        .unwrap_or("127.0.0.1")
+}
+
+/// Sends one A query for `domain` to `server` port 53.
+/// Ok(true) if it answered without error and with at least one record.
+fn query(server: &str, domain: &str) -> Result<bool> {
+    let socket = UdpSocket::bind(if server.contains(':') { "[::]:0" } else { "0.0.0.0:0" })?;
+    socket.set_read_timeout(Some(VERIFY_TIMEOUT))?;
+    let addr = if server.contains(':') { format!("[{}]:53", server) } else { format!("{}:53", server) };
+    socket.connect(addr)?;
+
+    // Header: id, flags (recursion desired), 1 question, no other records
+    let id = (now_secs() as u16) ^ (std::process::id() as u16);
+    let mut packet = Vec::with_capacity(32);
+    packet.extend_from_slice(&id.to_be_bytes());
+    packet.extend_from_slice(&[0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0]);
+    for label in domain.split('.') {
+        packet.push(label.len() as u8);
+        packet.extend_from_slice(label.as_bytes());
+    }
+    packet.extend_from_slice(&[0, 0, 1, 0, 1]); // End of name, type A, class IN
+    socket.send(&packet)?;
+
+    let mut buf = [0u8; 512];
+    let len = socket.recv(&mut buf)?;
+    let is_reply = len >= 12 && buf[..2] == id.to_be_bytes() && buf[2] & 0x80 != 0;
+    let rcode = buf[3] & 0x0f;
+    let answers = u16::from_be_bytes([buf[6], buf[7]]);
+    Ok(is_reply && rcode == 0 && answers > 0)
+}
+
+/// Retries the lookup while the freshly started service comes up.
+fn dns_works(server: &str) -> bool {
+    for attempt in 1..=VERIFY_ATTEMPTS {
+        match query(server, VERIFY_DOMAIN) {
+            Ok(true) => return true,
+            Ok(false) => eprintln!("DNS check {}/{}: no answer from {}", attempt, VERIFY_ATTEMPTS, server),
+            Err(e) => eprintln!("DNS check {}/{}: {}: {}", attempt, VERIFY_ATTEMPTS, server, e),
+        }
+        if attempt < VERIFY_ATTEMPTS {
+            thread::sleep(VERIFY_RETRY_DELAY);
+        }
+    }
+    false
+}
+
 // --- User Mode (Phase 1) ---
 
 /// The entry point for the standard user.
@@ -60,13 +140,15 @@ fn run_as_user() -> Result<()> {
         .context("Failed to load config for user")?
         .cloudflare_toggle;
 
-    // Check current service status to toggle it
-    let is_running = Command::new("systemctl")
-        .arg("is-active")
-        .arg(&config.service_name)
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false);
+    // Check current service status to toggle it (a fresh cache saves the systemctl call)
+    let is_running = read_status_cache().unwrap_or_else(|| {
+        Command::new("systemctl")
+            .arg("is-active")
+            .arg(&config.service_name)
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    });
     
     let mode = if is_running { "--stop" } else { "--start" };
     let content_on = &config.resolv_content_on;
@@ -89,23 +171,79 @@ fn run_as_user() -> Result<()> {
 
     // Signal Waybar to refresh status immediately on success
     if status.success() {
-        let sig_base = 34;
-        let signal = sig_base + config.bar_signal_num;
-        let _ = Command::new("pkill")
-            .arg(format!("-{}", signal))
-            .arg("-x")
-            .arg(&config.bar_process_name)
-            .status();
+        signal_bar(&config);
+    } else if status.code() == Some(EXIT_DNS_FAILED.into()) {
+        // The root phase already switched back, so the bar needs the refresh too
+        signal_bar(&config);
+        let _ = Notification::new()
+            .summary("Cloudflare DNS failed")
+            .body(&format!(
+                "{} did not resolve {}. Restored the fallback DNS.",
+                nameserver(content_on), VERIFY_DOMAIN
+            ))
+            .icon("network-error")
+            .urgency(Urgency::Critical)
+            .show();
+    }
+    Ok(())
+}
+
+/// Switches cloudflared to the named provider's upstreams (via pkexec, like the toggle).
+fn run_provider_as_user(name: &str) -> Result<()> {
+    let config = load_config()
+        .context("Failed to load config for user")?
+        .cloudflare_toggle;
+
+    let Some(upstreams) = config.providers.get(name) else {
+        let mut known: Vec<&str> = config.providers.keys().map(String::as_str).collect();
+        known.sort();
+        anyhow::bail!(
+            "Unknown provider '{}'. Configured providers: {}",
+            name,
+            if known.is_empty() { "none".to_string() } else { known.join(", ") }
+        );
+    };
+    if upstreams.is_empty() {
+        anyhow::bail!("Provider '{}' has no upstream URLs", name);
+    }
+    for url in upstreams {
+        check_upstream(url).with_context(|| format!("Provider '{}'", name))?;
+    }
+
+    let self_exe = env::current_exe()
+        .context("Failed to get path to own executable")?;
+    let status = Command::new("pkexec")
+        .arg(self_exe)
+        .arg("--set-upstream")
+        .arg(&config.service_name)
+        .args(upstreams)
+        .status()
+        .context("Failed to run pkexec")?;
+
+    if status.success() {
+        signal_bar(&config);
     }
     Ok(())
 }
 
+/// Sends SIGRTMIN+N to the bar so the DNS module refreshes immediately.
+fn signal_bar(config: &Config) {
+    let sig_base = 34;
+    let signal = sig_base + config.bar_signal_num;
+    let _ = Command::new("pkill")
+        .arg(format!("-{}", signal))
+        .arg("-x")
+        .arg(&config.bar_process_name)
+        .status();
+}
+
 // --- Root Mode (Phase 2) ---
 
 /// The privileged worker.
 /// This function only runs when `pkexec` invokes this binary.
 /// It has permission to write to /etc/ and control systemd.
-fn run_as_root(mode: &str, service_name: &str, content_on: &str, content_off: &str) -> Result<()> {
+/// Returns false if DNS didn't work after starting (the fallback is restored by then).
+fn run_as_root(mode: &str, service_name: &str, content_on: &str, content_off: &str) -> Result<bool> {
     if mode == "--start" {
         // Enable service
         Command::new("systemctl")
@@ -121,6 +259,22 @@ fn run_as_root(mode: &str, service_name: &str, content_on: &str, content_off: &s
         fs::write("/etc/resolv.conf", content_on)
             .context("Failed to write /etc/resolv.conf")?;
 
+        // Verify before declaring victory; a dead resolver means no DNS at all
+        if !dns_works(nameserver(content_on)) {
+            eprintln!("DNS check failed, restoring the fallback resolv.conf");
+            fs::write("/etc/resolv.conf", content_off)
+                .context("Failed to restore /etc/resolv.conf")?;
+            // Stop the broken service too, so the bar shows DNS as off
+            let _ = Command::new("systemctl")
+                .arg("disable")
+                .arg("--now")
+                .arg(service_name)
+                .status();
+            write_status_cache(false);
+            return Ok(false);
+        }
+        write_status_cache(true);
+
     } else if mode == "--stop" {
         // Disable Service
         Command::new("systemctl")
@@ -135,12 +289,75 @@ fn run_as_root(mode: &str, service_name: &str, content_on: &str, content_off: &s
         // Restore DNS
         fs::write("/etc/resolv.conf", content_off)
             .context("Failed to write /etc/resolv.conf")?;
+        write_status_cache(false);
+    }
+    Ok(true)
+}
+
+/// Upstreams are written verbatim into a root-owned YAML file, so anything that could
+/// break out of its list item (a line break) or isn't a DoH URL is refused.
+fn check_upstream(url: &str) -> Result<()> {
+    if url.contains(['\n', '\r']) || !url.starts_with("https://") {
+        anyhow::bail!("Invalid upstream '{}': expected a single-line https:// URL", url.escape_debug());
+    }
+    Ok(())
+}
+
+/// Replaces the `proxy-dns-upstream` entry in cloudflared's config with `upstreams`,
+/// keeping every other line, then restarts the service if it is running.
+fn set_upstream_as_root(service_name: &str, upstreams: &[String]) -> Result<()> {
+    for url in upstreams {
+        check_upstream(url)?;
+    }
+    let current = fs::read_to_string(CLOUDFLARED_CONFIG).unwrap_or_default();
+    let mut lines: Vec<String> = Vec::new();
+    let mut lines_iter = current.lines().peekable();
+    let mut replaced = false;
+    while let Some(line) = lines_iter.next() {
+        if !line.starts_with("proxy-dns-upstream:") {
+            lines.push(line.to_string());
+            continue;
+        }
+        // Drop the old value: either inline or the indented "- url" list below it
+        while lines_iter
+            .peek()
+            .is_some_and(|l| l.starts_with(char::is_whitespace) && l.trim_start().starts_with('-'))
+        {
+            lines_iter.next();
+        }
+        push_upstreams(&mut lines, upstreams);
+        replaced = true;
     }
+    if !replaced {
+        push_upstreams(&mut lines, upstreams);
+    }
+
+    let mut content = lines.join("\n");
+    content.push('\n');
+    fs::write(CLOUDFLARED_CONFIG, content)
+        .with_context(|| format!("Failed to write {}", CLOUDFLARED_CONFIG))?;
+
+    // try-restart leaves a stopped service stopped; the new upstream applies on the next toggle
+    Command::new("systemctl")
+        .arg("try-restart")
+        .arg(service_name)
+        .status()?
+        .success()
+        .then_some(())
+        .context("Failed to restart systemctl service")?;
     Ok(())
 }
 
+fn push_upstreams(lines: &mut Vec<String>, upstreams: &[String]) {
+    lines.push("proxy-dns-upstream:".to_string());
+    for url in upstreams {
+        lines.push(format!("  - {}", url));
+    }
+}
+
 // --- Main Dispatcher ---
-fn main() -> Result<()> {
+fn main() -> Result<ExitCode> {
+    common::version::exit_if_requested(env!("CARGO_BIN_NAME"), env!("CARGO_PKG_VERSION"));
     let args: Vec<String> = env::args().collect();
 
     // Detect Mode based on arguments
@@ -150,6 +367,25 @@ fn main() -> Result<()> {
     // [2]: service_name
     // [3]: content_on
     // [4]: content_off
+    //
+    // Provider switching uses its own shapes:
+    // user: cf-toggle --provider <name>
+    // root: cf-toggle --set-upstream <service_name> <url>...
+
+    if args.len() > 1 && args[1] == "--provider" {
+        let name = args.get(2).context("Usage: cf-toggle --provider <name>")?;
+        run_provider_as_user(name)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    if args.len() > 1 && args[1] == "--set-upstream" {
+        if args.len() < 4 {
+            eprintln!("Internal Error: Missing arguments for root mode.");
+            // Non-zero so the user phase doesn't report a switch that never happened
+            return Ok(ExitCode::FAILURE);
+        }
+        set_upstream_as_root(&args[2], &args[3..])?;
+        return Ok(ExitCode::SUCCESS);
+    }
 
     if args.len() > 1 {
         let mode = &args[1];
@@ -158,16 +394,21 @@ fn main() -> Result<()> {
         if args.len() < 5 {
             eprintln!("Internal Error: Missing arguments for root mode.");
             // We return Ok here to avoid panic, but the operation fails silently.
-            return Ok(());
+            return Ok(ExitCode::SUCCESS);
         }
 
         let service_name = &args[2];
         let content_on = &args[3];
         let content_off = &args[4];
 
-        run_as_root(mode, service_name, content_on, content_off)
+        if run_as_root(mode, service_name, content_on, content_off)? {
+            Ok(ExitCode::SUCCESS)
+        } else {
+            Ok(ExitCode::from(EXIT_DNS_FAILED))
+        }
     } else {
         // No arguments? We are the user clicking the button.
-        run_as_user()
+        run_as_user()?;
+        Ok(ExitCode::SUCCESS)
     }
 }
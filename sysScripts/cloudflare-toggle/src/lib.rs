@@ -0,0 +1,43 @@
+//! Cloudflare Toggle (shared library)
+//!
+//! The status cache shared by both binaries: cf-toggle's root phase writes it after
+//! every change, cf-status reads it to skip `systemctl is-active` right after one.
+
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+
+// --- Status Cache ---
+// /run is root-writable, world-readable and cleared on boot.
+pub const STATUS_CACHE: &str = "/run/cf-toggle-status.json";
+pub const STATUS_CACHE_MAX_AGE_SECS: u64 = 5;
+
+#[derive(Serialize, Deserialize, Debug)]
+struct StatusCache {
+    active: bool,
+    timestamp: u64,
+}
+
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// The cached service state, if the cache exists and is younger than the max age.
+pub fn read_status_cache() -> Option<bool> {
+    let content = fs::read_to_string(STATUS_CACHE).ok()?;
+    let cache: StatusCache = serde_json::from_str(&content).ok()?;
+    (now_secs().saturating_sub(cache.timestamp) <= STATUS_CACHE_MAX_AGE_SECS).then_some(cache.active)
+}
+
+/// Records the service state (root phase only; /run isn't user-writable).
+pub fn write_status_cache(active: bool) {
+    let cache = StatusCache { active, timestamp: now_secs() };
+    if let Ok(json) = serde_json::to_string(&cache)
+        && let Err(e) = fs::write(STATUS_CACHE, json)
+    {
+        eprintln!("Warning: failed to write status cache: {}", e);
+    }
+}
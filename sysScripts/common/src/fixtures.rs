@@ -0,0 +1,61 @@
+//! Offline fixture mode (feature `test-util`).
+//!
+//! When `RUST_DOTFILES_FIXTURES=<dir>` is set, HTTP requests and external commands are
+//! answered from canned files in `<dir>/<tool>/` instead of the network or shell.
+//! A missing fixture is an error, so a test run never silently goes online.
+//! See `docs/FIXTURES.md` for the naming scheme.
+//!
+//! Usage: `const FIXTURES: Fixtures = Fixtures::new("waybar-weather");`
+
+use std::fs;
+use std::path::PathBuf;
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+
+pub const ENV_VAR: &str = "RUST_DOTFILES_FIXTURES";
+
+/// The fixture directory of one tool, named after its binary.
+pub struct Fixtures {
+    tool: &'static str,
+}
+
+impl Fixtures {
+    pub const fn new(tool: &'static str) -> Self {
+        Self { tool }
+    }
+
+    fn dir(&self) -> Option<PathBuf> {
+        std::env::var_os(ENV_VAR)
+            .filter(|v| !v.is_empty())
+            .map(|d| PathBuf::from(d).join(self.tool))
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.dir().is_some()
+    }
+
+    /// Returns the fixture contents when fixture mode is on, None otherwise.
+    pub fn load(&self, name: &str) -> Option<Result<String>> {
+        let path = self.dir()?.join(name);
+        Some(fs::read_to_string(&path).with_context(|| format!("Missing fixture {}", path.display())))
+    }
+
+    /// Like `load`, but parses the fixture as the JSON body of the request.
+    pub fn load_json<T: DeserializeOwned>(&self, name: &str) -> Option<Result<T>> {
+        self.load(name).map(|res| {
+            res.and_then(|s| serde_json::from_str(&s).with_context(|| format!("Invalid fixture {}", name)))
+        })
+    }
+}
+
+/// Builds a deterministic fixture file name from the request kind and its arguments,
+/// e.g. `["quote", "BTC-USD"]` -> `quote-BTC-USD.json`, `["market"]` -> `market.json`.
+/// Characters outside `[A-Za-z0-9.-]` (like `^`, `=`, `,`) become `_`.
+pub fn name(parts: &[&str]) -> String {
+    let stem: String = parts
+        .join("-")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+        .collect();
+    format!("{}.json", stem)
+}
@@ -0,0 +1,50 @@
+//! Shared Helpers (common)
+//!
+//! Library crate for the sysScripts tools. Every tool reads the same central config file
+//! (`~/.config/rust-dotfiles/config.toml`) and accepts `~/`-style paths in it, so both live
+//! here instead of being copied into each binary, along with the `-v` stderr logger
+//! (`logging`), the `--version` output (`version`) and, behind the `test-util` feature,
+//! the offline fixture mode (`fixtures`).
+//!
+//! Usage (in a tool's Cargo.toml): `common = { path = "../common" }`
+
+use std::fs;
+use std::path::PathBuf;
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+
+#[cfg(feature = "test-util")]
+pub mod fixtures;
+pub mod logging;
+pub mod version;
+
+/// Location of the central config, relative to `$HOME`.
+pub const CONFIG_RELATIVE_PATH: &str = ".config/rust-dotfiles/config.toml";
+
+/// Expands shell-style paths like `~/` to absolute system paths.
+pub fn expand_path(path: &str) -> PathBuf {
+    if let Some(stripped) = path.strip_prefix("~/")
+        && let Some(home) = dirs::home_dir() {
+            return home.join(stripped);
+        }
+    PathBuf::from(path)
+}
+
+/// Absolute path of the central config file.
+pub fn config_path() -> Result<PathBuf> {
+    Ok(dirs::home_dir()
+        .context("Cannot find home dir")?
+        .join(CONFIG_RELATIVE_PATH))
+}
+
+/// Loads and parses the central config into the caller's model.
+/// Each tool only declares the sections it needs; serde ignores the rest.
+pub fn load_config<T: DeserializeOwned>() -> Result<T> {
+    let config_path = config_path()?;
+
+    let config_str = fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read config file from path {}", config_path.display()))?;
+
+    toml::from_str(&config_str)
+        .context("Failed to parse config.toml. Check for syntax errors.")
+}
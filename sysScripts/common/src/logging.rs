@@ -0,0 +1,61 @@
+//! Minimal stderr logger behind the `log` macros.
+//!
+//! Waybar keeps a module's stderr in its own log, so the default only lets warnings and
+//! errors through. `-v` adds the major steps, `-vv` the request and cache decisions,
+//! `-vvv` the dependencies' own logging (reqwest, rustls, zbus, ...).
+//!
+//! Usage: `common::logging::init(module_path!(), common::logging::verbosity_from_args());`
+
+use std::sync::OnceLock;
+use log::{LevelFilter, Log, Metadata, Record};
+
+struct StderrLogger {
+    /// Crate name of the calling binary; records from other crates need `-vvv`.
+    tool: OnceLock<&'static str>,
+}
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        let root = metadata.target().split("::").next();
+        let ours = root == self.tool.get().copied() || root == Some("common");
+        metadata.level() <= log::max_level() && (ours || log::max_level() == LevelFilter::Trace)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{}] {}", record.level(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger { tool: OnceLock::new() };
+
+/// Counts `-v`, `-vv` (etc.) and `--verbose` in argv, for tools without clap.
+pub fn verbosity_from_args() -> u8 {
+    std::env::args()
+        .skip(1)
+        .map(|arg| match arg.as_str() {
+            "--verbose" => 1,
+            a if a.len() > 1 && a.starts_with('-') && a[1..].chars().all(|c| c == 'v') => a.len() - 1,
+            _ => 0,
+        })
+        .sum::<usize>()
+        .min(u8::MAX as usize) as u8
+}
+
+/// Installs the logger. `tool` is the caller's `module_path!()` at the crate root.
+/// 0 = warnings and errors, 1 = info, 2 = debug, 3+ = trace.
+pub fn init(tool: &'static str, verbosity: u8) {
+    let level = match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Info,
+        2 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    };
+    let _ = LOGGER.tool.set(tool);
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(level);
+    }
+}
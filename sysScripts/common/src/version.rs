@@ -0,0 +1,21 @@
+//! `--version` output shared by the tools: crate version plus the git commit it was built
+//! from, so a bug report says exactly which build it came from.
+//!
+//! Usage, first thing in `main`:
+//! `common::version::exit_if_requested(env!("CARGO_BIN_NAME"), env!("CARGO_PKG_VERSION"));`
+
+/// Short hash of the commit `common` was built from ("unknown" outside a git checkout).
+pub const GIT_HASH: &str = env!("COMMON_GIT_HASH");
+
+/// e.g. "0.1.0 (a588a13)". `pkg_version` is the caller's `CARGO_PKG_VERSION`.
+pub fn version_string(pkg_version: &str) -> String {
+    format!("{} ({})", pkg_version, GIT_HASH)
+}
+
+/// Prints "<tool> <version_string>" and exits if argv has `--version` or `-V`.
+pub fn exit_if_requested(tool: &str, pkg_version: &str) {
+    if std::env::args().skip(1).any(|arg| arg == "--version" || arg == "-V") {
+        println!("{} {}", tool, version_string(pkg_version));
+        std::process::exit(0);
+    }
+}
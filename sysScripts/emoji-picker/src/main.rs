@@ -7,9 +7,15 @@
 //! 2. **Hidden Metadata:** Injects invisible Pango markup so users can search by name ("smile")
 //!    without cluttering the visual interface with text.
 //! 3. **Wayland Integration:** Pipes the result directly to `wl-copy` for immediate pasting.
+//! 4. **Recents:** The last picks (`~/.config/rust-dotfiles/emoji_recent.json`) are listed first.
+//! 5. **Skin Tones:** An optional `default_skin_tone` is applied to every emoji that has variants.
+//! 6. **Typing:** With `--type` (or `type_emoji = true`) the emoji is injected via `wtype` instead,
+//!    falling back to the clipboard when `wtype` isn't available.
 
 use anyhow::{anyhow, Context, Result};
+use notify_rust::Notification;
 use serde::Deserialize;
+use std::env;
 use std::fmt::Write;
 use std::fs;
 use std::io::Write as IoWrite;
@@ -30,6 +36,38 @@ fn expand_path(path: &str) -> PathBuf {
 struct EmojiConfig {
     rofi_config: String,
     message: String,
+    #[serde(default)]
+    default_skin_tone: Option<SkinToneSetting>, // Unset = the default yellow emojis
+    #[serde(default)]
+    type_emoji: bool, // Same as passing --type
+}
+
+#[derive(Debug, Deserialize, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+enum SkinToneSetting {
+    Light,
+    MediumLight,
+    Medium,
+    MediumDark,
+    Dark,
+}
+
+impl SkinToneSetting {
+    fn tone(self) -> emojis::SkinTone {
+        match self {
+            SkinToneSetting::Light => emojis::SkinTone::Light,
+            SkinToneSetting::MediumLight => emojis::SkinTone::MediumLight,
+            SkinToneSetting::Medium => emojis::SkinTone::Medium,
+            SkinToneSetting::MediumDark => emojis::SkinTone::MediumDark,
+            SkinToneSetting::Dark => emojis::SkinTone::Dark,
+        }
+    }
+}
+
+/// Returns the emoji in the requested tone. Emojis without skin tone variants
+/// (or no configured tone) pass through unchanged.
+fn apply_tone(emoji: &'static emojis::Emoji, tone: Option<SkinToneSetting>) -> &'static emojis::Emoji {
+    tone.and_then(|t| emoji.with_skin_tone(t.tone())).unwrap_or(emoji)
 }
 #[derive(Debug, Deserialize)]
 struct GlobalConfig {
@@ -47,27 +85,66 @@ fn load_config() -> Result<GlobalConfig> {
     Ok(config)
 }
 
+// --- Recently Used ---
+
+const MAX_RECENT: usize = 24;
+
+fn get_recent_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".config/rust-dotfiles/emoji_recent.json"))
+}
+
+/// Most-recent-first list of previously picked emojis. Missing/corrupt file = no recents.
+fn load_recent() -> Vec<String> {
+    get_recent_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+/// Moves `emoji` to the front of the recents list, capped at MAX_RECENT.
+fn save_recent(emoji: &str) -> Result<()> {
+    let path = get_recent_path().context("Cannot find home dir")?;
+    let mut recent = load_recent();
+    recent.retain(|e| e != emoji);
+    recent.insert(0, emoji.to_string());
+    recent.truncate(MAX_RECENT);
+    fs::write(&path, serde_json::to_string(&recent)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
 // --- Core Logic ---
 
+/// Appends one Rofi row: the visible emoji (in the configured tone) followed by its
+/// invisible search keywords. Keywords come from the base emoji, so toned variants
+/// stay searchable by their usual name/shortcode.
+fn write_emoji_row(buffer: &mut String, emoji: &'static emojis::Emoji, tone: Option<SkinToneSetting>) {
+    let shortcode = emoji.shortcode().unwrap_or("");
+    // Format: <Visible Emoji> <Invisible Keywords>
+    let _ = writeln!(
+        buffer, 
+        "{} <span size='1' foreground='#00000000'>{} {}</span>", 
+        apply_tone(emoji, tone).as_str(), 
+        emoji.name(), 
+        shortcode
+        );
+}
+
 /// Generates the input buffer for Rofi.
 /// 
 /// UX Trick: I want users to be able to search for "fire" and see 🔥, 
 /// but we don't want the word "fire" taking up screen space.
 /// We use Pango markup to make the metadata (name, shortcode) strictly invisible 
 /// (size 1, transparent color), but Rofi's filter engine still sees it.
-fn build_emoji_list() -> String {
+///
+/// Recently used emojis are listed first (and again in their normal place further down).
+fn build_emoji_list(recent: &[String], tone: Option<SkinToneSetting>) -> String {
     // Pre-allocate memory to avoid re-allocations during the loop (approx 60kb data)
     let mut buffer = String::with_capacity(60 * 1024);
+    for emoji in recent.iter().filter_map(|e| emojis::get(e)) {
+        write_emoji_row(&mut buffer, emoji, tone);
+    }
     for emoji in emojis::iter() {
-        let shortcode = emoji.shortcode().unwrap_or("");
-        // Format: <Visible Emoji> <Invisible Keywords>
-        let _ = writeln!(
-            buffer, 
-            "{} <span size='1' foreground='#00000000'>{} {}</span>", 
-            emoji.as_str(), 
-            emoji.name(), 
-            shortcode
-            );
+        write_emoji_row(&mut buffer, emoji, tone);
     }
     buffer
 }
@@ -104,15 +181,52 @@ fn show_rofi(list: &str, config: &EmojiConfig) -> Result<String> {
 }
 
 /// Processing the result.
-/// Extracts the actual emoji character from the selected line and copies it to clipboard.
-fn parse_and_copy(selection: &str) -> Result<()> {
+/// Extracts the actual emoji character from the selected line and either types it
+/// into the focused window or copies it to clipboard.
+/// Returns the emoji (None for an empty selection).
+fn parse_and_output(selection: &str, tone: Option<SkinToneSetting>, type_it: bool) -> Result<Option<&str>> {
     // 1. Extract: The string contains "🔥 <span...". I only want the first part.
     let emoji = match selection.split_whitespace().next() {
         Some(emoji_char) => emoji_char,
-        None => return Ok(()), // Empty selection
+        None => return Ok(None), // Empty selection
     };
+    // Rows are already toned; this also covers recents saved before the tone changed.
+    let emoji = emojis::get(emoji)
+        .map(|e| apply_tone(e, tone).as_str())
+        .unwrap_or(emoji);
+
+    // 2. Output: Type it if asked, degrading to the clipboard if wtype can't.
+    if type_it {
+        match type_emoji(emoji) {
+            Ok(()) => return Ok(Some(emoji)),
+            Err(e) => {
+                let _ = Notification::new()
+                    .summary("Emoji Picker")
+                    .body(&format!("Couldn't type the emoji ({}). Copied to clipboard instead.", e))
+                    .show();
+            }
+        }
+    }
+    copy_to_clipboard(emoji)?;
+    Ok(Some(emoji))
+}
 
-    // 2. Clipboard: Pipe to `wl-copy`.
+/// Injects the emoji into the focused window via `wtype`.
+fn type_emoji(emoji: &str) -> Result<()> {
+    let status = Command::new("wtype")
+        .arg("--")
+        .arg(emoji)
+        .status()
+        .context("wtype is not installed")?;
+
+    if !status.success() {
+        return Err(anyhow!("wtype failed"));
+    }
+    Ok(())
+}
+
+/// Pipes the emoji to `wl-copy`.
+fn copy_to_clipboard(emoji: &str) -> Result<()> {
     // We explicitly set MIME type to UTF-8 text to ensure compatibility across apps.
     let mut child = Command::new("wl-copy")
         .arg("--type")
@@ -133,14 +247,17 @@ fn parse_and_copy(selection: &str) -> Result<()> {
 }
 fn main() -> Result<()> {
     let config = load_config()?.emoji_picker;
+    let type_it = config.type_emoji || env::args().any(|a| a == "--type");
     // Generate data
-    let emoji_list_string = build_emoji_list();
+    let emoji_list_string = build_emoji_list(&load_recent(), config.default_skin_tone);
     // Prompt User
     let selection = show_rofi(&emoji_list_string, &config)?;
     // Execute
-    if !selection.is_empty() {
-        parse_and_copy(&selection)?;
-    }
+    if !selection.is_empty()
+        && let Some(emoji) = parse_and_output(&selection, config.default_skin_tone, type_it)?
+        && let Err(e) = save_recent(emoji) {
+            eprintln!("Warning: Failed to save recent emojis: {}", e);
+        }
     Ok(())
 }
 
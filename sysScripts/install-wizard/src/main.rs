@@ -7,7 +7,7 @@
 //! Core Responsibilities:
 //! 1. **Hardware Detection:** Automatically identifies GPU vendors (NVIDIA/AMD/Intel)
 //!    via `lspci` and installs the appropriate drivers/VAAPI packages.
-//! 2. **Package Management:** Orchestrates `pacman` (official repo) and `yay` (AUR) installations.
+//! 2. **Package Management:** Orchestrates `pacman` (official repo) and `yay`/`paru` (AUR) installations.
 //! 3. **Security Hardening:** Configures UFW, Polkit, and secure directory permissions.
 //! 4. **Config Deployment:** Links dotfiles and generates machine-specific secrets (API keys)
 //!    securely without storing them in git.
@@ -15,28 +15,50 @@
 
 use colored::*;
 use inquire::Text;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashSet;
 use std::fs;
-use std::io::Write;
+use std::io::{IsTerminal, Write};
 use std::os::unix::fs::OpenOptionsExt;
 use std::os::unix::fs::PermissionsExt;
+use std::os::unix::process::ExitStatusExt;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
+use std::process::{Command, ExitStatus, Output, Stdio};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tempfile::NamedTempFile;
 
-const TURING_IDS: &[&str] = &[
-    "0x1e02", "0x1e04", "0x1e07", "0x1e30", // Titan RTX, 2080 Ti, Quadro...
-    "0x1f02", "0x1f06", "0x1f08", "0x1f82", // 2070, 2060, 1650 (TU106)...
-    "0x2182", "0x2184", "0x2187", "0x2188", // 1660 Ti, 1660, 1650 Super, 1650...
-    "0x2191", "0x21d1", // GTX 1650 Mobile variants..."0x1e02", "0x1e04", "0x1e07", "0x1e30",
-];
+// --- NVIDIA Driver Pins ---
+// Cards that regress on the current repo driver get pinned to an older driver branch.
+// Each rule matches the PCI device ID (e.g. "0x1f06") and names the AUR packages to
+// install instead of the repo driver. Cards matching no rule get the repo driver
+// ([nvidia]). The built-in table is the `[[nvidia_driver]]` list in packages.toml.template;
+// entries in packages.toml replace it, so a new regression doesn't need a rebuild.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(deny_unknown_fields)]
+struct DriverPin {
+    name: String,
+    /// Regex over the PCI device ID as sysfs reports it ("0x" + 4 lowercase hex digits)
+    device_ids: String,
+    packages: Vec<String>,
+    /// 32-bit userspace, installed best-effort
+    #[serde(default)]
+    lib32_packages: Vec<String>,
+    /// Turing needs NVreg_EnableGpuFirmware=0 on the legacy branch to survive suspend
+    #[serde(default = "default_gsp_firmware")]
+    gsp_firmware: bool,
+}
+
+fn default_gsp_firmware() -> bool {
+    true
+}
 
 // --- Enums for Hardware Detection ---
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 enum NvidiaArch {
     Modern,
-    Turing,
+    Pinned(DriverPin),
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -47,36 +69,426 @@ enum GpuVendor {
     Nvidia(NvidiaArch),
 }
 
-// Hardware Specific: NVIDIA
-const NVIDIA_PACKAGES: &[&str] = &[
-    "nvidia-dkms",
-    "nvidia-prime",
-    "nvidia-settings",
-    "libva-nvidia-driver",
-];
-
-// Hardware Specific: AMD
-const AMD_PACKAGES: &[&str] = &["vulkan-radeon", "libva-mesa-driver", "xf86-video-amdgpu"];
-
-// AUR
-const AUR_PACKAGES: &[&str] = &[
-    "zoom",
-    "slack-desktop",
-    "ledger-live-bin",
-    "visual-studio-code-bin",
-    "pinta",
-    "ttf-victor-mono",
-    "pear-desktop-bin",
-    "librewolf-bin",
-];
-
 const NEW_REPO_DIR: &str = "Genoa";
 const LEGACY_REPO_DIR: &str = "rust-wayland-power";
+
+// --- Output Control ---
+// Set once from `--quiet` at startup. Errors (eprintln!) and the final result
+// are always printed; everything routed through `status!` is progress noise.
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if !QUIET.load(Ordering::Relaxed) {
+            println!($($arg)*);
+        }
+    };
+}
+
+// --- Dry Run ---
+// Set once from `--dry-run`. Privileged commands, package installs and file writes are
+// printed instead of executed; read-only probes (lspci, pacman -Q, which) still run so
+// the preview follows the same branches a real install would. Always printed, even with --quiet.
+static DRY_RUN: AtomicBool = AtomicBool::new(false);
+
+fn dry_run() -> bool {
+    DRY_RUN.load(Ordering::Relaxed)
+}
+
+/// In dry-run mode, prints what would have happened and returns true so the caller skips it.
+fn dry_run_skip(action: &str) -> bool {
+    if dry_run() {
+        println!("   {} would {}", "[dry-run]".cyan(), action);
+    }
+    dry_run()
+}
+
+// --- Unattended Mode ---
+// Set once from `--unattended` (`--answers <file>` implies it). Prompts take their answer
+// from the answers file instead of the terminal. A missing answer never blocks: API keys
+// are left blank, the GPU is auto-picked and the driver reboot is skipped.
+static UNATTENDED: AtomicBool = AtomicBool::new(false);
+static ANSWERS: OnceLock<Answers> = OnceLock::new();
+
+/// Preseeded answers (see answers.toml.template).
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+struct Answers {
+    owm_api_key: Option<String>,
+    finnhub_api_key: Option<String>,
+    google_geo_api_key: Option<String>,
+    /// GPU driver path on multi-vendor machines: "nvidia", "amd" or "intel"
+    gpu: Option<String>,
+    /// Reboot after the driver phase (the script must then be run again)
+    reboot: bool,
+}
+
+fn unattended() -> bool {
+    UNATTENDED.load(Ordering::Relaxed)
+}
+
+fn answers() -> &'static Answers {
+    ANSWERS.get_or_init(Answers::default)
+}
+
+fn load_answers(path: &Path) -> Result<Answers, std::io::Error> {
+    let content = fs::read_to_string(path)?;
+    toml::from_str(&content)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+}
+
+/// A text prompt, or the preseeded answer (blank if missing) when unattended.
+/// `cancelled` is what an Esc at the interactive prompt yields.
+fn prompt_text(message: &str, answer: Option<&str>, cancelled: &str) -> String {
+    if unattended() {
+        return answer.unwrap_or_default().to_string();
+    }
+    Text::new(message)
+        .prompt()
+        .unwrap_or_else(|_| cancelled.to_string())
+}
+
+/// `status()`/`output()` that only print the command line under `--dry-run`,
+/// reporting success so the install flow carries on as if it had run.
+trait DryRunExt {
+    fn run_status(&mut self) -> std::io::Result<ExitStatus>;
+    fn run_output(&mut self) -> std::io::Result<Output>;
+}
+
+impl DryRunExt for Command {
+    fn run_status(&mut self) -> std::io::Result<ExitStatus> {
+        if dry_run() {
+            print_dry_command(self);
+            return Ok(ExitStatus::from_raw(0));
+        }
+        self.status()
+    }
+
+    fn run_output(&mut self) -> std::io::Result<Output> {
+        if dry_run() {
+            print_dry_command(self);
+            return Ok(Output {
+                status: ExitStatus::from_raw(0),
+                stdout: Vec::new(),
+                stderr: Vec::new(),
+            });
+        }
+        self.output()
+    }
+}
+
+fn print_dry_command(cmd: &Command) {
+    let mut line = cmd.get_program().to_string_lossy().to_string();
+    for arg in cmd.get_args() {
+        let arg = arg.to_string_lossy();
+        if arg.contains(char::is_whitespace) {
+            line.push_str(&format!(" '{}'", arg));
+        } else {
+            line.push(' ');
+            line.push_str(&arg);
+        }
+    }
+    if let Some(dir) = cmd.get_current_dir() {
+        line.push_str(&format!("   (in {})", dir.display()));
+    }
+    println!("   {} {}", "[dry-run]".cyan(), line);
+}
+// --- Package Manifest ---
+// An optional `packages.toml` (next to the installer binary, else in the repo root)
+// replaces any of the package lists without recompiling. Each section is
+// `[name]` + `packages = [...]`; sections left out keep the built-in defaults, which are
+// packages.toml.template itself (compiled in), so the template can't drift from them.
+// [common] replaces pkglist.txt, [rust_apps] limits which sysScripts crates get built.
+// A top-level `aur_helper = "paru"` picks the AUR helper (`--aur-helper` overrides it).
+const PACKAGE_MANIFEST: &str = "packages.toml";
+const DEFAULT_MANIFEST: &str = include_str!("../../../packages.toml.template");
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+struct PackageManifest {
+    aur_helper: Option<AurHelper>,
+    common: Option<PackageSection>,
+    nvidia: Option<PackageSection>,
+    amd: Option<PackageSection>,
+    aur: Option<PackageSection>,
+    rust_apps: Option<PackageSection>,
+    nvidia_driver: Option<Vec<DriverPin>>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct PackageSection {
+    packages: Vec<String>,
+}
+
+impl PackageManifest {
+    /// The built-in lists, parsed once from the compiled-in template.
+    fn defaults() -> &'static PackageManifest {
+        static DEFAULTS: OnceLock<PackageManifest> = OnceLock::new();
+        DEFAULTS.get_or_init(|| {
+            toml::from_str(DEFAULT_MANIFEST).expect("packages.toml.template is a valid manifest")
+        })
+    }
+
+    fn nvidia(&self) -> Vec<&str> {
+        section_or(&self.nvidia, &Self::defaults().nvidia)
+    }
+
+    fn amd(&self) -> Vec<&str> {
+        section_or(&self.amd, &Self::defaults().amd)
+    }
+
+    fn aur(&self) -> Vec<&str> {
+        section_or(&self.aur, &Self::defaults().aur)
+    }
+
+    fn driver_pins(&self) -> Vec<DriverPin> {
+        self.nvidia_driver
+            .as_ref()
+            .or(Self::defaults().nvidia_driver.as_ref())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Crate names under sysScripts/ to build, or None to build all of them.
+    fn rust_apps(&self) -> Option<&[String]> {
+        self.rust_apps.as_ref().map(|s| s.packages.as_slice())
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum AurHelper {
+    Yay,
+    Paru,
+}
+
+impl AurHelper {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "yay" => Some(AurHelper::Yay),
+            "paru" => Some(AurHelper::Paru),
+            _ => None,
+        }
+    }
+
+    fn bin(self) -> &'static str {
+        match self {
+            AurHelper::Yay => "yay",
+            AurHelper::Paru => "paru",
+        }
+    }
+
+    fn repo_url(self) -> &'static str {
+        match self {
+            AurHelper::Yay => "https://aur.archlinux.org/yay.git",
+            AurHelper::Paru => "https://aur.archlinux.org/paru.git",
+        }
+    }
+
+    fn other(self) -> Self {
+        match self {
+            AurHelper::Yay => AurHelper::Paru,
+            AurHelper::Paru => AurHelper::Yay,
+        }
+    }
+
+    fn is_installed(self) -> bool {
+        Command::new("which")
+            .arg(self.bin())
+            .stdout(Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success())
+    }
+
+    /// The requested helper if installed, else whichever one already is, else the
+    /// requested one (which `install_aur_packages` then bootstraps).
+    fn resolve(requested: Self) -> Self {
+        if !requested.is_installed() && requested.other().is_installed() {
+            status!(
+                "   ℹ️  '{}' not found, using the installed '{}' instead.",
+                requested.bin(),
+                requested.other().bin()
+            );
+            return requested.other();
+        }
+        requested
+    }
+}
+
+fn section_or<'a>(
+    section: &'a Option<PackageSection>,
+    default: &'a Option<PackageSection>,
+) -> Vec<&'a str> {
+    section
+        .as_ref()
+        .or(default.as_ref())
+        .map(|section| section.packages.iter().map(String::as_str).collect())
+        .unwrap_or_default()
+}
+
+// --- Resumable Phases ---
+// Each major block of main() records itself in ~/.cache/rust_installer_state.json when
+// it finishes. `--resume` skips the recorded phases, so a crash in e.g. rust-apps doesn't
+// mean reinstalling every package. Runs without `--resume` start a fresh state, and a run
+// that reaches the end deletes it. (The driver reboot checkpoint is separate and predates this.)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Drivers,
+    Packages,
+    Aur,
+    RustApps,
+    SystemConfig,
+    Dotfiles,
+    Secrets,
+    Finalize,
+}
+
+impl Phase {
+    fn name(self) -> &'static str {
+        match self {
+            Phase::Drivers => "drivers",
+            Phase::Packages => "packages",
+            Phase::Aur => "aur",
+            Phase::RustApps => "rust-apps",
+            Phase::SystemConfig => "system-config",
+            Phase::Dotfiles => "dotfiles",
+            Phase::Secrets => "secrets",
+            Phase::Finalize => "finalize",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct StepState {
+    /// Whether the interrupted run was a fresh install. Kept so a resumed run doesn't
+    /// switch to update mode just because the secrets phase already wrote config.toml.
+    fresh_install: bool,
+    completed: Vec<String>,
+}
+
+struct Progress {
+    path: PathBuf,
+    resume: bool,
+    state: StepState,
+}
+
+impl Progress {
+    fn start(home: &Path, resume: bool, fresh_install: bool) -> Self {
+        let path = home.join(".cache/rust_installer_state.json");
+        let saved = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<StepState>(&content).ok());
+        let state = match saved {
+            Some(saved) if resume => {
+                status!(
+                    "{}",
+                    format!(
+                        "⏩ Resuming. Completed phases: {}",
+                        saved.completed.join(", ")
+                    )
+                    .magenta()
+                    .bold()
+                );
+                saved
+            }
+            _ => {
+                if resume {
+                    status!("   ℹ️  No interrupted install to resume. Starting from the top.");
+                }
+                StepState {
+                    fresh_install,
+                    completed: Vec::new(),
+                }
+            }
+        };
+        let progress = Progress {
+            path,
+            resume,
+            state,
+        };
+        progress.save();
+        progress
+    }
+
+    /// True (and says so) when resuming past a phase an earlier run finished.
+    fn skip(&self, phase: Phase) -> bool {
+        let done = self.resume && self.state.completed.iter().any(|p| p == phase.name());
+        if done {
+            status!(
+                "\n   ⏭️  Skipping {} (completed in a previous run).",
+                phase.name()
+            );
+        }
+        done
+    }
+
+    fn complete(&mut self, phase: Phase) {
+        if !self.state.completed.iter().any(|p| p == phase.name()) {
+            self.state.completed.push(phase.name().to_string());
+        }
+        self.save();
+    }
+
+    fn save(&self) {
+        if dry_run() {
+            return;
+        }
+        if let Some(parent) = self.path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_string_pretty(&self.state)
+            && let Err(e) = fs::write(&self.path, json)
+        {
+            eprintln!("   ⚠️ Failed to save install progress: {}", e);
+        }
+    }
+
+    /// The run got to the end; nothing left to resume.
+    fn finish(self) {
+        if !dry_run() {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
 // ---------- Main Execution ------_-------
 
 // ---------- Main Execution -----------------
 // ---------- Main Execution -----------------
 fn main() {
+    // 0. Parse Arguments
+    let args: Vec<String> = std::env::args().collect();
+    let refresh_mode = args.contains(&"--refresh-configs".to_string());
+    let resume = args.contains(&"--resume".to_string());
+    QUIET.store(args.contains(&"--quiet".to_string()), Ordering::Relaxed);
+    DRY_RUN.store(args.contains(&"--dry-run".to_string()), Ordering::Relaxed);
+    UNATTENDED.store(
+        args.iter().any(|a| a == "--unattended" || a == "--answers"),
+        Ordering::Relaxed,
+    );
+    if let Some(i) = args.iter().position(|a| a == "--answers") {
+        let Some(path) = args.get(i + 1) else {
+            eprintln!("❌ --answers expects a path to a TOML answers file.");
+            std::process::exit(1);
+        };
+        match load_answers(Path::new(path)) {
+            Ok(loaded) => {
+                let _ = ANSWERS.set(loaded);
+            }
+            Err(e) => {
+                eprintln!("❌ Failed to load answers from {}: {}", path, e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // `colored` already honors NO_COLOR, but set it explicitly so `--no-color`
+    // and an empty-but-set NO_COLOR behave the same when piped into a log.
+    if args.contains(&"--no-color".to_string()) || std::env::var_os("NO_COLOR").is_some() {
+        colored::control::set_override(false);
+    }
+
     let home = dirs::home_dir().unwrap_or_else(|| {
         eprintln!(
             "{}",
@@ -97,8 +509,17 @@ fn main() {
         eprintln!("The script is designed to safely elevate privileges internally when needed.");
         std::process::exit(1);
     }
+    if args.contains(&"--restore-backups".to_string()) {
+        if let Err(e) = restore_system_backups() {
+            eprintln!("{}", format!("❌ Failed to restore backups: {}", e).red());
+            std::process::exit(1);
+        }
+        return;
+    }
     let previous_repo_root = read_repo_root_from_config(&home);
     let has_existing_install = home.join(".config/rust-dotfiles/config.toml").exists();
+    let mut progress = Progress::start(&home, resume, !has_existing_install);
+    let has_existing_install = !progress.state.fresh_install;
 
     migrate_legacy_users(&home);
 
@@ -107,13 +528,45 @@ fn main() {
         std::process::exit(1);
     });
 
-    // 0. Parse Arguments
-    let args: Vec<String> = std::env::args().collect();
-    let refresh_mode = args.contains(&"--refresh-configs".to_string());
+    let manifest = load_package_manifest(&repo_root).unwrap_or_else(|e| {
+        eprintln!("❌ {}", e);
+        std::process::exit(1);
+    });
+
+    // --aur-helper <yay|paru> beats packages.toml, which beats the yay default
+    let requested_helper = match args.iter().position(|a| a == "--aur-helper") {
+        Some(i) => args
+            .get(i + 1)
+            .and_then(|name| AurHelper::parse(name))
+            .unwrap_or_else(|| {
+                eprintln!("❌ --aur-helper expects 'yay' or 'paru'.");
+                std::process::exit(1);
+            }),
+        None => manifest.aur_helper.unwrap_or(AurHelper::Yay),
+    };
+    let aur_helper = AurHelper::resolve(requested_helper);
+    let driver_pins = manifest.driver_pins();
+
+    if dry_run() {
+        println!(
+            "{}",
+            "🔍 DRY RUN: nothing will be installed, written or enabled."
+                .cyan()
+                .bold()
+        );
+    }
+    if unattended() {
+        status!(
+            "{}",
+            "🤖 UNATTENDED: answers come from --answers; missing ones are left blank."
+                .cyan()
+                .bold()
+        );
+    }
 
     if refresh_mode {
-        println!("{}", "🔄 Running in CONFIG REFRESH MODE".magenta().bold());
-        let status = Command::new("sudo").arg("-v").status().unwrap();
+        status!("{}", "🔄 Running in CONFIG REFRESH MODE".magenta().bold());
+        let status = Command::new("sudo").arg("-v").run_status().unwrap();
         if !status.success() {
             eprintln!("{}", "❌ Sudo required.".red());
             std::process::exit(1);
@@ -122,7 +575,7 @@ fn main() {
         // ==========================================
         //  FULL INSTALL MODE (Fresh Install Only)
         // ==========================================
-        println!(
+        status!(
             "{}",
             "🚀 Starting Rust Wayland Power Installation..."
                 .green()
@@ -131,13 +584,13 @@ fn main() {
 
         let status = Command::new("sudo")
             .arg("-v")
-            .status()
+            .run_status()
             .expect("Failed to sudo");
         if !status.success() {
             std::process::exit(1);
         }
 
-        println!(
+        status!(
             "\n{}",
             "⚔️  Resolving Audio Conflicts (Removing jack2)...".yellow()
         );
@@ -147,69 +600,78 @@ fn main() {
             .status()
             .is_ok_and(|s| s.success())
         {
-            println!("   👉 Detected 'jackd' in PATH. Removing 'jack2' to prevent conflicts...");
+            status!("   👉 Detected 'jackd' in PATH. Removing 'jack2' to prevent conflicts...");
             let _ = Command::new("sudo")
                 .args(["pacman", "-Rdd", "--noconfirm", "jack2"])
                 .stdout(Stdio::null())
                 .stderr(Stdio::null())
-                .status();
+                .run_status();
         } else {
-            println!("   ✅ No JACK audio server detected. Skipping removal.");
+            status!("   ✅ No JACK audio server detected. Skipping removal.");
         }
 
         // GPU Drivers Checkpoint & Exit Logic
         let state_file = home.join(".cache/rust_installer_drivers_done");
 
         if state_file.exists() {
-            println!(
+            status!(
                 "\n{}",
                 "✅ Drivers already installed (Checkpoint found). Skipping to prevent crash."
                     .green()
             );
-        } else {
-            println!(
+        } else if !progress.skip(Phase::Drivers) {
+            status!(
                 "\n{}",
                 "🔍 Detecting GPU Hardware & Installing Base Drivers..."
                     .blue()
                     .bold()
             );
-            let gpu = detect_gpu();
+            let gpu = choose_gpu(&driver_pins, &home);
             match gpu {
-                GpuVendor::Nvidia(NvidiaArch::Turing) => {
-                    println!("   👉 NVIDIA Turing Detected (GTX 16xx / RTX 20xx).");
-                    if let Err(e) = setup_turing_gpu() {
+                GpuVendor::Nvidia(NvidiaArch::Pinned(pin)) => {
+                    status!(
+                        "   👉 NVIDIA {} Detected (pinned to {}).",
+                        pin.name,
+                        pin.packages[0]
+                    );
+                    if let Err(e) = setup_pinned_nvidia(&pin, aur_helper) {
                         eprintln!("   ❌ Failed to install legacy NVIDIA drivers: {}", e);
                         std::process::exit(1);
                     }
                 }
                 GpuVendor::Nvidia(NvidiaArch::Modern) => {
-                    println!("   👉 Modern NVIDIA Detected (RTX 30xx/40xx).");
-                    if let Err(e) = install_pacman_packages(NVIDIA_PACKAGES) {
+                    status!("   👉 Modern NVIDIA Detected (RTX 30xx/40xx).");
+                    if let Err(e) = install_pacman_packages(&manifest.nvidia()) {
                         eprintln!("   ❌ Failed to install NVIDIA drivers: {}", e);
                         std::process::exit(1);
                     }
                 }
                 GpuVendor::Amd => {
-                    println!("   👉 AMD Detected.");
-                    if let Err(e) = install_pacman_packages(AMD_PACKAGES) {
+                    status!("   👉 AMD Detected.");
+                    if let Err(e) = install_pacman_packages(&manifest.amd()) {
                         eprintln!("   ❌ Failed to install AMD drivers: {}", e);
                         std::process::exit(1);
                     }
                 }
-                GpuVendor::Intel => println!("   👉 Intel Detected (Drivers in common)."),
-                GpuVendor::Unknown => println!("   ⚠️  No dedicated GPU detected."),
+                GpuVendor::Intel => status!("   👉 Intel Detected (Drivers in common)."),
+                GpuVendor::Unknown => status!("   ⚠️  No dedicated GPU detected."),
             }
 
             let is_gui =
                 std::env::var("WAYLAND_DISPLAY").is_ok() || std::env::var("DISPLAY").is_ok();
 
-            if is_gui {
+            if is_gui
+                && !dry_run_skip(
+                    "save the driver checkpoint and ask to reboot (a real run stops here)",
+                )
+            {
                 println!("\n{}", "⚠️  GRAPHICS DRIVERS INSTALLED".yellow().bold());
                 println!("We must reboot to load the new kernel modules safely.");
 
                 if let Ok(mut file) = fs::File::create(&state_file) {
                     writeln!(file, "Drivers installed successfully.").unwrap();
                 }
+                progress.complete(Phase::Drivers);
 
                 println!(
                     "{}",
@@ -217,19 +679,26 @@ fn main() {
                         .green()
                         .bold()
                 );
-                let should_reboot = inquire::Confirm::new("Reboot now?")
-                    .with_default(true)
-                    .prompt()
-                    .unwrap_or(true);
+                let should_reboot = if unattended() {
+                    answers().reboot
+                } else {
+                    inquire::Confirm::new("Reboot now?")
+                        .with_default(true)
+                        .prompt()
+                        .unwrap_or(true)
+                };
                 if should_reboot {
-                    let _ = Command::new("sudo").arg("reboot").status();
+                    let _ = Command::new("sudo").arg("reboot").run_status();
                 }
                 std::process::exit(0);
             }
+            progress.complete(Phase::Drivers);
         }
 
-        println!("\n{}", "🦀 Setting up Rust (rustup)...".blue().bold());
-        let _ = Command::new("rustup").args(["default", "stable"]).status();
+        status!("\n{}", "🦀 Setting up Rust (rustup)...".blue().bold());
+        let _ = Command::new("rustup")
+            .args(["default", "stable"])
+            .run_status();
     }
 
     // ==========================================
@@ -237,61 +706,76 @@ fn main() {
     // ==========================================
 
     // 1. Sync Standard & AUR Packages
-    println!("\n{}", "📦 Syncing Standard Packages...".blue().bold());
-    let mut common_pkgs = match load_packages_from_file("pkglist.txt", &repo_root) {
-        Ok(pkgs) => pkgs,
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-            println!("   ⚠️  pkglist.txt not found. Skipping package installation.");
-            Vec::new()
-        }
-        Err(e) => {
-            eprintln!("   ❌ Failed to read pkglist.txt: {}", e);
-            std::process::exit(1);
-        }
-    };
+    if !progress.skip(Phase::Packages) {
+        status!("\n{}", "📦 Syncing Standard Packages...".blue().bold());
+        let common_from_file = match &manifest.common {
+            Some(section) => {
+                status!("   ✅ Using [common] from {}.", PACKAGE_MANIFEST);
+                Ok(section.packages.clone())
+            }
+            None => load_packages_from_file("pkglist.txt", &repo_root),
+        };
+        let mut common_pkgs = match common_from_file {
+            Ok(pkgs) => pkgs,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                status!("   ⚠️  pkglist.txt not found. Skipping package installation.");
+                Vec::new()
+            }
+            Err(e) => {
+                eprintln!("   ❌ Failed to read pkglist.txt: {}", e);
+                std::process::exit(1);
+            }
+        };
 
-    let ignored_pkgs = get_ignored_packages();
-    common_pkgs.retain(|pkg| !ignored_pkgs.contains(pkg));
+        let ignored_pkgs = get_ignored_packages();
+        common_pkgs.retain(|pkg| !ignored_pkgs.contains(pkg));
 
-    if common_pkgs.is_empty() {
-        println!("   ⚠️  No packages found in pkglist.txt.");
-    } else {
-        let pkg_refs: Vec<&str> = common_pkgs.iter().map(|s| s.as_str()).collect();
-        if let Err(e) = install_pacman_packages(&pkg_refs) {
-            eprintln!("   ❌ Failed to install standard packages: {}", e);
-            std::process::exit(1);
-        };
+        if common_pkgs.is_empty() {
+            status!("   ⚠️  No packages found in pkglist.txt.");
+        } else {
+            let pkg_refs: Vec<&str> = common_pkgs.iter().map(|s| s.as_str()).collect();
+            if let Err(e) = install_pacman_packages(&pkg_refs) {
+                eprintln!("   ❌ Failed to install standard packages: {}", e);
+                std::process::exit(1);
+            };
+        }
+        progress.complete(Phase::Packages);
     }
 
-    if !AUR_PACKAGES.is_empty() {
-        println!("\n{}", "📦 Syncing AUR Packages...".blue().bold());
-        if let Err(e) = install_aur_packages(&home) {
-            eprintln!("   ❌ Failed to install AUR packages: {}", e);
-        };
+    let aur_pkgs = manifest.aur();
+    if !aur_pkgs.is_empty() && !progress.skip(Phase::Aur) {
+        status!("\n{}", "📦 Syncing AUR Packages...".blue().bold());
+        match install_aur_packages(&home, aur_helper, &aur_pkgs) {
+            Ok(()) => progress.complete(Phase::Aur),
+            Err(e) => eprintln!("   ❌ Failed to install AUR packages: {}", e),
+        }
     }
 
     // 2. Re-compile Rust Apps (Ensures updates to your tools are applied)
-    println!("\n{}", "🦀 Syncing Custom Rust Apps...".blue().bold());
-    // GUARANTEE Rust toolchain is loaded and set to stable (fixes GUI launcher bug)
-    let _ = Command::new("rustup")
-        .args(["default", "stable"])
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .status();
-    if let Err(e) = build_custom_apps(&home, &repo_root) {
-        println!("   ⚠️  Failed to build custom Rust apps: {}", e);
-    };
+    if !progress.skip(Phase::RustApps) {
+        status!("\n{}", "🦀 Syncing Custom Rust Apps...".blue().bold());
+        // GUARANTEE Rust toolchain is loaded and set to stable (fixes GUI launcher bug)
+        let _ = Command::new("rustup")
+            .args(["default", "stable"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .run_status();
+        match build_custom_apps(&home, &repo_root, manifest.rust_apps()) {
+            Ok(()) => progress.complete(Phase::RustApps),
+            Err(e) => eprintln!("   ⚠️  Failed to build custom Rust apps: {}", e),
+        }
+    }
 
     let update_only_mode = has_existing_install && !refresh_mode;
     if update_only_mode {
-        println!(
+        status!(
             "\n{}",
             "ℹ️  Existing install detected. Limiting this run to pkglist + sysScripts sync."
                 .yellow()
                 .bold()
         );
-    } else {
-        println!(
+    } else if !progress.skip(Phase::SystemConfig) {
+        status!(
             "\n{}",
             "⚙️  Applying System Configurations...".blue().bold()
         );
@@ -300,13 +784,13 @@ fn main() {
         }
 
         // 3. Hardware Enforcement
-        let current_gpu = detect_gpu();
+        let current_gpu = choose_gpu(&driver_pins, &home);
 
         let is_nvidia = if let GpuVendor::Nvidia(arch) = current_gpu {
-            if arch == NvidiaArch::Turing
-                && let Err(e) = setup_turing_gpu()
+            if let NvidiaArch::Pinned(pin) = &arch
+                && let Err(e) = setup_pinned_nvidia(pin, aur_helper)
             {
-                eprintln!("   ❌ Failed to set up Turing NVIDIA drivers: {}", e);
+                eprintln!("   ❌ Failed to set up pinned NVIDIA drivers: {}", e);
                 std::process::exit(1);
             }
             if let Err(e) = apply_nvidia_configs(&arch) {
@@ -324,13 +808,14 @@ fn main() {
         if let Err(e) = setup_battery_daemon(&home) {
             eprintln!("   ❌ Failed to set up battery-daemon: {}", e);
         }
+        progress.complete(Phase::SystemConfig);
     }
 
     // 5. Finalize
     if !refresh_mode {
         if has_existing_install {
             // --- UPDATE MODE (safe for personal configs) ---
-            println!(
+            status!(
                 "\n{}",
                 "🔧 Repairing managed symlink targets...".blue().bold()
             );
@@ -350,26 +835,35 @@ fn main() {
             );
         } else {
             // --- FRESH INSTALL ONLY ---
-            println!("\n{}", "🔗 Linking Config Files...".blue().bold());
-            link_dotfiles_and_copy_resources(&home, &repo_root);
+            if !progress.skip(Phase::Dotfiles) {
+                status!("\n{}", "🔗 Linking Config Files...".blue().bold());
+                link_dotfiles_and_copy_resources(&home, &repo_root);
 
-            if let Err(e) = configure_system(&home) {
-                eprintln!("   ❌ Failed to configure system services: {}", e);
-                std::process::exit(1);
-            }
+                if let Err(e) = configure_system(&home) {
+                    eprintln!("   ❌ Failed to configure system services: {}", e);
+                    std::process::exit(1);
+                }
 
-            if let Err(e) = setup_librewolf(&home) {
-                eprintln!("   ⚠️ Failed to configure LibreWolf: {}", e);
+                if let Err(e) = setup_librewolf(&home) {
+                    eprintln!("   ⚠️ Failed to configure LibreWolf: {}", e);
+                }
+                setup_waybar_configs(&home);
+                patch_waybar_sidebar_toggle_path(&home);
+                progress.complete(Phase::Dotfiles);
             }
-            setup_waybar_configs(&home);
-            patch_waybar_sidebar_toggle_path(&home);
-            if let Err(e) = setup_secrets_and_geoclue(&home) {
-                eprintln!("   ⚠️ Failed to set up secrets and geoclue: {}", e);
+            if !progress.skip(Phase::Secrets) {
+                if let Err(e) = setup_secrets_and_geoclue(&home) {
+                    eprintln!("   ⚠️ Failed to set up secrets and geoclue: {}", e);
+                }
+                if let Err(e) = write_repo_root(&repo_root) {
+                    eprintln!("   ⚠️ Failed to write repository root to config: {}", e);
+                }
+                progress.complete(Phase::Secrets);
             }
-            if let Err(e) = write_repo_root(&repo_root) {
-                eprintln!("   ⚠️ Failed to write repository root to config: {}", e);
+            if !progress.skip(Phase::Finalize) {
+                finalize_setup(&home); // Neovim/Tmux plugins
+                progress.complete(Phase::Finalize);
             }
-            finalize_setup(&home); // Neovim/Tmux plugins
 
             print_logo();
             println!(
@@ -379,7 +873,7 @@ fn main() {
         }
     } else {
         // --- REFRESH MODE (Updater) ---
-        println!(
+        status!(
             "\n{}",
             "🔧 Repairing managed symlink targets...".blue().bold()
         );
@@ -398,6 +892,7 @@ fn main() {
                 .bold()
         );
     }
+    progress.finish();
 }
 
 // --- Helper functions ---
@@ -409,18 +904,26 @@ fn migrate_legacy_users(home: &Path) {
 
     // If the old repo exists, we have a legacy user who needs rescuing
     if old_repo.exists() {
-        println!(
+        status!(
             "\n{}",
             "🔄 Legacy installation detected. Silently migrating system...".magenta()
         );
+        if dry_run_skip(&format!(
+            "move {} to {} and point its origin at Mccalabrese/Genoa",
+            old_repo.display(),
+            new_repo.display()
+        )) {
+            return;
+        }
 
         // 1. Move the physical folder to the new name
         // (This is safe because this binary is currently running from ~/.cargo/bin/)
         if !new_repo.exists()
-            && let Err(e) = fs::rename(&old_repo, &new_repo) {
-                eprintln!("   ⚠️ Failed to rename repository folder: {}", e);
-                return; // Abort migration, let them safely remain on the old folder for now
-            }
+            && let Err(e) = fs::rename(&old_repo, &new_repo)
+        {
+            eprintln!("   ⚠️ Failed to rename repository folder: {}", e);
+            return; // Abort migration, let them safely remain on the old folder for now
+        }
 
         let active_repo = if new_repo.exists() {
             &new_repo
@@ -459,7 +962,7 @@ fn migrate_legacy_users(home: &Path) {
         // 3. Generate the new config.toml and burn the new path into it
         let _ = write_repo_root(active_repo);
 
-        println!("   ✅ Migration complete. Welcome to the new architecture.");
+        status!("   ✅ Migration complete. Welcome to the new architecture.");
     }
 }
 
@@ -471,9 +974,19 @@ fn write_repo_root(repo_root: &Path) -> Result<(), std::io::Error> {
     let repo_root_str = repo_root
         .to_str()
         .ok_or_else(|| std::io::Error::other("Invalid repo root path"))?;
-    let config_str = fs::read_to_string(&config_path)?;
+    let config_str = match fs::read_to_string(&config_path) {
+        // In a dry run the config was never generated, so there's nothing to update
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && dry_run() => return Ok(()),
+        result => result?,
+    };
     let updated_toml = upsert_repo_root_in_config(&config_str, repo_root_str);
-    if updated_toml != config_str {
+    if updated_toml != config_str
+        && !dry_run_skip(&format!(
+            "set repo.root = {:?} in {}",
+            repo_root_str,
+            config_path.display()
+        ))
+    {
         fs::write(&config_path, updated_toml)?;
     }
     Ok(())
@@ -534,7 +1047,7 @@ fn load_packages_from_file(filename: &str, repo_root: &Path) -> std::io::Result<
     let path = repo_root.join(filename);
 
     let content = fs::read_to_string(&path)?;
-    println!("   ✅ Loaded package list from '{}'.", filename);
+    status!("   ✅ Loaded package list from '{}'.", filename);
     Ok(content
         .lines()
         .map(str::trim)
@@ -543,12 +1056,56 @@ fn load_packages_from_file(filename: &str, repo_root: &Path) -> std::io::Result<
         .collect::<Vec<String>>())
 }
 
+/// Reads `packages.toml` from next to the running binary, falling back to the repo root.
+/// No manifest at all means the built-in lists; a manifest that fails to parse is an error
+/// rather than a silent fallback, so a typo can't quietly install the wrong set.
+fn load_package_manifest(repo_root: &Path) -> Result<PackageManifest, std::io::Error> {
+    let beside_binary = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(PACKAGE_MANIFEST)));
+    let candidates = beside_binary
+        .into_iter()
+        .chain(std::iter::once(repo_root.join(PACKAGE_MANIFEST)));
+
+    for path in candidates {
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        let manifest: PackageManifest = toml::from_str(&content).map_err(|e| {
+            std::io::Error::other(format!("Failed to parse {}: {}", path.display(), e))
+        })?;
+        for pin in manifest.nvidia_driver.iter().flatten() {
+            if let Err(e) = regex::Regex::new(&pin.device_ids) {
+                return Err(std::io::Error::other(format!(
+                    "Invalid device_ids for nvidia_driver '{}' in {}: {}",
+                    pin.name,
+                    path.display(),
+                    e
+                )));
+            }
+            if pin.packages.is_empty() {
+                return Err(std::io::Error::other(format!(
+                    "nvidia_driver '{}' in {} has no packages",
+                    pin.name,
+                    path.display()
+                )));
+            }
+        }
+        status!("   ✅ Loaded package manifest from {}", path.display());
+        return Ok(manifest);
+    }
+    Ok(PackageManifest::default())
+}
+
 /// Parses `lspci` output to identify GPU vendor IDs.
 /// 10de = NVIDIA, 1002 = AMD, 8086 = Intel.
-fn detect_gpu() -> GpuVendor {
+/// NVIDIA cards are checked against `pins` in order; the first matching rule wins.
+fn detect_gpus(pins: &[DriverPin]) -> Vec<GpuVendor> {
     let Ok(entries) = std::fs::read_dir("/sys/bus/pci/devices") else {
         eprintln!("⚠️ Failed to read PCI devices. Defaulting to Unknown");
-        return GpuVendor::Unknown;
+        return Vec::new();
     };
     let mut gpus = Vec::new();
 
@@ -569,15 +1126,13 @@ fn detect_gpu() -> GpuVendor {
             // VGA Controller
             match vendor_hex.trim() {
                 "0x10de" => {
-                    let dev = device_hex.trim();
-                    if TURING_IDS.contains(&dev)
-                        || dev.starts_with("0x1e")
-                        || dev.starts_with("0x1f")
-                        || dev.starts_with("0x21")
-                    {
-                        gpus.push(GpuVendor::Nvidia(NvidiaArch::Turing));
-                    } else {
-                        gpus.push(GpuVendor::Nvidia(NvidiaArch::Modern));
+                    let dev = device_hex.trim().to_lowercase();
+                    let pin = pins.iter().find(|pin| {
+                        regex::Regex::new(&pin.device_ids).is_ok_and(|re| re.is_match(&dev))
+                    });
+                    match pin {
+                        Some(pin) => gpus.push(GpuVendor::Nvidia(NvidiaArch::Pinned(pin.clone()))),
+                        None => gpus.push(GpuVendor::Nvidia(NvidiaArch::Modern)),
                     }
                 }
                 "0x1002" => gpus.push(GpuVendor::Amd),
@@ -586,7 +1141,78 @@ fn detect_gpu() -> GpuVendor {
             }
         }
     }
-    gpus.into_iter().max().unwrap_or(GpuVendor::Unknown) // If multiple GPUs, we prioritize NVIDIA > AMD > Intel
+    // Best first: if multiple GPUs, we prioritize NVIDIA > AMD > Intel
+    gpus.sort_by(|a, b| b.cmp(a));
+    gpus.dedup_by(|a, b| a.key() == b.key()); // One entry per vendor (two NVIDIA cards ask nothing)
+    gpus
+}
+
+impl GpuVendor {
+    /// Stable name for the saved choice file.
+    fn key(&self) -> &'static str {
+        match self {
+            GpuVendor::Nvidia(_) => "nvidia",
+            GpuVendor::Amd => "amd",
+            GpuVendor::Intel => "intel",
+            GpuVendor::Unknown => "unknown",
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            GpuVendor::Nvidia(NvidiaArch::Pinned(pin)) => {
+                format!("NVIDIA {} ({})", pin.name, pin.packages[0])
+            }
+            GpuVendor::Nvidia(NvidiaArch::Modern) => "NVIDIA (nvidia-dkms)".to_string(),
+            GpuVendor::Amd => "AMD (vulkan-radeon)".to_string(),
+            GpuVendor::Intel => "Intel (no extra driver packages)".to_string(),
+            GpuVendor::Unknown => "None".to_string(),
+        }
+    }
+}
+
+/// Which driver path to take. With one GPU vendor (or none) that's the detected one.
+/// Hybrid machines (e.g. Intel iGPU + NVIDIA dGPU) ask, with the auto-pick preselected,
+/// and the answer is saved so the post-reboot run enforces the same drivers. Unattended
+/// runs and runs without a terminal take `gpu` from the answers file, else the auto-pick.
+fn choose_gpu(pins: &[DriverPin], home: &Path) -> GpuVendor {
+    let mut gpus = detect_gpus(pins);
+    if gpus.len() <= 1 {
+        return gpus.pop().unwrap_or(GpuVendor::Unknown);
+    }
+
+    let choice_file = home.join(".cache/rust_installer_gpu_choice");
+    if let Ok(saved) = fs::read_to_string(&choice_file)
+        && let Some(index) = gpus.iter().position(|g| g.key() == saved.trim())
+    {
+        status!("   👉 Using the saved GPU choice: {}", gpus[index].label());
+        return gpus.swap_remove(index);
+    }
+
+    if unattended() || !std::io::stdin().is_terminal() {
+        let index = answers()
+            .gpu
+            .as_deref()
+            .and_then(|key| gpus.iter().position(|g| g.key() == key))
+            .unwrap_or(0);
+        return gpus.swap_remove(index);
+    }
+    let labels: Vec<String> = gpus.iter().map(GpuVendor::label).collect();
+    let index = match inquire::Select::new(
+        "Multiple GPU vendors detected. Which driver path should be installed?",
+        labels,
+    )
+    .with_starting_cursor(0)
+    .raw_prompt()
+    {
+        Ok(choice) => choice.index,
+        Err(_) => 0, // Esc / no input: keep the auto-pick
+    };
+    let gpu = gpus.swap_remove(index);
+    if !dry_run() {
+        let _ = fs::write(&choice_file, gpu.key());
+    }
+    gpu
 }
 
 /// Scans /sys/class/drm to find the integrated GPU (Intel or AMD).
@@ -625,15 +1251,16 @@ fn find_igpu() -> Option<(String, String)> {
 }
 
 /// 1. Check if user is on old drivers and ignoring updates in their pacman conf.
-/// 2. If they are installingg from scratch, just install AUR nvidia-580-dkms which supports Turing and older cards on newer kernels.
+/// 2. If they are installingg from scratch, just install the pinned AUR driver (e.g. nvidia-580xx-dkms,
+///    which supports Turing and older cards on newer kernels).
 /// 3. For users on old drivers, halt&warn, execute removing ignore line from pacman conf, pacman
 ///    -Rdd old drivers, install mainline kernel, install AUR drivers, run mkinicpio and
 ///    grub-mkconfig if user is on grub, and force reboot to load the new drivers safely.
-fn setup_turing_gpu() -> Result<(), std::io::Error> {
+fn setup_pinned_nvidia(pin: &DriverPin, aur_helper: AurHelper) -> Result<(), std::io::Error> {
     let pacman_conf = "/etc/pacman.conf";
     let pac_conf_content = fs::read_to_string(pacman_conf)?;
     let drivers_installed = Command::new("pacman")
-        .args(["-Q", "nvidia-580xx-dkms"])
+        .args(["-Q", pin.packages[0].as_str()])
         .stdout(Stdio::null())
         .status()
         .is_ok_and(|s| s.success());
@@ -643,7 +1270,9 @@ fn setup_turing_gpu() -> Result<(), std::io::Error> {
             && trimmed.starts_with("IgnorePkg")
             && (trimmed.contains("nvidia") || trimmed.contains("nvidia-dkms"))
     });
+    // Unattended runs take the prompt's default and migrate
     if is_legacy_nvidia
+        && !unattended()
         && !inquire::Confirm::new("⚠️  Legacy NVIDIA configuration detected. We need to migrate you to the new AUR drivers to restore mainline kernel support. This will rebuild your drivers and reboot your computer. Proceed?").with_default(true).prompt().unwrap_or(false) {        
             std::process::exit(1);
         }
@@ -680,6 +1309,7 @@ fn setup_turing_gpu() -> Result<(), std::io::Error> {
         }
     }
     if config_modified {
+        backup_system_file(pacman_conf)?;
         let mut temp_file = NamedTempFile::new()?;
         write!(temp_file, "{}", lines.join("\n"))?;
         run_cmd(
@@ -721,19 +1351,15 @@ fn setup_turing_gpu() -> Result<(), std::io::Error> {
         )?; // Ensure mainline kernel is installed
     }
     if is_legacy_nvidia || !drivers_installed {
-        println!("   👉 Installing legacy NVIDIA drivers from AUR...");
-        run_cmd(
-            "yay",
-            &[
-                "-S",
-                "--noconfirm",
-                "nvidia-580xx-dkms",
-                "nvidia-580xx-utils",
-                "nvidia-580xx-settings",
-                "libva-nvidia-driver",
-            ],
-        )?;
-        let _ = run_cmd("yay", &["-S", "--noconfirm", "lib32-nvidia-580xx-utils"]); // Install 32-bit
+        status!("   👉 Installing pinned NVIDIA drivers from AUR...");
+        let mut args = vec!["-S", "--noconfirm"];
+        args.extend(pin.packages.iter().map(String::as_str));
+        run_cmd(aur_helper.bin(), &args)?;
+        if !pin.lib32_packages.is_empty() {
+            let mut args = vec!["-S", "--noconfirm"];
+            args.extend(pin.lib32_packages.iter().map(String::as_str));
+            let _ = run_cmd(aur_helper.bin(), &args); // Install 32-bit
+        }
     }
     if is_legacy_nvidia || !drivers_installed {
         run_cmd("sudo", &["mkinitcpio", "-P"])?; // Regenerate initramfs
@@ -748,18 +1374,18 @@ fn setup_turing_gpu() -> Result<(), std::io::Error> {
 
 /// Generates the sway-hybrid wrapper script with DYNAMIC paths.
 fn create_sway_hybrid_script() -> Result<bool, std::io::Error> {
-    println!("   🔧 Generating dynamic Sway-Hybrid wrapper...");
+    status!("   🔧 Generating dynamic Sway-Hybrid wrapper...");
 
     // 1. Find the iGPU
     let (card_path, vendor) = match find_igpu() {
         Some(tuple) => tuple,
         None => {
-            println!("   ⚠️  Could not detect iGPU. Defaulting to /dev/dri/card1 (Risky!)");
+            status!("   ⚠️  Could not detect iGPU. Defaulting to /dev/dri/card1 (Risky!)");
             ("/dev/dri/card1".to_string(), "intel".to_string())
         }
     };
 
-    println!("      👉 iGPU Found: {} ({})", card_path, vendor);
+    status!("      👉 iGPU Found: {} ({})", card_path, vendor);
 
     // 2. Determine Vulkan JSON path based on vendor
     let vulkan_driver = if vendor == "amd" {
@@ -799,7 +1425,7 @@ exec sway
     if fs::read_to_string(wrapper_path)
         .is_ok_and(|current_content| current_content == script_content)
     {
-        println!("   ✅ Sway-Hybrid script is already up to date. No changes made.");
+        status!("   ✅ Sway-Hybrid script is already up to date. No changes made.");
         return Ok(false);
     }
 
@@ -818,7 +1444,7 @@ exec sway
         .arg("root")
         .arg(local_tmp.path())
         .arg(wrapper_path)
-        .status()?;
+        .run_status()?;
 
     if !status.success() {
         eprintln!("{}", "❌ Failed to install sway-hybrid script.".red());
@@ -828,7 +1454,12 @@ exec sway
 }
 //-------- Main Steps ------
 fn setup_librewolf(home: &Path) -> Result<(), std::io::Error> {
-    println!("   🐺 Configuring LibreWolf for Human Beings...");
+    status!("   🐺 Configuring LibreWolf for Human Beings...");
+    if dry_run_skip(
+        "write ~/.librewolf/librewolf.overrides.cfg and make LibreWolf the default browser",
+    ) {
+        return Ok(());
+    }
 
     let wolf_dir = home.join(".librewolf");
     let override_file = wolf_dir.join("librewolf.overrides.cfg");
@@ -849,7 +1480,7 @@ fn setup_librewolf(home: &Path) -> Result<(), std::io::Error> {
     // Write it
     fs::write(&override_file, config_content)?;
     // Set as Default Browser (XDG)
-    println!("   👉 Setting LibreWolf as default browser...");
+    status!("   👉 Setting LibreWolf as default browser...");
     let mimes = [
         "text/html",
         "x-scheme-handler/http",
@@ -878,7 +1509,10 @@ fn install_pacman_packages(packages: &[&str]) -> Result<(), std::io::Error> {
     }
     let mut args = vec!["-S", "--needed", "--noconfirm"];
     args.extend(packages);
-    let status = Command::new("sudo").arg("pacman").args(&args).status()?;
+    let status = Command::new("sudo")
+        .arg("pacman")
+        .args(&args)
+        .run_status()?;
     if !status.success() {
         eprintln!(
             "{}",
@@ -886,20 +1520,20 @@ fn install_pacman_packages(packages: &[&str]) -> Result<(), std::io::Error> {
         );
         return Err(std::io::Error::other("Failed to install packages"));
     }
-    println!("   ✅ Installed packages: {}", packages.join(", "));
+    status!("   ✅ Installed packages: {}", packages.join(", "));
     Ok(())
 }
 
-/// Bootstraps 'yay' from the AUR git repo if not present.
+/// Bootstraps the AUR helper (yay or paru) from its AUR git repo if not present.
 /// This allows the script to run on a truly clean Arch install.
-fn install_aur_packages(home: &Path) -> Result<(), std::io::Error> {
-    if !Command::new("which")
-        .arg("yay")
-        .status()
-        .is_ok_and(|s| s.success())
-    {
-        println!("   ⬇️  Bootstrapping 'yay'...");
-        let clone_path = home.join("yay-clone");
+fn install_aur_packages(
+    home: &Path,
+    helper: AurHelper,
+    packages: &[&str],
+) -> Result<(), std::io::Error> {
+    if !helper.is_installed() {
+        status!("   ⬇️  Bootstrapping '{}'...", helper.bin());
+        let clone_path = home.join(format!("{}-clone", helper.bin()));
 
         if clone_path.exists() {
             let _ = fs::remove_dir_all(&clone_path);
@@ -907,27 +1541,35 @@ fn install_aur_packages(home: &Path) -> Result<(), std::io::Error> {
 
         Command::new("git")
             .arg("clone")
-            .arg("https://aur.archlinux.org/yay.git")
+            .arg(helper.repo_url())
             .arg(&clone_path)
-            .status()?;
+            .run_status()?;
 
         let status = Command::new("makepkg")
             .arg("-si")
             .arg("--noconfirm")
             .current_dir(&clone_path)
-            .status()?;
+            .run_status()?;
 
-        fs::remove_dir_all(&clone_path)?;
+        if !dry_run() {
+            fs::remove_dir_all(&clone_path)?;
+        }
 
         if !status.success() {
-            eprintln!("{}", "❌ Failed to install yay from AUR.".red());
-            return Err(std::io::Error::other("Failed to install yay"));
+            eprintln!(
+                "{}",
+                format!("❌ Failed to install {} from AUR.", helper.bin()).red()
+            );
+            return Err(std::io::Error::other(format!(
+                "Failed to install {}",
+                helper.bin()
+            )));
         }
     }
 
     let mut args = vec!["-S", "--needed", "--noconfirm"];
-    args.extend(AUR_PACKAGES);
-    let status = Command::new("yay").args(&args).status()?;
+    args.extend(packages);
+    let status = Command::new(helper.bin()).args(&args).run_status()?;
 
     if !status.success() {
         eprintln!("{}", "⚠️  AUR Warning.".yellow());
@@ -941,22 +1583,32 @@ fn install_aur_packages(home: &Path) -> Result<(), std::io::Error> {
 /// without causing issues.
 fn configure_system(home: &Path) -> Result<(), std::io::Error> {
     sanitize_mkinitcpio()?;
-    run_cmd("sudo", &["systemctl", "enable", "geoclue.service"])?;
-    run_cmd("sudo", &["systemctl", "enable", "bluetooth.service"])?;
-    run_cmd("sudo", &["systemctl", "enable", "bolt.service"])?;
+    enable_service("geoclue.service", &[])?;
+    enable_service("bluetooth.service", &[])?;
+    enable_service("bolt.service", &[])?;
     configure_dns()?;
     // Prevent Pacman from eating the entire hard drive over time
-    println!("   🧹 Enabling automated Pacman cache cleanup...");
-    run_cmd("sudo", &["systemctl", "enable", "--now", "paccache.timer"])?;
+    status!("   🧹 Enabling automated Pacman cache cleanup...");
+    enable_service("paccache.timer", &["--now"])?;
 
     // --- ENVIRONMENT & LOGIND ---
-    println!("    🔧 Configuring Session Environment (PATH)...");
+    status!("    🔧 Configuring Session Environment (PATH)...");
     let env_dir = home.join(".config/environment.d");
-    let env_file = env_dir.join("99-cargo-path.conf");
-
-    fs::create_dir_all(&env_dir)?;
-    let content = "PATH=$HOME/.cargo/bin:$PATH\n";
-    fs::write(&env_file, content)?;
+    if !dry_run() {
+        fs::create_dir_all(&env_dir)?;
+    }
+    // environment.d only covers sessions started by the systemd user manager, so the
+    // login shell profiles get the same export for greeter/TTY launched sessions.
+    ensure_cargo_path(
+        &env_dir.join("99-cargo-path.conf"),
+        "PATH=$HOME/.cargo/bin:$PATH",
+    )?;
+    for profile in [".profile", ".zprofile"] {
+        ensure_cargo_path(
+            &home.join(profile),
+            "export PATH=\"$HOME/.cargo/bin:$PATH\"",
+        )?;
+    }
 
     configure_logind()?;
     configure_greetd()?;
@@ -964,12 +1616,42 @@ fn configure_system(home: &Path) -> Result<(), std::io::Error> {
     Ok(())
 }
 
+/// Appends `line` to `path` unless the file already puts `~/.cargo/bin` on PATH.
+/// Existing content is never rewritten, so user edits (or a symlinked repo dotfile
+/// that already exports it) are left alone.
+fn ensure_cargo_path(path: &Path, line: &str) -> Result<(), std::io::Error> {
+    let existing = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+
+    if existing.contains(".cargo/bin") {
+        status!("      ✅ {} already includes ~/.cargo/bin", path.display());
+        return Ok(());
+    }
+
+    if dry_run_skip(&format!("append `{}` to {}", line, path.display())) {
+        return Ok(());
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    if !existing.is_empty() && !existing.ends_with('\n') {
+        writeln!(file)?;
+    }
+    writeln!(file, "{}", line)?;
+    status!("      ➕ Added ~/.cargo/bin to {}", path.display());
+    Ok(())
+}
+
 /// Cleans up the `mkinitcpio.conf` file to fix the known Archinstall 2025 bug that appends 'o"' to
 /// the end of the file,
 fn sanitize_mkinitcpio() -> Result<(), std::io::Error> {
     // --- SANITIZE MKINITCPIO (Fix Archinstall 2025 Bug) ---
     // This protects NVIDIA users from the 'o"' corruption crash.
-    println!("   🧹 Checking mkinitcpio.conf for corruption...");
+    status!("   🧹 Checking mkinitcpio.conf for corruption...");
     let mkinit_path = "/etc/mkinitcpio.conf";
 
     // Check if the file specifically ends with the garbage (ignoring whitespace)
@@ -977,7 +1659,7 @@ fn sanitize_mkinitcpio() -> Result<(), std::io::Error> {
     if let Ok(content) = fs::read_to_string(mkinit_path) {
         let trimmed = content.trim(); // Removes trailing \n
         if trimmed.ends_with("o\"") || trimmed.ends_with("o”") {
-            println!("   ⚠️  Corruption detected at end of file. Cleaning up...");
+            status!("   ⚠️  Corruption detected at end of file. Cleaning up...");
             let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
             let mut last_line = lines.pop().unwrap_or_default();
             if last_line.trim_end().ends_with("o\"") || last_line.trim_end().ends_with("o”") {
@@ -990,6 +1672,7 @@ fn sanitize_mkinitcpio() -> Result<(), std::io::Error> {
                 // If the last line doesn't match, we put it back (defensive)
                 lines.push(last_line);
             }
+            backup_system_file(mkinit_path)?;
             let mut temp_file = NamedTempFile::new()?;
             writeln!(temp_file, "{}", lines.join("\n"))?;
             let status = Command::new("sudo")
@@ -1002,7 +1685,7 @@ fn sanitize_mkinitcpio() -> Result<(), std::io::Error> {
                 .arg("root")
                 .arg(temp_file.path())
                 .arg(mkinit_path)
-                .status()?;
+                .run_status()?;
             if !status.success() {
                 eprintln!("{}", "❌ Failed to sanitize mkinitcpio.conf.".red());
                 return Err(std::io::Error::other("Failed to sanitize mkinitcpio.conf"));
@@ -1015,12 +1698,12 @@ fn sanitize_mkinitcpio() -> Result<(), std::io::Error> {
 ///Configures dnscrypt-proxy to use Cloudflare's DNS servers for enhanced privacy and security.
 fn configure_dns() -> Result<(), std::io::Error> {
     // --- DNS Crypt Proxy CONFIGURATION ---
-    println!("   🔧 Configuring dnscrypt-proxy (DNS Proxy)...");
+    status!("   🔧 Configuring dnscrypt-proxy (DNS Proxy)...");
 
     // 1. Ensure package is installed (failsafe)
     let status = Command::new("sudo")
         .args(["pacman", "-S", "--needed", "--noconfirm", "dnscrypt-proxy"])
-        .status()?;
+        .run_status()?;
     if !status.success() {
         eprintln!(
             "{}",
@@ -1030,7 +1713,16 @@ fn configure_dns() -> Result<(), std::io::Error> {
     }
     // 2. Configure TOML to use Cloudflare
     let dns_conf = "/etc/dnscrypt-proxy/dnscrypt-proxy.toml";
-    let content = fs::read_to_string(dns_conf)?;
+    // A dry run only previewed the install above, so the file may not exist yet
+    let content = match fs::read_to_string(dns_conf) {
+        Err(e)
+            if e.kind() == std::io::ErrorKind::NotFound
+                && dry_run_skip(&format!("point {} at Cloudflare", dns_conf)) =>
+        {
+            String::new()
+        }
+        result => result?,
+    };
     let mut modified = false;
     let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
     for line in &mut lines {
@@ -1052,6 +1744,7 @@ fn configure_dns() -> Result<(), std::io::Error> {
         }
     }
     if modified {
+        backup_system_file(dns_conf)?;
         let mut temp_file = NamedTempFile::new()?;
         writeln!(temp_file, "{}", lines.join("\n"))?;
         let status = Command::new("sudo")
@@ -1064,7 +1757,7 @@ fn configure_dns() -> Result<(), std::io::Error> {
             .arg("root")
             .arg(temp_file.path())
             .arg(dns_conf)
-            .status()?;
+            .run_status()?;
         if !status.success() {
             eprintln!(
                 "{}",
@@ -1076,38 +1769,54 @@ fn configure_dns() -> Result<(), std::io::Error> {
         }
     }
     // 3. Enable the service
-    run_cmd("sudo", &["systemctl", "enable", "--now", "dnscrypt-proxy"])?;
+    enable_service("dnscrypt-proxy.service", &["--now"])?;
 
     // 4. Clean up old Cloudflared artifacts if they exist
     Command::new("sudo")
         .args(["systemctl", "disable", "--now", "cloudflared-dns"])
-        .status()?;
+        .run_status()?;
     Command::new("sudo")
         .args(["rm", "-f", "/etc/systemd/system/cloudflared-dns.service"])
-        .status()?;
+        .run_status()?;
     Command::new("sudo")
         .args(["systemctl", "daemon-reload"])
-        .status()?;
+        .run_status()?;
     Ok(())
 }
 
 ///Configures the user's shell to Zsh and sets up Tmux Plugin Manager for enhanced terminal
 ///experience.
 fn configure_shell(home: &Path) -> Result<(), std::io::Error> {
-    println!("    🔧 Setting Shell to Zsh...");
+    status!("    🔧 Setting Shell to Zsh...");
     let user = std::env::var("USER").unwrap_or_else(|_| "root".to_string());
-    Command::new("sudo")
-        .args(["chsh", "-s", "/usr/bin/zsh", &user])
-        .output()?;
+    // chsh prompts for a password again on every run, so only call it when needed
+    let current_shell = Command::new("getent")
+        .args(["passwd", &user])
+        .output()
+        .ok()
+        .and_then(|out| {
+            String::from_utf8_lossy(&out.stdout)
+                .trim()
+                .rsplit(':')
+                .next()
+                .map(str::to_string)
+        });
+    if current_shell.as_deref() == Some("/usr/bin/zsh") {
+        status!("      ✅ Login shell is already zsh");
+    } else {
+        Command::new("sudo")
+            .args(["chsh", "-s", "/usr/bin/zsh", &user])
+            .run_output()?;
+    }
 
-    println!("    ✨ Setting up Tmux Plugin Manager...");
+    status!("    ✨ Setting up Tmux Plugin Manager...");
     let tpm_dir = home.join(".tmux/plugins/tpm");
     if !tpm_dir.exists() {
         Command::new("git")
             .arg("clone")
             .arg("https://github.com/tmux-plugins/tpm")
             .arg(tpm_dir)
-            .status()?;
+            .run_status()?;
     }
     Ok(())
 }
@@ -1115,7 +1824,7 @@ fn configure_shell(home: &Path) -> Result<(), std::io::Error> {
 ///Configures systemd-logind to ensure that user processes are killed on logout, preventing
 ///lingering sessions and resource leaks.
 fn configure_logind() -> Result<(), std::io::Error> {
-    println!("    🔧 Configuring Logind...");
+    status!("    🔧 Configuring Logind...");
     let logind_conf = "/etc/systemd/logind.conf";
     let content = fs::read_to_string(logind_conf)?;
     let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
@@ -1125,7 +1834,7 @@ fn configure_logind() -> Result<(), std::io::Error> {
         let trimmed = line.trim_start();
         if trimmed.starts_with("KillUserProcesses=") || trimmed.starts_with("#KillUserProcesses=") {
             if trimmed == "KillUserProcesses=yes" {
-                println!("   ✅ KillUserProcesses is already set to yes.");
+                status!("   ✅ KillUserProcesses is already set to yes.");
                 found = true;
                 break;
             }
@@ -1148,6 +1857,7 @@ fn configure_logind() -> Result<(), std::io::Error> {
         modified = true;
     }
     if modified {
+        backup_system_file(logind_conf)?;
         let mut temp_file = NamedTempFile::new()?;
         writeln!(temp_file, "{}", lines.join("\n"))?;
         let status = Command::new("sudo")
@@ -1160,7 +1870,7 @@ fn configure_logind() -> Result<(), std::io::Error> {
             .arg("root")
             .arg(temp_file.path())
             .arg(logind_conf)
-            .status()?;
+            .run_status()?;
         if !status.success() {
             eprintln!(
                 "{}",
@@ -1174,7 +1884,7 @@ fn configure_logind() -> Result<(), std::io::Error> {
 
 /// Configures Greetd with a custom tuigreet session and disables other DMs.
 fn configure_greetd() -> Result<(), std::io::Error> {
-    println!("    🔧 Configuring Greetd...");
+    status!("    🔧 Configuring Greetd...");
     let greetd_config = r#"
 [terminal]
 vt = 1
@@ -1182,6 +1892,7 @@ vt = 1
 command = "tuigreet --time --remember --sessions /usr/share/wayland-sessions:/usr/share/xsessions"
 user = "greeter"
 "#;
+    backup_system_file("/etc/greetd/config.toml")?;
     tempfile::NamedTempFile::new()
         .and_then(|mut temp_file| {
             temp_file.write_all(greetd_config.as_bytes())?;
@@ -1195,7 +1906,7 @@ user = "greeter"
                 .arg("root")
                 .arg(temp_file.path())
                 .arg("/etc/greetd/config.toml")
-                .status()
+                .run_status()
         })
         .and_then(|status| {
             if status.success() {
@@ -1206,17 +1917,103 @@ user = "greeter"
         })?;
     Command::new("sudo")
         .args(["systemctl", "disable", "gdm", "sddm", "lightdm"])
-        .status()?;
-    run_cmd(
-        "sudo",
-        &["systemctl", "enable", "--force", "greetd.service"],
-    )?;
+        .run_status()?;
+    enable_service("greetd.service", &["--force"])?;
+    Ok(())
+}
+
+// --- System File Backups ---
+// Every file under /etc that the installer rewrites gets a one-time `{path}.rwp-backup`
+// copy before its first patch, so `--restore-backups` can undo a bad install. Files the
+// installer creates from scratch (modprobe/udev rules on a fresh system) have no original
+// to keep and are left alone on restore; a `{path}.rwp-created` marker records that, so a
+// later run doesn't mistake the installer's own file for the original and back it up.
+const BACKUP_SUFFIX: &str = ".rwp-backup";
+const CREATED_SUFFIX: &str = ".rwp-created";
+
+/// Everything `backup_system_file` is called on. `--restore-backups` walks this list.
+const PATCHED_SYSTEM_FILES: &[&str] = &[
+    "/etc/pacman.conf",
+    "/etc/mkinitcpio.conf",
+    "/etc/dnscrypt-proxy/dnscrypt-proxy.toml",
+    "/etc/systemd/logind.conf",
+    "/etc/greetd/config.toml",
+    "/etc/modprobe.d/nvidia.conf",
+    "/etc/modprobe.d/99-nvidia-uvm-blacklist.conf",
+    "/etc/udev/rules.d/90-nvidia-pm.rules",
+    "/etc/geoclue/geoclue.conf",
+    "/etc/UPower/UPower.conf",
+];
+
+/// Copies `path` to `{path}.rwp-backup` unless a backup already exists, so the backup
+/// always holds the pre-install original rather than an earlier patch. A missing `path`
+/// is about to be created by the installer and only gets its `.rwp-created` marker.
+fn backup_system_file(path: &str) -> Result<(), std::io::Error> {
+    let backup = format!("{}{}", path, BACKUP_SUFFIX);
+    let created = format!("{}{}", path, CREATED_SUFFIX);
+    if Path::new(&backup).exists() || Path::new(&created).exists() {
+        return Ok(());
+    }
+    if !Path::new(path).exists() {
+        if dry_run_skip(&format!("mark {} as created by the installer", path)) {
+            return Ok(());
+        }
+        if let Some(parent) = Path::new(path).parent() {
+            run_cmd("sudo", &["mkdir", "-p", &parent.to_string_lossy()])?;
+        }
+        return run_cmd("sudo", &["touch", &created]);
+    }
+    if dry_run_skip(&format!("back up {} to {}", path, backup)) {
+        return Ok(());
+    }
+    run_cmd("sudo", &["cp", "-a", path, &backup])?;
+    status!("   💾 Backed up {} to {}", path, backup);
+    Ok(())
+}
+
+/// Moves every `.rwp-backup` back over the file it was taken from. Rebuilds the
+/// initramfs if anything it reads (mkinitcpio.conf, modprobe.d) was reverted.
+fn restore_system_backups() -> Result<(), std::io::Error> {
+    status!(
+        "{}",
+        "♻️  Restoring system files from backups...".blue().bold()
+    );
+    let mut restored = 0;
+    let mut requires_rebuild = false;
+    for path in PATCHED_SYSTEM_FILES {
+        let backup = format!("{}{}", path, BACKUP_SUFFIX);
+        if !Path::new(&backup).exists() {
+            continue;
+        }
+        restored += 1;
+        requires_rebuild |=
+            path.starts_with("/etc/mkinitcpio") || path.starts_with("/etc/modprobe.d");
+        if dry_run_skip(&format!("restore {} from {}", path, backup)) {
+            continue;
+        }
+        run_cmd("sudo", &["mv", "-f", &backup, path])?;
+        status!("   ✅ Restored {}", path);
+    }
+    if restored == 0 {
+        status!("   ℹ️  No backups found. Nothing to restore.");
+        return Ok(());
+    }
+    if requires_rebuild {
+        status!("    🏗️  Rebuilding Initramfs...");
+        run_cmd("sudo", &["mkinitcpio", "-P"])?;
+    }
+    status!(
+        "{}",
+        format!("✅ Restored {} file(s). Reboot to apply them.", restored)
+            .green()
+            .bold()
+    );
     Ok(())
 }
 
 /// Helper to run a command and check for success, returning an error if it fails.
 fn run_cmd(cmd: &str, args: &[&str]) -> Result<(), std::io::Error> {
-    let status = Command::new(cmd).args(args).status()?;
+    let status = Command::new(cmd).args(args).run_status()?;
     if !status.success() {
         return Err(std::io::Error::other(format!(
             "Command '{}' with args {:?} failed",
@@ -1226,10 +2023,31 @@ fn run_cmd(cmd: &str, args: &[&str]) -> Result<(), std::io::Error> {
     Ok(())
 }
 
+/// `sudo systemctl enable {extra} {unit}`, skipped when the unit is already enabled
+/// (and, for `--now`, already running). The queries are read-only, so they run in
+/// dry-run mode too.
+fn enable_service(unit: &str, extra: &[&str]) -> Result<(), std::io::Error> {
+    let query = |verb: &str| {
+        Command::new("systemctl")
+            .args([verb, "--quiet", unit])
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success())
+    };
+    if query("is-enabled") && (!extra.contains(&"--now") || query("is-active")) {
+        status!("      ✅ {} is already enabled", unit);
+        return Ok(());
+    }
+    let mut args = vec!["systemctl", "enable"];
+    args.extend_from_slice(extra);
+    args.push(unit);
+    run_cmd("sudo", &args)
+}
+
 /// Gleans pacman.conf to remove unwanted sessions and prevent future installs.
 /// Gnome installs a lot of sessions we don't need, this keeps the list clean.
 fn optimize_pacman_config() -> Result<(), std::io::Error> {
-    println!("   🔧 Optimizing pacman.conf & Cleaning Sessions...");
+    status!("   🔧 Optimizing pacman.conf & Cleaning Sessions...");
 
     let sessions_to_remove = vec![
         "/usr/share/wayland-sessions/gnome-classic.desktop",
@@ -1237,15 +2055,16 @@ fn optimize_pacman_config() -> Result<(), std::io::Error> {
     ];
 
     for session in sessions_to_remove {
-        Command::new("sudo").args(["rm", "-f", session]).output()?;
+        Command::new("sudo")
+            .args(["rm", "-f", session])
+            .run_output()?;
     }
 
     let pacman_conf = "/etc/pacman.conf";
     let content = fs::read_to_string(pacman_conf)?;
 
     if content.contains("NoExtract = usr/share/wayland-sessions/niri.desktop") {
-        //println!("   👉 Injecting NoExtract rules into [options]...");
-        println!("   👉 Removing old NoExtract rules to allow session updates...");
+        status!("   👉 Removing old NoExtract rules to allow session updates...");
         let temp_content = content
             .lines()
             .filter(|line| {
@@ -1255,6 +2074,7 @@ fn optimize_pacman_config() -> Result<(), std::io::Error> {
             })
             .collect::<Vec<&str>>()
             .join("\n");
+        backup_system_file(pacman_conf)?;
         let mut temp_file = NamedTempFile::new()?;
         writeln!(temp_file, "{}", temp_content)?;
         let status = Command::new("sudo")
@@ -1267,7 +2087,7 @@ fn optimize_pacman_config() -> Result<(), std::io::Error> {
             .arg("root")
             .arg(temp_file.path())
             .arg(pacman_conf)
-            .status()?;
+            .run_status()?;
         if !status.success() {
             eprintln!(
                 "{}",
@@ -1285,17 +2105,21 @@ fn optimize_pacman_config() -> Result<(), std::io::Error> {
 /// 3. Rebuilds initramfs via `mkinitcpio`.
 ///
 /// Security Note: Uses a secure temp file pattern for writing to /etc/.
-/// NOW SMART: Differentiates between Turing (Legacy) and Modern (Ampere/Ada) cards.
+/// NOW SMART: Differentiates between pinned legacy cards (e.g. Turing) and Modern (Ampere/Ada) cards.
 fn apply_nvidia_configs(arch: &NvidiaArch) -> Result<(), std::io::Error> {
-    println!("    Applying Nvidia Configs...");
+    status!("    Applying Nvidia Configs...");
 
-    let is_turing = *arch == NvidiaArch::Turing;
+    // Legacy = a pinned branch that must run without GSP firmware (Turing on 580xx)
+    let is_turing = matches!(arch, NvidiaArch::Pinned(pin) if !pin.gsp_firmware);
     let mut requires_rebuild = false;
 
-    if is_turing {
-        println!("    ℹ️  Configuring for Turing Architecture (GTX 16xx / RTX 20xx)...");
+    if let NvidiaArch::Pinned(pin) = arch {
+        status!(
+            "    ℹ️  Configuring for {} on its pinned driver...",
+            pin.name
+        );
     } else {
-        println!("    ℹ️  Configuring for Modern NVIDIA Architecture...");
+        status!("    ℹ️  Configuring for Modern NVIDIA Architecture...");
     }
 
     // Helper closure: Write to local dir (safe) then install
@@ -1303,9 +2127,17 @@ fn apply_nvidia_configs(arch: &NvidiaArch) -> Result<(), std::io::Error> {
         if let Ok(existing) = fs::read_to_string(dest)
             && existing == content
         {
-            println!("   ✅ {} is already up to date.", dest);
+            status!("   ✅ {} is already up to date.", dest);
             return Ok(false); // No changes made
         }
+        backup_system_file(dest)?;
+        if dry_run_skip(&format!(
+            "install {} (root:root 644):\n{}",
+            dest,
+            content.trim_end()
+        )) {
+            return Ok(true);
+        }
         //let local_tmp = format!("./{}", filename);
         let mut temp_file = NamedTempFile::new()?;
         temp_file.write_all(content.as_bytes())?;
@@ -1322,7 +2154,7 @@ fn apply_nvidia_configs(arch: &NvidiaArch) -> Result<(), std::io::Error> {
                 temp_file.path().to_str().unwrap(),
                 dest,
             ])
-            .status()?;
+            .run_status()?;
         if !status.success() {
             eprintln!("❌ Failed to install file to {}.", dest);
             return Err(std::io::Error::other(format!(
@@ -1366,11 +2198,13 @@ fn apply_nvidia_configs(arch: &NvidiaArch) -> Result<(), std::io::Error> {
 
     create_sway_hybrid_script()?;
 
-    println!("    🏗️  Rebuilding Initramfs...");
+    status!("    🏗️  Rebuilding Initramfs...");
     if requires_rebuild {
-        Command::new("sudo").args(["mkinitcpio", "-P"]).status()?;
+        Command::new("sudo")
+            .args(["mkinitcpio", "-P"])
+            .run_status()?;
     } else {
-        println!("    ✅ No changes to initramfs configuration. Skipping rebuild.");
+        status!("    ✅ No changes to initramfs configuration. Skipping rebuild.");
     }
     Ok(())
 }
@@ -1378,7 +2212,7 @@ fn apply_nvidia_configs(arch: &NvidiaArch) -> Result<(), std::io::Error> {
 /// Helper: Safely adds nvidia modules to mkinitcpio.conf if missing.
 /// Handles the request: "-added nvidia to modules in mkinitcpio"
 fn ensure_nvidia_modules_in_initcpio() -> Result<bool, std::io::Error> {
-    println!("    🔧 Checking mkinitcpio modules for Modern NVIDIA support...");
+    status!("    🔧 Checking mkinitcpio modules for Modern NVIDIA support...");
     let config_path = "/etc/mkinitcpio.conf";
     let content = fs::read_to_string(config_path)?;
 
@@ -1408,6 +2242,7 @@ fn ensure_nvidia_modules_in_initcpio() -> Result<bool, std::io::Error> {
     if new_content == content.trim_end() {
         return Ok(false); // No changes needed
     }
+    backup_system_file(config_path)?;
     let mut temp_file = NamedTempFile::new()?;
     writeln!(temp_file, "{}", new_content)?;
     let status = Command::new("sudo")
@@ -1420,9 +2255,9 @@ fn ensure_nvidia_modules_in_initcpio() -> Result<bool, std::io::Error> {
         .arg("root")
         .arg(temp_file.path())
         .arg(config_path)
-        .status()?;
+        .run_status()?;
     if status.success() {
-        println!("    ✅ Added nvidia modules to Initramfs config.");
+        status!("    ✅ Added nvidia modules to Initramfs config.");
         Ok(true)
     } else {
         eprintln!("    ⚠️  Failed to update mkinitcpio.conf.");
@@ -1440,24 +2275,69 @@ fn setup_waybar_configs(home: &Path) {
         let target = waybar_dir.join(config);
 
         if template.exists() && !target.exists() {
+            if dry_run_skip(&format!("create {} from its template", target.display())) {
+                continue;
+            }
             match fs::copy(&template, &target) {
-                Ok(_) => println!("   ✅ Created {} from template", config),
-                Err(e) => println!("   ⚠️  Failed to create {}: {}", config, e),
+                Ok(_) => status!("   ✅ Created {} from template", config),
+                Err(e) => eprintln!("   ⚠️  Failed to create {}: {}", config, e),
             }
         } else if target.exists() {
-            println!("   ℹ️  {} already exists", config);
+            status!("   ℹ️  {} already exists", config);
         }
     }
 }
+const OWM_KEY_PLACEHOLDER: &str = "YOUR_SECRET_OWM_KEY_HERE";
+
+/// Asks for the OpenWeatherMap key. A blank answer means "use Open-Meteo" (no key needed);
+/// cancelling keeps the placeholder so we ask again next run.
+fn prompt_weather_key() -> String {
+    prompt_text(
+        "Enter OpenWeatherMap API Key (get one by making a free account at https://home.openweathermap.org/users/sign_up), or leave blank to use Open-Meteo (no key needed):",
+        answers().owm_api_key.as_deref(),
+        OWM_KEY_PLACEHOLDER,
+    )
+}
+
+fn prompt_finnhub_key() -> String {
+    prompt_text(
+        "Enter Finnhub.io API Key (get one by making a free account at finnhub.io/register):",
+        answers().finnhub_api_key.as_deref(),
+        "YOUR_FINNHUB_KEY_HERE",
+    )
+}
+
+/// Fills the OWM key placeholder in `line`, whatever the spacing around it.
+/// A blank key also switches [waybar_weather] to Open-Meteo.
+fn fill_weather_key(line: &str, weather_api: &str) -> String {
+    if !line.contains(OWM_KEY_PLACEHOLDER) {
+        return line.to_string();
+    }
+    let key = weather_api.trim();
+    if key.is_empty() {
+        format!(
+            "provider = \"open-meteo\"\n{}",
+            line.replace(OWM_KEY_PLACEHOLDER, "")
+        )
+    } else {
+        line.replace(OWM_KEY_PLACEHOLDER, key)
+    }
+}
+
 /// Interactive wizard to generate the local `config.toml`.
 /// Validates input to prevent injection attacks before writing to system files (like /etc/geoclue).
 fn setup_secrets_and_geoclue(home: &Path) -> Result<(), std::io::Error> {
+    if dry_run_skip(
+        "prompt for API keys, write ~/.config/rust-dotfiles/config.toml and configure geoclue",
+    ) {
+        return Ok(());
+    }
     let config_dir = home.join(".config/rust-dotfiles");
     let config_path = config_dir.join("config.toml");
     // Logic to handle if 'rust-dotfiles' exists as a file instead of a directory
     if config_dir.exists() {
         if !config_dir.is_dir() {
-            println!("   ⚠️  Found a file blocking config directory. Backing it up...");
+            status!("   ⚠️  Found a file blocking config directory. Backing it up...");
             let backup = format!("{}.bak", config_dir.display());
             std::fs::rename(&config_dir, &backup)?;
             std::fs::create_dir_all(&config_dir)?;
@@ -1467,17 +2347,15 @@ fn setup_secrets_and_geoclue(home: &Path) -> Result<(), std::io::Error> {
     }
 
     if !config_path.exists() {
-        println!(
+        status!(
             "   🧙 We need to generate your central config.toml and configure Location Services."
         );
-        let weather_api = Text::new("Enter OpenWeatherMap API Key (get one by making a free account at https://home.openweathermap.org/users/sign_up):").prompt().unwrap_or("YOUR_SECRET_OWM_KEY_HERE".to_string());
-        let finnhub_api = Text::new(
-            "Enter Finnhub.io API Key (get one by making a free account at finnhub.io/register):",
-        )
-        .prompt()
-        .unwrap_or("YOUR_FINNHUB_KEY_HERE".to_string());
+        let weather_api = prompt_weather_key();
+        let finnhub_api = prompt_finnhub_key();
         let template = include_str!("../../../.config/rust-dotfiles/config.toml.template")
-            .replace("YOUR_SECRET_OWM_KEY_HERE", &weather_api)
+            .split_inclusive('\n')
+            .map(|line| fill_weather_key(line, &weather_api))
+            .collect::<String>()
             .replace("YOUR_FINNHUB_KEY_HERE", &finnhub_api);
         let mut options = fs::OpenOptions::new();
         options.write(true).create(true).truncate(true).mode(0o600);
@@ -1485,7 +2363,7 @@ fn setup_secrets_and_geoclue(home: &Path) -> Result<(), std::io::Error> {
             Ok(mut file) => {
                 file.write_all(template.as_bytes())
                     .expect("Failed to write secure config.toml");
-                println!("  ✅ Config generated securely at {:?}", config_path);
+                status!("  ✅ Config generated securely at {:?}", config_path);
             }
             Err(e) => {
                 eprintln!("❌ Failed to securely open config.toml: {}", e);
@@ -1495,14 +2373,13 @@ fn setup_secrets_and_geoclue(home: &Path) -> Result<(), std::io::Error> {
     } else {
         let contents = fs::read_to_string(&config_path)?;
         if contents.contains("YOUR_SECRET_OWM_KEY") || contents.contains("YOUR_FINNHUB_KEY") {
-            let weather_api = Text::new("Enter OpenWeatherMap API Key (get one by making a free account at https://home.openweathermap.org/users/sign_up):").prompt().unwrap_or("YOUR_SECRET_OWM_KEY_HERE".to_string());
-            let finnhub_api = Text::new("Enter Finnhub.io API Key (get one by making a free account at finnhub.io/register):").prompt().unwrap_or("YOUR_FINNHUB_KEY_HERE".to_string());
+            let weather_api = prompt_weather_key();
+            let finnhub_api = prompt_finnhub_key();
             let mut modified = false;
             let mut lines: Vec<String> = contents.lines().map(|s| s.to_string()).collect();
             for line in &mut lines {
                 if line.contains("YOUR_SECRET_OWM_KEY") || line.contains("YOUR_FINNHUB_KEY") {
-                    *line = line
-                        .replace("YOUR_SECRET_OWM_KEY_HERE", &weather_api)
+                    *line = fill_weather_key(line, &weather_api)
                         .replace("YOUR_FINNHUB_KEY_HERE", &finnhub_api);
                     modified = true;
                 }
@@ -1520,7 +2397,7 @@ fn setup_secrets_and_geoclue(home: &Path) -> Result<(), std::io::Error> {
                     .arg(std::env::var("USER").unwrap_or_else(|_| "root".to_string()))
                     .arg(temp_file.path())
                     .arg(&config_path)
-                    .status()?;
+                    .run_status()?;
                 if !status.success() {
                     eprintln!("{}", "❌ Failed to update config.toml with API keys.".red());
                     return Err(std::io::Error::other("Failed to update config.toml"));
@@ -1531,7 +2408,7 @@ fn setup_secrets_and_geoclue(home: &Path) -> Result<(), std::io::Error> {
     configure_geoclue()?;
     let wallpaper_path = home.join("Pictures/Wallpapers");
     if !wallpaper_path.exists() {
-        println!(
+        status!(
             "   🖼️  Creating wallpaper directory at {:?}",
             wallpaper_path
         );
@@ -1541,45 +2418,36 @@ fn setup_secrets_and_geoclue(home: &Path) -> Result<(), std::io::Error> {
 }
 
 fn configure_geoclue() -> Result<(), std::io::Error> {
-    println!("   🌍 Configuring Geoclue...");
+    status!("   🌍 Configuring Geoclue...");
     let gc_path = "/etc/geoclue/geoclue.conf";
-    let google_geo_api = Text::new("Enter Google Geolocation API Key for Geoclue(get one at console.cloud.google.com/apis/library/geocoding-backend.googleapis.com):").prompt().unwrap_or_default();
+    let google_geo_api = prompt_text(
+        "Enter Google Geolocation API Key for Geoclue(get one at console.cloud.google.com/apis/library/geocoding-backend.googleapis.com):",
+        answers().google_geo_api_key.as_deref(),
+        "",
+    );
     if google_geo_api.is_empty() {
-        println!("   ⚠️  No API key entered. Skipping Geoclue configuration.");
+        status!("   ⚠️  No API key entered. Skipping Geoclue configuration.");
         return Ok(());
     }
 
-    let mut modified = false;
     let new_url = format!(
-        "url=https://www.googleapis.com/geolocation/v1/geolocate?key={}",
+        "https://www.googleapis.com/geolocation/v1/geolocate?key={}",
         google_geo_api
     );
     let content = fs::read_to_string(gc_path)?;
     let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
-    for line in &mut lines {
-        let normalized = line
-            .trim_start()
-            .trim_start_matches(['#', ';'])
-            .trim_start();
-        if normalized.starts_with("enable=") {
-            if normalized == "enable=true" {
-                println!("   ✅ Geoclue is already enabled.");
-                continue; // Already enabled
-            }
-            *line = "enable=true".to_string();
-            modified = true;
-        } else if normalized.contains("googleapis.com") && normalized != new_url {
-            *line = new_url.clone();
-            modified = true;
-        } else if normalized.starts_with("method=") && !normalized.contains("gmaps") {
-            *line = "method=gmaps".to_string();
-            modified = true;
-        }
+    // Only the [wifi] source talks to the geolocation service; the other sections have
+    // their own `enable=` keys (e.g. static-source) that must be left alone.
+    let mut modified = set_ini_key(&mut lines, "wifi", "enable", "true");
+    modified |= set_ini_key(&mut lines, "wifi", "url", &new_url);
+    if ini_key_value(&lines, "wifi", "method").is_some_and(|m| m != "gmaps") {
+        modified |= set_ini_key(&mut lines, "wifi", "method", "gmaps");
     }
     if !modified {
-        println!("   ⚠️  No changes needed for geoclue.conf. It may already be configured.");
+        status!("   ⚠️  No changes needed for geoclue.conf. It may already be configured.");
         return Ok(());
     }
+    backup_system_file(gc_path)?;
     let mut temp_file = NamedTempFile::new()?;
     writeln!(temp_file, "{}", lines.join("\n"))?;
     let status = Command::new("sudo")
@@ -1592,7 +2460,7 @@ fn configure_geoclue() -> Result<(), std::io::Error> {
         .arg("root")
         .arg(temp_file.path())
         .arg(gc_path)
-        .status()?;
+        .run_status()?;
     if !status.success() {
         eprintln!(
             "{}",
@@ -1603,6 +2471,72 @@ fn configure_geoclue() -> Result<(), std::io::Error> {
     Ok(())
 }
 
+/// Returns the line range of `[section]` (header excluded), or None if it doesn't exist.
+fn ini_section_range(lines: &[String], section: &str) -> Option<(usize, usize)> {
+    let header = format!("[{}]", section);
+    let start = lines.iter().position(|l| l.trim() == header)? + 1;
+    let end = lines[start..]
+        .iter()
+        .position(|l| l.trim_start().starts_with('['))
+        .map_or(lines.len(), |offset| start + offset);
+    Some((start, end))
+}
+
+/// Splits `key=value` (optionally commented out with `#`/`;`) into (commented, key, value).
+fn parse_ini_line(line: &str) -> Option<(bool, &str, &str)> {
+    let trimmed = line.trim_start();
+    let uncommented = trimmed.trim_start_matches(['#', ';']).trim_start();
+    let (key, value) = uncommented.split_once('=')?;
+    Some((uncommented.len() != trimmed.len(), key.trim(), value.trim()))
+}
+
+/// Reads the active (uncommented) value of `key` in `[section]`.
+fn ini_key_value<'a>(lines: &'a [String], section: &str, key: &str) -> Option<&'a str> {
+    let (start, end) = ini_section_range(lines, section)?;
+    lines[start..end]
+        .iter()
+        .find_map(|l| match parse_ini_line(l) {
+            Some((false, k, v)) if k == key => Some(v),
+            _ => None,
+        })
+}
+
+/// Idempotently sets `key=value` inside `[section]`. Prefers rewriting the active line,
+/// then uncommenting the first commented example, and otherwise appends to the section
+/// (creating it if needed). Returns true if anything changed.
+fn set_ini_key(lines: &mut Vec<String>, section: &str, key: &str, value: &str) -> bool {
+    let wanted = format!("{}={}", key, value);
+    let Some((start, end)) = ini_section_range(lines, section) else {
+        lines.push(String::new());
+        lines.push(format!("[{}]", section));
+        lines.push(wanted);
+        return true;
+    };
+
+    let find = |commented: bool| {
+        (start..end).find(
+            |&i| matches!(parse_ini_line(&lines[i]), Some((c, k, _)) if c == commented && k == key),
+        )
+    };
+
+    if let Some(i) = find(false) {
+        if parse_ini_line(&lines[i]).is_some_and(|(_, _, v)| v == value) {
+            return false;
+        }
+        lines[i] = wanted;
+    } else if let Some(i) = find(true) {
+        lines[i] = wanted;
+    } else {
+        // Insert after the last non-blank line so the section's trailing spacing is kept.
+        let insert_at = (start..end)
+            .rev()
+            .find(|&i| !lines[i].trim().is_empty())
+            .map_or(start, |i| i + 1);
+        lines.insert(insert_at, wanted);
+    }
+    true
+}
+
 /// Helper to parse `cargo metadata` and extract the expected binary names for a given app.
 /// Parses the JSON in a way that explicitly returns the app name if the parsing fails or the
 /// expected fields are missing
@@ -1659,11 +2593,22 @@ fn expected_binary_names(app_path: &Path, app_name: &str) -> HashSet<String> {
 
 /// Builds custom Rust apps using native caching.
 /// If source files haven't changed, this takes milliseconds.
-fn build_custom_apps(home: &Path, repo_root: &Path) -> Result<(), std::io::Error> {
+fn build_custom_apps(
+    home: &Path,
+    repo_root: &Path,
+    only: Option<&[String]>,
+) -> Result<(), std::io::Error> {
     let sys_scripts_dir = repo_root.join("sysScripts");
 
     // Ensure ~/.cargo/bin exists
     let cargo_bin_dir = home.join(".cargo/bin");
+    if dry_run_skip(&format!(
+        "build every crate in {} and copy its binaries to {}",
+        sys_scripts_dir.display(),
+        cargo_bin_dir.display()
+    )) {
+        return Ok(());
+    }
 
     fs::create_dir_all(&cargo_bin_dir)?;
 
@@ -1674,10 +2619,14 @@ fn build_custom_apps(home: &Path, repo_root: &Path) -> Result<(), std::io::Error
                 let app_name = match app_path.file_name().and_then(|n| n.to_str()) {
                     Some(name) => name,
                     None => {
-                        println!("   ⚠️  Skipping app with invalid name at {:?}", app_path);
+                        status!("   ⚠️  Skipping app with invalid name at {:?}", app_path);
                         continue;
                     }
                 };
+                if only.is_some_and(|apps| !apps.iter().any(|a| a == app_name)) {
+                    status!("   ⏭️  Skipping {} (not in [rust_apps])", app_name);
+                    continue;
+                }
                 //let app_name = app_path.file_name().unwrap().to_str().unwrap();
                 let status = Command::new("cargo")
                     .args(["build", "--release", "-q"])
@@ -1730,7 +2679,7 @@ fn build_custom_apps(home: &Path, repo_root: &Path) -> Result<(), std::io::Error
                                 }
                                 match fs::copy(&bin_path, &target_bin) {
                                     Ok(_) => {
-                                        println!("   ✅ Synced binary: {}", filename);
+                                        status!("   ✅ Synced binary: {}", filename);
                                     }
                                     Err(e) => {
                                         eprintln!("   ❌ Failed to sync {}: {}", filename, e);
@@ -1742,12 +2691,12 @@ fn build_custom_apps(home: &Path, repo_root: &Path) -> Result<(), std::io::Error
                                 }
                             }
                             if !should_update && target_exists {
-                                println!("   ✅  {} is already up to date.", filename);
+                                status!("   ✅  {} is already up to date.", filename);
                             }
                         }
                     }
                 } else {
-                    println!("      ❌ Failed to build {}", app_name);
+                    eprintln!("      ❌ Failed to build {}", app_name);
                     return Err(std::io::Error::other(format!(
                         "Failed to build {}",
                         app_name
@@ -1762,7 +2711,7 @@ fn build_custom_apps(home: &Path, repo_root: &Path) -> Result<(), std::io::Error
 /// Strategy: Move standard files (e.g. niri.desktop) to custom numbered files (10-niri.desktop).
 /// This prevents Pacman from deleting our custom config during updates while NoExtract is active.
 fn enforce_session_order(is_nvidia: bool) {
-    println!("   🔧 Enforcing Session Order (Renaming .desktop files)...");
+    status!("   🔧 Enforcing Session Order (Renaming .desktop files)...");
 
     let sessions_dir = "/usr/share/wayland-sessions";
 
@@ -1785,10 +2734,10 @@ fn enforce_session_order(is_nvidia: bool) {
         // 1. If the standard file exists (fresh install or update), STEAL IT.
         // We move it to the custom path so Pacman doesn't own it anymore.
         if Path::new(&std_path).exists() {
-            println!("      Moving {} -> {}", std_name, custom_name);
+            status!("      Moving {} -> {}", std_name, custom_name);
             let _ = Command::new("sudo")
                 .args(["mv", "-f", &std_path, &custom_path])
-                .status();
+                .run_status();
         }
 
         // 2. Patch the Name inside the CUSTOM file (if it exists)
@@ -1796,7 +2745,7 @@ fn enforce_session_order(is_nvidia: bool) {
             let sed_cmd = format!("s/^Name=.*/Name={}/", display_name);
             let _ = Command::new("sudo")
                 .args(["sed", "-i", &sed_cmd, &custom_path])
-                .status();
+                .run_status();
         }
     }
 
@@ -1804,7 +2753,7 @@ fn enforce_session_order(is_nvidia: bool) {
 
     if Path::new(sway_session).exists() {
         if is_nvidia {
-            println!("   🔧 Pointing Sway (Battery) to NVIDIA hybrid wrapper...");
+            status!("   🔧 Pointing Sway (Battery) to NVIDIA hybrid wrapper...");
             // Replace Exec=sway with Exec=/usr/local/bin/sway-hybrid
             let _ = Command::new("sudo")
                 .args([
@@ -1813,17 +2762,17 @@ fn enforce_session_order(is_nvidia: bool) {
                     "s|^Exec=.*|Exec=/usr/local/bin/sway-hybrid|",
                     sway_session,
                 ])
-                .status();
+                .run_status();
         } else {
-            println!(" 🔧 Ensuring Sway uses native launch (Non-NVIDIA)...");
+            status!(" 🔧 Ensuring Sway uses native launch (Non-NVIDIA)...");
             // Standardize back to native sway
             let _ = Command::new("sudo")
                 .args(["sed", "-i", "s|^Exec=.*|Exec=sway|", sway_session])
-                .status();
+                .run_status();
             //Clean up wwrapper script if it exists from a previous hardware config
             let _ = Command::new("sudo")
                 .args(["rm", "-f", "/usr/local/bin/sway-hybrid"])
-                .status();
+                .run_status();
         }
     }
 }
@@ -1856,31 +2805,40 @@ fn link_dotfiles_and_copy_resources(home: &Path, repo_root: &Path) {
     // We only install this if the user has NO config, to avoid angering Vim power users.
     let nvim_dest = home.join(".config/nvim");
     if nvim_dest.exists() {
-        println!(
+        status!(
             "   ℹ️  Neovim config found. Skipping to preserve your setup. If you would like my setup, copy {}/.config/nvim to ~/.config/nvim",
             repo_root.display()
         );
-        println!("      (Note: The 'Neovim' cheat sheet in kb-launcher may not work)");
+        status!("      (Note: The 'Neovim' cheat sheet in kb-launcher may not work)");
     } else {
-        println!("   ✨ Installing LazyVim Config...");
+        status!("   ✨ Installing LazyVim Config...");
         let nvim_src = repo_root.join(".config/nvim");
         create_symlink(&nvim_src, &nvim_dest);
     }
     // Link TLP
     let tlp_src = repo_root.join("tlp.conf");
-    let _ = Command::new("sudo")
-        .args(["ln", "-sf", tlp_src.to_str().unwrap(), "/etc/tlp.conf"])
-        .status();
-    let _ = Command::new("sudo")
-        .args(["systemctl", "enable", "tlp.service"])
-        .output();
+    if fs::read_link("/etc/tlp.conf").is_ok_and(|current| current == tlp_src) {
+        status!("      ✅ /etc/tlp.conf is already linked");
+    } else {
+        let _ = Command::new("sudo")
+            .args(["ln", "-sf", tlp_src.to_str().unwrap(), "/etc/tlp.conf"])
+            .run_status();
+    }
+    let _ = enable_service("tlp.service", &[]);
 
     // Copy Wallpapers
-    println!("   🖼️  Seeding default wallpapers...");
+    status!("   🖼️  Seeding default wallpapers...");
     let wallpaper_src = repo_root.join("wallpapers");
     let wallpaper_dest = home.join("Pictures/Wallpapers");
 
     if wallpaper_src.exists() {
+        if dry_run_skip(&format!(
+            "copy {} into {}",
+            wallpaper_src.display(),
+            wallpaper_dest.display()
+        )) {
+            return;
+        }
         if let Ok(entries) = fs::read_dir(&wallpaper_src) {
             fs::create_dir_all(&wallpaper_dest).unwrap_or_else(|e| {
                 eprintln!("❌ Failed to create wallpaper destination dir: {}", e);
@@ -1893,18 +2851,34 @@ fn link_dotfiles_and_copy_resources(home: &Path, repo_root: &Path) {
                     let _ = fs::copy(entry.path(), dest_path);
                 }
             }
-            println!("   ✅ Copied wallpapers to ~/Pictures/Wallpapers");
+            status!("   ✅ Copied wallpapers to ~/Pictures/Wallpapers");
         }
     } else {
-        println!("   ⚠️  'wallpapers' directory not found in repo root.");
+        status!("   ⚠️  'wallpapers' directory not found in repo root.");
     }
-    println!("   🏠 Updating User Directories (XDG)...");
+    status!("   🏠 Updating User Directories (XDG)...");
     // This regenerates ~/.config/user-dirs.dirs and ~/.config/gtk-3.0/bookmarks
     // ensuring they point to the *current* user's home, not Michael's.
-    let _ = Command::new("xdg-user-dirs-update").status();
+    let _ = Command::new("xdg-user-dirs-update").run_status();
 }
 ///Helper to create symlinks, backing up existing files if needed.
 fn create_symlink(src: &Path, dest: &Path) {
+    if fs::read_link(dest).is_ok_and(|current| current == src) {
+        status!("      ✅ {} is already linked", dest.display());
+        return;
+    }
+    if dry_run_skip(&format!(
+        "link {} -> {}{}",
+        dest.display(),
+        src.display(),
+        if dest.exists() && !dest.is_symlink() {
+            " (backing up the existing file)"
+        } else {
+            ""
+        }
+    )) {
+        return;
+    }
     if dest.exists() && !dest.is_symlink() {
         let backup = format!("{}.backup", dest.to_string_lossy());
         let _ = fs::rename(dest, &backup);
@@ -1998,8 +2972,16 @@ fn maybe_repair_symlink(
         return;
     }
 
+    if dry_run_skip(&format!(
+        "repair symlink {} -> {}",
+        dest.display(),
+        expected_target.display()
+    )) {
+        return;
+    }
+
     if fs::remove_file(dest).is_ok() && std::os::unix::fs::symlink(expected_target, dest).is_ok() {
-        println!(
+        status!(
             "   ✅ Repaired symlink: {} -> {}",
             dest.display(),
             expected_target.display()
@@ -2056,8 +3038,14 @@ fn patch_waybar_sidebar_toggle_path(home: &Path) {
     updated.push_str(&updated_block);
     updated.push_str(&content[block_end + 1..]);
 
+    if dry_run_skip(&format!(
+        "update the sidebar_toggle path in {}",
+        modules_path.display()
+    )) {
+        return;
+    }
     match fs::write(&modules_path, updated) {
-        Ok(()) => println!(
+        Ok(()) => status!(
             "   ✅ Updated Waybar sidebar_toggle path in {}",
             modules_path.display()
         ),
@@ -2089,20 +3077,18 @@ fn ensure_tlp_symlink_and_service(repo_root: &Path) {
     if current.as_deref() != Some(desired.as_str()) {
         match Command::new("sudo")
             .args(["ln", "-sf", desired.as_str(), "/etc/tlp.conf"])
-            .status()
+            .run_status()
         {
             Ok(status) if status.success() => {
                 relinked = true;
-                println!("   ✅ Updated /etc/tlp.conf symlink to {}", desired);
+                status!("   ✅ Updated /etc/tlp.conf symlink to {}", desired);
             }
             Ok(_) => eprintln!("   ⚠️ Failed to relink /etc/tlp.conf"),
             Err(e) => eprintln!("   ⚠️ Failed to run sudo ln for /etc/tlp.conf: {}", e),
         }
     }
 
-    let _ = Command::new("sudo")
-        .args(["systemctl", "enable", "tlp.service"])
-        .status();
+    let _ = enable_service("tlp.service", &[]);
 
     let is_active = Command::new("systemctl")
         .args(["is-active", "--quiet", "tlp.service"])
@@ -2112,9 +3098,9 @@ fn ensure_tlp_symlink_and_service(repo_root: &Path) {
     if relinked || !is_active {
         match Command::new("sudo")
             .args(["systemctl", "restart", "tlp.service"])
-            .status()
+            .run_status()
         {
-            Ok(status) if status.success() => println!("   ✅ TLP service restarted"),
+            Ok(status) if status.success() => status!("   ✅ TLP service restarted"),
             Ok(_) => eprintln!("   ⚠️ Failed to restart TLP service"),
             Err(e) => eprintln!(
                 "   ⚠️ Failed to run sudo systemctl restart tlp.service: {}",
@@ -2126,15 +3112,18 @@ fn ensure_tlp_symlink_and_service(repo_root: &Path) {
 /// Runs post-install hooks to set up themes and plugins.
 /// This ensures the user doesn't see "broken" visuals on first launch.
 fn finalize_setup(home: &Path) {
-    println!(
+    status!(
         "\n{}",
         "✨ Finalizing Setup (Themes & Plugins)...".blue().bold()
     );
+    if dry_run_skip("install Tmux plugins and sync Neovim (Lazy.nvim) headlessly") {
+        return;
+    }
 
     // 1. Install Tmux Plugins (Fixes the Green Bar)
     let tpm_script = home.join(".tmux/plugins/tpm/bin/install_plugins");
     if tpm_script.exists() {
-        println!("   📦 Installing Tmux Plugins (Headless)...");
+        status!("   📦 Installing Tmux Plugins (Headless)...");
         // We capture output to avoid spamming the user's terminal unless it fails
         let status = Command::new(&tpm_script)
             .stdout(Stdio::null())
@@ -2142,9 +3131,9 @@ fn finalize_setup(home: &Path) {
             .status();
 
         match status {
-            Ok(s) if s.success() => println!("   ✅ Tmux Plugins Installed"),
+            Ok(s) if s.success() => status!("   ✅ Tmux Plugins Installed"),
             _ => {
-                println!("   ⚠️  Tmux plugin install failed (You can press Prefix + I inside Tmux)")
+                status!("   ⚠️  Tmux plugin install failed (You can press Prefix + I inside Tmux)")
             }
         }
     }
@@ -2153,7 +3142,7 @@ fn finalize_setup(home: &Path) {
     // Only run if we actually installed the config (check if dest exists)
     let nvim_config = home.join(".config/nvim/init.lua"); // Check for main config file
     if nvim_config.exists() {
-        println!("   📦 Bootstrapping Neovim (Lazy.nvim)...");
+        status!("   📦 Bootstrapping Neovim (Lazy.nvim)...");
         // --headless: Don't open a UI
         // "+Lazy! sync": Run the sync command
         // "+qa": Quit All after finishing
@@ -2164,8 +3153,8 @@ fn finalize_setup(home: &Path) {
             .status();
 
         match status {
-            Ok(s) if s.success() => println!("   ✅ Neovim Plugins Synced"),
-            _ => println!("   ⚠️  Neovim setup skipped (will run on first launch)"),
+            Ok(s) if s.success() => status!("   ✅ Neovim Plugins Synced"),
+            _ => status!("   ⚠️  Neovim setup skipped (will run on first launch)"),
         }
     }
 }
@@ -2291,14 +3280,20 @@ fn get_ignored_packages() -> Vec<String> {
 // Installs the battery life warning and exectes systemctl poweroff to protect battery
 /// Installs the battery life warning and exectes systemctl poweroff to protect battery
 fn setup_battery_daemon(home: &Path) -> Result<(), std::io::Error> {
-    println!("   🔋 Configuring Battery Safety Daemon...");
+    status!("   🔋 Configuring Battery Safety Daemon...");
 
     configure_upower()?;
 
     let systemd_user_dir = home.join(".config/systemd/user");
     let service_dest = systemd_user_dir.join("battery-daemon.service");
+    if dry_run_skip(&format!(
+        "install {} and enable it with systemctl --user",
+        service_dest.display()
+    )) {
+        return Ok(());
+    }
 
-    println!("   🔋 Setting up Battery Safety Daemon...");
+    status!("   🔋 Setting up Battery Safety Daemon...");
 
     // Make sure the ~/.config/systemd/user/ folder actually exists
     std::fs::create_dir_all(&systemd_user_dir)?;
@@ -2306,7 +3301,7 @@ fn setup_battery_daemon(home: &Path) -> Result<(), std::io::Error> {
     let existing_content = std::fs::read_to_string(&service_dest).unwrap_or_default();
 
     if existing_content != service_content {
-        println!("   ✅ Battery daemon already configured. Skipping systemd setup.");
+        status!("   ✅ Battery daemon already configured. Skipping systemd setup.");
 
         std::fs::write(&service_dest, service_content)?;
 
@@ -2319,7 +3314,7 @@ fn setup_battery_daemon(home: &Path) -> Result<(), std::io::Error> {
             return Err(std::io::Error::other("Failed to reload systemd daemon"));
         }
     } else {
-        println!("   ✅ Battery daemon already configured. Skipping systemd setup.");
+        status!("   ✅ Battery daemon already configured. Skipping systemd setup.");
     }
     let status = std::process::Command::new("systemctl")
         .arg("--user")
@@ -2334,16 +3329,24 @@ fn setup_battery_daemon(home: &Path) -> Result<(), std::io::Error> {
         ));
     }
 
-    println!("   ✅ Battery Daemon ready.");
+    status!("   ✅ Battery Daemon ready.");
 
     Ok(())
 }
 
 fn configure_upower() -> Result<(), std::io::Error> {
-    println!("🔋 Enforcing UPower Critical Shutdown at 5%...");
+    status!("🔋 Enforcing UPower Critical Shutdown at 5%...");
 
     let upower_conf = "/etc/UPower/UPower.conf";
-    let file_content = fs::read_to_string(upower_conf)?;
+    let file_content = match fs::read_to_string(upower_conf) {
+        Err(e)
+            if e.kind() == std::io::ErrorKind::NotFound
+                && dry_run_skip(&format!("set the critical battery action in {upower_conf}")) =>
+        {
+            return Ok(());
+        }
+        result => result?,
+    };
     let mut needs_update = false;
     let mut lines: Vec<String> = file_content.lines().map(|s| s.to_string()).collect();
 
@@ -2361,9 +3364,10 @@ fn configure_upower() -> Result<(), std::io::Error> {
         }
     }
     if !needs_update {
-        println!("⚡ UPower already configured for critical shutdown. Skipping.");
+        status!("⚡ UPower already configured for critical shutdown. Skipping.");
         return Ok(());
     }
+    backup_system_file(upower_conf)?;
     let mut temp_upower_file = NamedTempFile::new()?;
     writeln!(temp_upower_file, "{}", lines.join("\n"))?;
     let status = Command::new("sudo")
@@ -2376,7 +3380,7 @@ fn configure_upower() -> Result<(), std::io::Error> {
         .arg("root")
         .arg(temp_upower_file.path())
         .arg(upower_conf)
-        .status()?;
+        .run_status()?;
     if !status.success() {
         eprintln!(
             "{}",
@@ -2390,7 +3394,7 @@ fn configure_upower() -> Result<(), std::io::Error> {
     Ok(())
 }
 fn print_logo() {
-    println!(
+    status!(
         r#"
                                                                                                     
                                              ++++++++++                                             
@@ -2441,3 +3445,23 @@ fn print_logo() {
                                                *++++* "#
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template_provides_the_defaults() {
+        let manifest = PackageManifest::default();
+        assert!(manifest.nvidia().contains(&"nvidia-dkms"));
+        assert!(manifest.amd().contains(&"vulkan-radeon"));
+        assert!(!manifest.aur().is_empty());
+        assert!(manifest.rust_apps().is_none());
+        let pins = manifest.driver_pins();
+        assert_eq!(pins.len(), 1);
+        assert!(!pins[0].gsp_firmware);
+        let turing = regex::Regex::new(&pins[0].device_ids).unwrap();
+        assert!(turing.is_match("0x1f06"));
+        assert!(!turing.is_match("0x2204"));
+    }
+}
@@ -8,30 +8,31 @@
 //! 3. Resolves the target file path (expanding `~`).
 //! 4. Detects the current compositor (Hyprland/Sway/Niri) to apply specific window rules (floating/size).
 //! 5. Launches the user's preferred terminal running a pager (e.g., `bat` or `less`) to view the file.
+//!    A sheet can override the pager with its own `renderer` (e.g., `glow` for markdown).
+//!
+//! `kb-launcher --grep` skips the terminal: the chosen sheet's lines go through a second rofi
+//! menu for substring filtering, and the picked line is copied with `wl-copy`.
+//!
+//! `kb-launcher --list` prints the sheets for the current compositor as JSON
+//! (`[{"name", "file", "exists"}]`, paths expanded) so other tools can build their own menu.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
 use std::io::Write;
 use std::process::{Command, Stdio};
+use common::{expand_path, load_config};
 
 
-fn expand_path(path: &str) -> PathBuf {
-    if let Some(stripped) = path.strip_prefix("~/")
-        && let Some(home) = dirs::home_dir() {
-            return home.join(stripped);
-        }
-    PathBuf::from(path)
-}
-
 // --- Configuration Models ---
 #[derive(Deserialize, Debug)]
 struct Sheet {
     name: String, // Display name in Rofi (e.g., "Vim Keys")
     file: String, // Path to file (e.g., "~/docs/vim.md")
     compositor: Option<String>,
+    renderer: Option<String>, // Overrides global.pager for this sheet (e.g., "glow -p")
 }
 
 #[derive(Deserialize, Debug)]
@@ -61,18 +62,6 @@ struct GlobalConfig {
     kb_launcher: KbLauncherConfig,
 }
 
-/// Loads the centralized configuration file.
-fn load_config() -> Result<GlobalConfig> {
-    let config_path = dirs::home_dir()
-        .context("Cannot find home dir")?
-        .join(".config/rust-dotfiles/config.toml");
-    let config_str = fs::read_to_string(&config_path)
-        .with_context(|| format!("Failed to read config file from path {}", config_path.display()))?;
-    let config: GlobalConfig = toml::from_str(&config_str)
-        .context("Failed to parse config file")?;
-    Ok(config)
-}
-
 /// Detects the active Wayland compositor via environment variables.
 fn get_compositor() -> String {
     if env::var("NIRI_SOCKET").is_ok() { return "niri".to_string(); }
@@ -85,6 +74,55 @@ fn get_compositor() -> String {
     "unknown".to_string()
 }
 
+/// Checks whether a command's program (first word) is an executable in $PATH.
+fn in_path(command: &str) -> bool {
+    let Some(program) = command.split_whitespace().next() else {
+        return false;
+    };
+    if program.contains('/') {
+        return PathBuf::from(program).is_file();
+    }
+    env::var_os("PATH")
+        .map(|paths| env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+        .unwrap_or(false)
+}
+
+/// Picks the viewer for a sheet: its own renderer if installed, else the global pager.
+fn resolve_renderer<'a>(sheet: &'a Sheet, pager: &'a str) -> &'a str {
+    match sheet.renderer.as_deref() {
+        Some(renderer) if in_path(renderer) => renderer,
+        Some(renderer) => {
+            eprintln!(
+                "Warning: renderer '{}' for sheet '{}' not found in $PATH, using '{}'",
+                renderer, sheet.name, pager
+            );
+            pager
+        }
+        None => pager,
+    }
+}
+
+/// One `--list` entry.
+#[derive(Serialize)]
+struct SheetInfo<'a> {
+    name: &'a str,
+    file: PathBuf,
+    exists: bool,
+}
+
+/// --list: prints the sheets as JSON instead of showing a menu.
+fn print_sheet_list(sheets: &[&Sheet]) -> Result<()> {
+    let infos: Vec<SheetInfo> = sheets
+        .iter()
+        .map(|s| {
+            let file = expand_path(&s.file);
+            SheetInfo { name: &s.name, exists: file.is_file(), file }
+        })
+        .collect();
+    println!("{}", serde_json::to_string(&infos)?);
+    Ok(())
+}
+
 // --- UI Logic ---
 
 /// Spawns Rofi to let the user select a sheet.
@@ -96,12 +134,17 @@ fn show_rofi_menu(sheets: &[&Sheet]) -> Result<String> {
         .map(|s| s.name.as_str())
         .collect::<Vec<_>>()
         .join("\n");
+    run_rofi("View Cheat Sheet:", &menu_string)
+}
+
+/// Runs `rofi -dmenu` over newline-separated entries and returns the chosen one.
+fn run_rofi(prompt: &str, menu_string: &str) -> Result<String> {
     // Spawn Rofi
     let mut child = Command::new("rofi")
         .arg("-dmenu")
         .arg("-i")
         .arg("-p")
-        .arg("View Cheat Sheet:")
+        .arg(prompt)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .spawn()
@@ -123,10 +166,38 @@ fn show_rofi_menu(sheets: &[&Sheet]) -> Result<String> {
     Ok(choice.trim().to_string())
 }
 
+/// --grep: filter a sheet line by line in rofi and copy the selected line.
+fn grep_sheet(sheet_path: &Path, sheet_name: &str) -> Result<()> {
+    let contents = fs::read_to_string(sheet_path)
+        .with_context(|| format!("Failed to read sheet {}", sheet_path.display()))?;
+    let lines = contents
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let line = run_rofi(&format!("{}:", sheet_name), &lines)?;
+    if line.is_empty() {
+        return Ok(());
+    }
+
+    let mut child = Command::new("wl-copy")
+        .stdin(Stdio::piped())
+        .spawn()
+        .context("Failed to spawn wl-copy")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(line.as_bytes())
+            .context("Failed to write to wl-copy stdin")?;
+    }
+    child.wait().context("Failed to wait for wl-copy")?;
+    Ok(())
+}
+
 // --- Main Execution ---
 fn main() -> Result<()> {
     // Setup
-    let global_config = load_config()?;
+    let global_config = load_config::<GlobalConfig>()?;
     let global_conf = global_config.global;
     let kb_config = global_config.kb_launcher;
     let compositor = get_compositor();
@@ -138,6 +209,9 @@ fn main() -> Result<()> {
         })
         .collect();
 
+    if env::args().any(|a| a == "--list") {
+        return print_sheet_list(&available_sheets);
+    }
     if available_sheets.is_empty() {
         anyhow::bail!("No cheat sheets available for the current compositor");
     }
@@ -151,6 +225,10 @@ fn main() -> Result<()> {
 
     let sheet_path = expand_path(&chosen_sheet.file);
 
+    if env::args().any(|a| a == "--grep") {
+        return grep_sheet(&sheet_path, &chosen_sheet.name);
+    }
+
     // Environment specific args
     // Inject specific arguments (like `--title=float_me`) so the window manager 
     // knows to float this specific terminal window.
@@ -164,7 +242,8 @@ fn main() -> Result<()> {
     // a. Runs the pager (bat/less) on the file.
     // b. Prints a "Press key to close" prompt.
     // c. Waits for user input (read -n 1) so the terminal doesn't close immediately.
-    let inner_cmd = format!("{} '{}'; printf %s 'Press any key to close...'; read -n 1 -s -r", global_conf.pager, sheet_path.display());
+    let renderer = resolve_renderer(chosen_sheet, &global_conf.pager);
+    let inner_cmd = format!("{} '{}'; printf %s 'Press any key to close...'; read -n 1 -s -r", renderer, sheet_path.display());
     //Execution
     Command::new(&global_conf.terminal)
         .args(compositor_args)
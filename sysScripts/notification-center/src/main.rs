@@ -0,0 +1,279 @@
+//! Notification Center (notification-center)
+//!
+//! A persistent, searchable history for desktop notifications.
+//!
+//! swaync only keeps what is currently on screen. This tool fills the gap:
+//! 1. **Monitor (default):** Becomes a D-Bus monitor for `org.freedesktop.Notifications.Notify`
+//!    calls and appends each one (app, summary, body, timestamp) to a JSON Lines file.
+//!    It never answers the calls, so it runs alongside swaync without competing for the name.
+//! 2. **--list:** Prints the history (newest first) for scripts and terminals.
+//! 3. **--rofi:** Browses the history in rofi. Enter copies the body, Alt+Delete clears it.
+//! 4. **--clear:** Wipes the history file.
+
+use anyhow::{Context, Result, anyhow};
+use chrono::{Local, TimeZone};
+use common::expand_path;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use zbus::blocking::{Connection, MessageIterator};
+use zbus::blocking::fdo::MonitoringProxy;
+use zbus::message::Type as MessageType;
+use zbus::zvariant::OwnedValue;
+use zbus::MatchRule;
+
+// Rewrite the file down to `max_entries` after this many appends (and on startup).
+const COMPACT_EVERY: usize = 50;
+
+// --- Config Models ---
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct NotificationConfig {
+    history_file: String,
+    max_entries: usize,
+    rofi_config: String,
+    message: String,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            history_file: "~/.local/share/notification-center/history.jsonl".to_string(),
+            max_entries: 500,
+            rofi_config: "~/.config/rofi/config-notifications.rasi".to_string(),
+            message: "ENTER = Copy Body | ALT+DEL = Clear History".to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct GlobalConfig {
+    // Optional so existing config.toml files keep working without the new section.
+    #[serde(default)]
+    notification_center: NotificationConfig,
+}
+
+fn load_config() -> Result<NotificationConfig> {
+    let config_path = common::config_path()?;
+    let config_str = match fs::read_to_string(&config_path) {
+        Ok(s) => s,
+        Err(_) => return Ok(NotificationConfig::default()),
+    };
+    let config: GlobalConfig = toml::from_str(&config_str)
+        .context("Failed to parse config.toml. Check for syntax errors.")?;
+    Ok(config.notification_center)
+}
+
+// --- Data Model ---
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Entry {
+    timestamp: i64, // Unix seconds
+    app: String,
+    summary: String,
+    body: String,
+}
+
+// --- Persistence ---
+
+fn append_entry(path: &Path, entry: &Entry) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+/// Reads the history oldest-first. Corrupt lines (e.g. a torn write) are skipped.
+fn read_entries(path: &Path) -> Vec<Entry> {
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Keeps only the newest `max_entries` lines. The result is written to a sibling temp file
+/// and renamed over the history, so a concurrent reader (--list, --rofi) never sees it
+/// truncated or half-written.
+fn compact(path: &Path, max_entries: usize) -> Result<()> {
+    let entries = read_entries(path);
+    if entries.len() <= max_entries {
+        return Ok(());
+    }
+    let keep = &entries[entries.len() - max_entries..];
+    let mut out = String::new();
+    for entry in keep {
+        out.push_str(&serde_json::to_string(entry)?);
+        out.push('\n');
+    }
+    let tmp = path.with_extension("jsonl.tmp");
+    fs::write(&tmp, out).context("Failed to compact history")?;
+    fs::rename(&tmp, path).context("Failed to replace history")?;
+    Ok(())
+}
+
+// --- Modes ---
+
+/// Notify(app_name, replaces_id, app_icon, summary, body, actions, hints, expire_timeout)
+type NotifyArgs = (String, u32, String, String, String, Vec<String>, HashMap<String, OwnedValue>, i32);
+
+fn run_monitor(config: &NotificationConfig) -> Result<()> {
+    let history = expand_path(&config.history_file);
+    compact(&history, config.max_entries)?;
+
+    let conn = Connection::session().context("Failed to connect to the session bus")?;
+    let rule = MatchRule::builder()
+        .msg_type(MessageType::MethodCall)
+        .interface("org.freedesktop.Notifications")?
+        .member("Notify")?
+        .build();
+    // After this call the connection only receives matching traffic; it can't make requests.
+    MonitoringProxy::new(&conn)?
+        .become_monitor(&[rule], 0)
+        .context("BecomeMonitor failed (is the session bus policy blocking monitors?)")?;
+
+    let mut appended = 0;
+    for msg in MessageIterator::from(&conn) {
+        let msg = match msg {
+            Ok(m) => m,
+            Err(e) => {
+                eprintln!("D-Bus error: {}", e);
+                continue;
+            }
+        };
+        if msg.header().member().map(|m| m.as_str()) != Some("Notify") {
+            continue;
+        }
+        let Ok((app, _, _, summary, body, _, _, _)) = msg.body().deserialize::<NotifyArgs>() else {
+            continue;
+        };
+
+        let entry = Entry { timestamp: Local::now().timestamp(), app, summary, body };
+        if let Err(e) = append_entry(&history, &entry) {
+            eprintln!("Failed to record notification: {}", e);
+            continue;
+        }
+
+        appended += 1;
+        if appended % COMPACT_EVERY == 0
+            && let Err(e) = compact(&history, config.max_entries) {
+                eprintln!("Failed to compact history: {}", e);
+            }
+    }
+    Ok(())
+}
+
+/// One line per notification, newest first. Newlines are flattened for rofi.
+fn format_entries(entries: &[Entry]) -> Vec<String> {
+    entries
+        .iter()
+        .rev()
+        .map(|e| {
+            let when = Local
+                .timestamp_opt(e.timestamp, 0)
+                .single()
+                .map(|t| t.format("%b %d %H:%M").to_string())
+                .unwrap_or_default();
+            let body = e.body.replace('\n', " ");
+            if body.is_empty() {
+                format!("{}  {} — {}", when, e.app, e.summary)
+            } else {
+                format!("{}  {} — {}: {}", when, e.app, e.summary, body)
+            }
+        })
+        .collect()
+}
+
+fn run_list(config: &NotificationConfig) -> Result<()> {
+    let entries = read_entries(&expand_path(&config.history_file));
+    for line in format_entries(&entries) {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+fn run_clear(config: &NotificationConfig) -> Result<()> {
+    let history = expand_path(&config.history_file);
+    if history.exists() {
+        fs::write(&history, "").context("Failed to clear history")?;
+    }
+    Ok(())
+}
+
+fn copy_to_clipboard(text: &str) -> Result<()> {
+    let mut child = Command::new("wl-copy")
+        .stdin(Stdio::piped())
+        .spawn()
+        .context("Failed to spawn 'wl-copy'")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    if !child.wait()?.success() {
+        return Err(anyhow!("wl-copy failed"));
+    }
+    Ok(())
+}
+
+fn run_rofi(config: &NotificationConfig) -> Result<()> {
+    let entries = read_entries(&expand_path(&config.history_file));
+    let lines = format_entries(&entries);
+
+    let mut child = Command::new("rofi")
+        .arg("-i")
+        .arg("-dmenu")
+        .arg("-format")
+        .arg("i") // Return the selected index so we can recover the untruncated body
+        .arg("-kb-custom-1")
+        .arg("Alt+Delete") // Exit Code 10
+        .arg("-config")
+        .arg(expand_path(&config.rofi_config))
+        .arg("-mesg")
+        .arg(&config.message)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .context("Failed to spawn rofi")?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(lines.join("\n").as_bytes())?;
+    }
+
+    let output = child.wait_with_output()?;
+    let selection = String::from_utf8(output.stdout)?.trim().to_string();
+    match output.status.code().unwrap_or(1) {
+        0 => {
+            // Index is into the newest-first list.
+            if let Ok(index) = selection.parse::<usize>()
+                && let Some(entry) = entries.iter().rev().nth(index) {
+                    let text = if entry.body.is_empty() { &entry.summary } else { &entry.body };
+                    copy_to_clipboard(text)?;
+                }
+        }
+        10 => run_clear(config)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let config = load_config()?;
+    let mode = std::env::args().nth(1).unwrap_or_default();
+
+    match mode.as_str() {
+        "--list" => run_list(&config),
+        "--rofi" => run_rofi(&config),
+        "--clear" => run_clear(&config),
+        "" | "--monitor" => run_monitor(&config),
+        other => Err(anyhow!("Unknown argument '{}'. Use --monitor, --list, --rofi or --clear", other)),
+    }
+}
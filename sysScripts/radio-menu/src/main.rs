@@ -3,30 +3,67 @@
 //! A minimal internet radio player interface using `rofi` as the frontend and `mpv` as the backend.
 //! 
 //! Features:
-//! 1. **Search:** Queries the Community Radio Browser API (radio-browser.info).
+//! 1. **Search:** Queries the Community Radio Browser API (radio-browser.info) by name or genre tag.
 //! 2. **Favorites:** Persists preferred stations to a JSON file.
-//! 3. **Playback:** Spawns a detached `mpv` process to stream audio.
-//! 4. **Menu Navigation:** Implements a loop-based state machine to handle "Back", "Search", and "Home".
+//! 3. **Playback:** Spawns a detached `mpv` process to stream audio, with the `mpv-mpris`
+//!    plugin loaded so the stream shows up in the sidebar media widget (and any `playerctl` client).
+//! 4. **Volume:** mpv listens on an IPC socket so volume can change without restarting the stream.
+//! 5. **Now Playing:** Remembers the current station so reopening the menu shows what's on.
+//! 6. **Recording:** Toggles mpv's `stream-record` over the same socket to dump the stream to disk.
+//! 7. **Menu Navigation:** Implements a loop-based state machine to handle "Back", "Search", and "Home".
+//! 8. **Sharing:** `--export <file>` / `--import <file>` move favorites as an M3U or JSON playlist.
 
 use anyhow::{anyhow, Context, Result};
 use notify_rust::Notification;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+mod playlist;
 
 // --- Constants ---
 // Single Source of Truth for UI elements ensures consistency across re-renders.
 const ICON_STOP: &str = "⏹ Stop Radio";
 const ICON_SEARCH: &str = "🔍 Search Online...";
+const ICON_GENRE: &str = "🏷️ Browse by Genre";
+const ICON_VOL_UP: &str = "🔊 Volume Up";
+const ICON_VOL_DOWN: &str = "🔉 Volume Down";
+const ICON_RECORD: &str = "⏺ Record Stream";
+const ICON_RECORD_STOP: &str = "⏺ Stop Recording";
 const PREFIX_FAV: &str = "⭐ ";
+const PREFIX_PLAYING: &str = "▶ ";
 const ICON_REDO: &str = "🔄 Try Again";
 
 const RESULT_LIMIT: usize = 15; // API limit to keep the UI snappy
 
+// Radio Browser has no single API host. `all.api...` round-robins across the mirrors and
+// lists them at /json/servers; the fallbacks are used if even that lookup fails.
+const API_DISCOVERY_URL: &str = "https://all.api.radio-browser.info/json/servers";
+const FALLBACK_API_SERVERS: &[&str] = &[
+    "de1.api.radio-browser.info",
+    "de2.api.radio-browser.info",
+    "fi1.api.radio-browser.info",
+];
+const API_TIMEOUT: Duration = Duration::from_secs(5);
+
+// mpv JSON IPC socket for the running stream (see `--input-ipc-server`).
+const MPV_SOCKET: &str = "/tmp/radio-mpv.sock";
+const VOLUME_STEP: i32 = 5;
+
+// Where the `mpv-mpris` package installs its plugin. Checked in order.
+const MPRIS_PLUGIN_PATHS: &[&str] = &[
+    "/usr/lib/mpv-mpris/mpris.so",
+    "/usr/lib/mpv/mpris.so",
+    "/usr/share/mpv/scripts/mpris.so",
+];
+
 // Rofi UI Hints (displayed in menu)
 const SEARCH_PROMPT: &str = "Type to search station name...";
+const GENRE_PROMPT: &str = "Type a genre tag (e.g. jazz, lofi, classical)...";
 const HOME_HINT: &str = "<b>Enter:</b> Play  |  <b>Ctrl+R:</b> Remove Favorite";
 const SEARCH_HINT: &str = "<b>Enter:</b> Play  |  <b>Ctrl+S:</b> Save to Favorites  |  <b>Esc:</b> Back to Search";
 
@@ -58,6 +95,12 @@ struct Station {
 struct RadioConfig {
     rofi_config: String,
     message: String,
+    #[serde(default = "default_record_dir")]
+    record_dir: String,
+}
+
+fn default_record_dir() -> String {
+    "~/Music/radio".to_string()
 }
 
 #[derive(Deserialize, Debug)]
@@ -71,6 +114,12 @@ fn get_config_path() -> PathBuf {
         .join(".config/rust-dotfiles/config.toml")
 }
 
+fn get_now_playing_path() -> PathBuf {
+    dirs::cache_dir()
+        .expect("Could not find cache directory")
+        .join("radio-menu/now_playing.json")
+}
+
 fn get_favorites_path() -> PathBuf {
     dirs::home_dir()
         .expect("Could not find home directory")
@@ -84,14 +133,84 @@ fn load_config() -> Result<GlobalConfig> {
     Ok(config)
 }
 
+#[derive(Deserialize, Debug)]
+struct ApiServer {
+    name: String,
+}
+
 // --- Network Logic ---
 
-/// Queries the Radio Browser API.
+fn http_client() -> Result<reqwest::blocking::Client> {
+    // Radio Browser asks clients to send a descriptive User-Agent.
+    reqwest::blocking::Client::builder()
+        .user_agent(concat!("radio-menu/", env!("CARGO_PKG_VERSION")))
+        .timeout(API_TIMEOUT)
+        .build()
+        .context("Failed to build HTTP client")
+}
+
+/// Shuffles in place (Fisher-Yates with a clock-seeded xorshift).
+/// Only used to spread load across mirrors, so it doesn't need a real RNG.
+fn shuffle<T>(items: &mut [T]) {
+    let mut seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+        | 1;
+    for i in (1..items.len()).rev() {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        items.swap(i, (seed % (i as u64 + 1)) as usize);
+    }
+}
+
+/// Returns the Radio Browser mirrors in random order, as the project recommends.
+fn get_api_servers(client: &reqwest::blocking::Client) -> Vec<String> {
+    let discovered: Vec<String> = client
+        .get(API_DISCOVERY_URL)
+        .send()
+        .and_then(|r| r.json::<Vec<ApiServer>>())
+        .map(|servers| servers.into_iter().map(|s| s.name).collect())
+        .unwrap_or_default();
+
+    let mut servers = if discovered.is_empty() {
+        FALLBACK_API_SERVERS.iter().map(|s| s.to_string()).collect()
+    } else {
+        discovered
+    };
+    // The list has one entry per A/AAAA record, so the same host can appear twice.
+    servers.sort();
+    servers.dedup();
+    shuffle(&mut servers);
+    servers
+}
+
+/// Queries a `/json/stations/...` endpoint, trying each mirror until one answers.
 /// Uses a blocking client because the UI (Rofi) cannot display results until the search completes anyway.
+fn query_stations(endpoint: &str) -> Result<Vec<Station>> {
+    let client = http_client()?;
+    let mut errors = Vec::new();
+    for server in get_api_servers(&client) {
+        let url = format!("https://{}/json/stations/{}", server, endpoint);
+        match client.get(&url).send().and_then(|r| r.error_for_status()).and_then(|r| r.json::<Vec<Station>>()) {
+            Ok(response) => return Ok(response.into_iter().take(RESULT_LIMIT).collect()),
+            Err(e) => errors.push(format!("{}: {}", server, e)),
+        }
+    }
+    Err(anyhow!("All Radio Browser mirrors failed:\n{}", errors.join("\n")))
+}
+
 fn search_stations(query: &str) -> Result<Vec<Station>> {
-    let url = format!("https://de1.api.radio-browser.info/json/stations/byname/{}", query);
-    let response = reqwest::blocking::get(&url)?.json::<Vec<Station>>()?;
-    Ok(response.into_iter().take(RESULT_LIMIT).collect())
+    query_stations(&format!("byname/{}", query))
+}
+
+/// Stations tagged with `tag`, most popular first.
+fn search_by_tag(tag: &str) -> Result<Vec<Station>> {
+    query_stations(&format!(
+        "bytag/{}?order=clickcount&reverse=true&hidebroken=true&limit={}",
+        tag, RESULT_LIMIT
+    ))
 }
 
 // --- Persistence Logic ---
@@ -105,48 +224,198 @@ fn load_favorites() -> Result<Vec<Station>> {
     Ok(stations)
 }
 
+fn write_favorites(favorites: &[Station]) -> Result<()> {
+    let json = serde_json::to_string_pretty(favorites)?;
+    fs::write(get_favorites_path(), json)?;
+    Ok(())
+}
+
 fn save_favorite(station: Station) -> Result<()> {
-    let path = get_favorites_path();
     let mut favorites = load_favorites()?;
     // Prevent duplicates by UUID
     if !favorites.iter().any(|s| s.stationuuid == station.stationuuid) {
         favorites.push(station);
-        let json = serde_json::to_string_pretty(&favorites)?;
-        fs::write(path, json)?;
+        write_favorites(&favorites)?;
     }
     Ok(())
 }
 
 fn remove_favorite(station_name: &str) -> Result<()> {
-    let path = get_favorites_path();
     let mut favorites = load_favorites()?;
     favorites.retain(|s| s.name != station_name);
-    let json = serde_json::to_string_pretty(&favorites)?;
-    fs::write(path, json)?;
+    write_favorites(&favorites)
+}
+
+/// What `play_station` last started. Written on successful spawn, removed by `stop_radio`.
+#[derive(Deserialize, Serialize, Debug)]
+struct NowPlaying {
+    name: String,
+    stationuuid: String,
+    /// File mpv is currently recording the stream to, if any.
+    #[serde(default)]
+    recording: Option<PathBuf>,
+}
+
+fn save_now_playing(state: &NowPlaying) -> Result<()> {
+    let path = get_now_playing_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string(state)?)?;
     Ok(())
 }
 
+/// Returns the current station, or None if nothing is playing.
+/// If mpv died on its own (stream dropped), the stale state file is cleaned up here.
+fn load_now_playing() -> Option<NowPlaying> {
+    let path = get_now_playing_path();
+    let state: NowPlaying = serde_json::from_str(&fs::read_to_string(&path).ok()?).ok()?;
+    let running = Command::new("pgrep")
+        .arg("-x")
+        .arg("mpv")
+        .stdout(Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false);
+    if !running {
+        let _ = fs::remove_file(path);
+        return None;
+    }
+    Some(state)
+}
+
+/// Rofi renders `-mesg` as Pango markup, so station names need escaping.
+fn escape_markup(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
 // --- Player Logic ---
 
 /// Kills any existing background player instance to prevent audio overlap.
 fn stop_radio() {
     let _ = Command::new("pkill").arg("-x").arg("mpv").status();
+    let _ = fs::remove_file(get_now_playing_path());
+    let _ = fs::remove_file(MPV_SOCKET); // mpv doesn't unlink it when killed
+}
+
+/// Sends one JSON IPC command to the running player.
+fn mpv_command(command: serde_json::Value) -> Result<()> {
+    let mut socket = UnixStream::connect(MPV_SOCKET).context("Radio is not playing")?;
+    writeln!(socket, "{}", serde_json::json!({ "command": command }))
+        .context("Failed to send command to mpv")?;
+    Ok(())
+}
+
+/// Reads one property from the running player as a string. Events mpv pushes to every
+/// client can arrive before the reply, so lines are skipped until the request id matches.
+fn mpv_get_property(name: &str) -> Option<String> {
+    let mut socket = UnixStream::connect(MPV_SOCKET).ok()?;
+    socket.set_read_timeout(Some(Duration::from_secs(1))).ok()?;
+    writeln!(socket, "{}", serde_json::json!({ "command": ["get_property", name], "request_id": 1 })).ok()?;
+    BufReader::new(socket)
+        .lines()
+        .map_while(|line| line.ok())
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(&line).ok())
+        .find(|reply| reply["request_id"] == 1)
+        .and_then(|reply| reply["data"].as_str().map(str::to_string))
+}
+
+/// File extension for a `stream-record` dump. mpv writes the stream in its own format,
+/// so the extension follows the demuxer (`file-format`), then the codec; Matroska is
+/// the catch-all since it can hold any codec.
+fn record_extension(file_format: Option<&str>, codec: Option<&str>) -> &'static str {
+    match (file_format, codec) {
+        (Some("ogg"), Some("opus")) => "opus",
+        (Some("ogg"), _) => "ogg",
+        (Some("mp3"), _) | (_, Some("mp3")) => "mp3",
+        (Some("aac"), _) | (_, Some("aac")) => "aac",
+        (Some("flac"), _) | (_, Some("flac")) => "flac",
+        (Some("mpegts" | "hls"), _) => "ts",
+        (_, Some("opus")) => "opus",
+        (_, Some("vorbis")) => "ogg",
+        _ => "mka",
+    }
+}
+
+/// Adjusts the running player's volume by `delta` over the IPC socket.
+fn change_volume(delta: i32) -> Result<()> {
+    mpv_command(serde_json::json!(["add", "volume", delta]))
+}
+
+/// Station names go into a file name, so keep them to something every filesystem accepts.
+fn file_safe(name: &str) -> String {
+    let safe: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    safe.trim_matches('_').to_string()
+}
+
+/// Starts or stops mpv's `stream-record` on the live stream. Returns the notification text.
+/// Setting the property to an empty string closes the file; mpv keeps playing either way.
+fn toggle_recording(now_playing: &mut NowPlaying, config: &RadioConfig) -> Result<String> {
+    if let Some(path) = now_playing.recording.take() {
+        mpv_command(serde_json::json!(["set_property", "stream-record", ""]))?;
+        save_now_playing(now_playing)?;
+        return Ok(format!("Recording saved to {}", path.display()));
+    }
+    let dir = expand_path(&config.record_dir);
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let extension = record_extension(
+        mpv_get_property("file-format").as_deref(),
+        mpv_get_property("audio-codec-name").as_deref(),
+    );
+    let path = dir.join(format!("{}-{}.{}", file_safe(&now_playing.name), timestamp, extension));
+    mpv_command(serde_json::json!(["set_property", "stream-record", path]))?;
+    let message = format!("Recording to {}", path.display());
+    now_playing.recording = Some(path);
+    save_now_playing(now_playing)?;
+    Ok(message)
+}
+
+/// Returns the MPRIS plugin to pass via `--script`, or None if mpv already autoloads it.
+/// Loading it twice would register two players on the session bus.
+fn mpris_script() -> Option<PathBuf> {
+    let mut autoload_dirs = vec![PathBuf::from("/etc/mpv/scripts")];
+    if let Some(config_dir) = dirs::config_dir() {
+        autoload_dirs.push(config_dir.join("mpv/scripts"));
+    }
+    if autoload_dirs.iter().any(|dir| dir.join("mpris.so").exists()) {
+        return None;
+    }
+
+    MPRIS_PLUGIN_PATHS
+        .iter()
+        .map(PathBuf::from)
+        .find(|path| path.exists())
 }
 
 /// Spawns a detached mpv process to stream the audio.
-fn play_station(station_name: &str, url: &str) -> Result<()> {
+/// The forced media title is what MPRIS reports as `xesam:title`, so the sidebar shows the station name.
+fn play_station(station: &Station) -> Result<()> {
     stop_radio(); // Enforce single-instance playback
+    let station_name = &station.name;
     
-    Command::new("mpv")
-        .arg("--no-video")
+    let mut cmd = Command::new("mpv");
+    cmd.arg("--no-video")
         .arg(format!("--force-media-title={}", station_name))
-        .arg(url)
+        .arg(format!("--input-ipc-server={}", MPV_SOCKET));
+    if let Some(script) = mpris_script() {
+        cmd.arg(format!("--script={}", script.display()));
+    }
+    cmd.arg(&station.url_resolved)
         .stdin(Stdio::null())
         .stdout(Stdio::null())
         .stderr(Stdio::null())
         .spawn()
         .context("Failed to spawn mpv")?;
 
+    let state = NowPlaying { name: station.name.clone(), stationuuid: station.stationuuid.clone(), recording: None };
+    if let Err(e) = save_now_playing(&state) {
+        eprintln!("Failed to save now-playing state: {}", e);
+    }
+
     let _ = Notification::new()
         .summary("Radio Playing")
         .body(station_name)
@@ -204,22 +473,45 @@ fn show_rofi(options: &[String], prompt: &str, config: &RadioConfig, custom_mess
 
 // --- Search Workflow ---
 
+/// What the search loop looks stations up by.
+#[derive(Clone, Copy)]
+enum SearchKind {
+    Name,
+    Tag,
+}
+
+impl SearchKind {
+    fn prompt(self) -> &'static str {
+        match self {
+            SearchKind::Name => SEARCH_PROMPT,
+            SearchKind::Tag => GENRE_PROMPT,
+        }
+    }
+
+    fn fetch(self, query: &str) -> Result<Vec<Station>> {
+        match self {
+            SearchKind::Name => search_stations(query),
+            SearchKind::Tag => search_by_tag(query),
+        }
+    }
+}
+
 /// Handles the search loop: Prompt -> Fetch -> Display Results -> Handle Action.
 /// Returns true if an action was taken (play/save), false if user backed out.
-fn search(initial_query: Option<String>, config: &RadioConfig) -> Result<bool> {
+fn search(kind: SearchKind, initial_query: Option<String>, config: &RadioConfig) -> Result<bool> {
     let mut current_query = initial_query;
     loop {
         // 1. Get Query (either passed in or prompted)
         let query = match current_query.take() {
             Some(q) => q,
             None => {
-                let (code, q) = show_rofi(&[], "Search Query", config, Some(SEARCH_PROMPT))?;
+                let (code, q) = show_rofi(&[], "Search Query", config, Some(kind.prompt()))?;
                 if code == 1 || q.is_empty() { return Ok(false); }
                 q
             }
         };
         // 2. Perform Search
-        let results = search_stations(&query)?;
+        let results = kind.fetch(&query)?;
 
         // 3. Handle No Results
         if results.is_empty() {
@@ -251,12 +543,12 @@ fn search(initial_query: Option<String>, config: &RadioConfig) -> Result<bool> {
             if r_code == 10 {
                 // Ctrl+S -> Save
                 save_favorite(station.clone())?;
-                play_station(&station.name, &station.url_resolved)?;
+                play_station(&station)?;
                 let _ = Notification::new().summary("Radio").body("Station Saved").show();
                 return Ok(true);
             } else if r_code == 0 {
                 // Enter -> Play
-                play_station(&station.name, &station.url_resolved)?;
+                play_station(&station)?;
                 return Ok(true);
             }
         }
@@ -272,7 +564,7 @@ fn handle_favorite_actions(clean_name: &str, code: i32, favorites: &[Station]) -
     } else if code == 0 {
         // Enter: Play Favorite
         if let Some(station) = favorites.iter().find(|s| s.name == clean_name) {
-            play_station(&station.name, &station.url_resolved)?;
+            play_station(station)?;
             Ok(Action::Exit)
         } else {
             Ok(Action::Continue)
@@ -283,6 +575,18 @@ fn handle_favorite_actions(clean_name: &str, code: i32, favorites: &[Station]) -
 }
 // --- Main Execution ---
 fn main() -> Result<()> {
+    // Playlist sharing runs headless, without rofi or the config
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        [] => {}
+        ["--export", file] => return playlist::export(Path::new(file)),
+        ["--import", file] => return playlist::import(Path::new(file)),
+        _ => {
+            println!("Usage: radio-menu [--export <file> | --import <file>]");
+            return Ok(());
+        }
+    }
+
     let global_config = load_config()?;
     let config = global_config.radio_menu;
     let mut menu_options = Vec::with_capacity(20);
@@ -291,33 +595,75 @@ fn main() -> Result<()> {
     // Keeps the menu open until the user plays a station or explicitly quits.
     'main_menu: loop {
         let favorites = load_favorites()?;
+        let mut now_playing = load_now_playing();
         // Rebuild Menu Options
         menu_options.clear();
         menu_options.push(ICON_STOP.to_string());
         menu_options.push(ICON_SEARCH.to_string());
+        menu_options.push(ICON_GENRE.to_string());
+        menu_options.push(ICON_VOL_UP.to_string());
+        menu_options.push(ICON_VOL_DOWN.to_string());
+        // Recording only makes sense while a stream is live
+        match &now_playing {
+            Some(np) if np.recording.is_some() => menu_options.push(ICON_RECORD_STOP.to_string()),
+            Some(_) => menu_options.push(ICON_RECORD.to_string()),
+            None => {}
+        }
 
         for station in &favorites {
-            menu_options.push([PREFIX_FAV, &station.name].concat());
+            let playing = now_playing.as_ref().is_some_and(|np| np.stationuuid == station.stationuuid);
+            let marker = if playing { PREFIX_PLAYING } else { "" };
+            menu_options.push([marker, PREFIX_FAV, &station.name].concat());
         }
 
+        let hint = match &now_playing {
+            Some(np) => {
+                let rec = if np.recording.is_some() { " (⏺ recording)" } else { "" };
+                format!("<b>Now Playing:</b> {}{}\n{}", escape_markup(&np.name), rec, HOME_HINT)
+            }
+            None => HOME_HINT.to_string(),
+        };
         let (code, selection) = show_rofi(
             &menu_options, 
             "Radio", 
             &config, 
-            Some(HOME_HINT)
+            Some(&hint)
         )?;
+        let selection = selection.strip_prefix(PREFIX_PLAYING).unwrap_or(&selection).to_string();
 
         if code == 1 { break 'main_menu; } // Esc -> Quit
 
         if selection == ICON_STOP {
             stop_radio();
-            let _ = Notification::new().summary("Radio").body("Stopped").show();
+            // Killing mpv closes the recording, so it is saved as-is
+            let body = match now_playing.and_then(|np| np.recording) {
+                Some(path) => format!("Stopped. Recording saved to {}", path.display()),
+                None => "Stopped".to_string(),
+            };
+            let _ = Notification::new().summary("Radio").body(&body).show();
             break 'main_menu; 
+        } else if selection == ICON_RECORD || selection == ICON_RECORD_STOP {
+            if let Some(np) = now_playing.as_mut() {
+                let body = toggle_recording(np, &config).unwrap_or_else(|e| e.to_string());
+                let _ = Notification::new().summary("Radio").body(&body).icon("media-record").show();
+            }
+            break 'main_menu;
+        } else if selection == ICON_VOL_UP || selection == ICON_VOL_DOWN {
+            // Stay in the menu so repeated presses keep adjusting
+            let delta = if selection == ICON_VOL_UP { VOLUME_STEP } else { -VOLUME_STEP };
+            if let Err(e) = change_volume(delta) {
+                let _ = Notification::new().summary("Radio").body(&e.to_string()).show();
+            }
+            continue 'main_menu;
         } else if selection == ICON_SEARCH {
             // Enter Search Loop
-            if search(None, &config)? {
+            if search(SearchKind::Name, None, &config)? {
                 break 'main_menu; // If seach ended in playback, exit app
             }
+        } else if selection == ICON_GENRE {
+            if search(SearchKind::Tag, None, &config)? {
+                break 'main_menu;
+            }
         } else if let Some(clean_name) = selection.strip_prefix(PREFIX_FAV) { 
             // Handle Favorites
             let action = handle_favorite_actions(clean_name, code, &favorites)?;
@@ -328,7 +674,7 @@ fn main() -> Result<()> {
             }
         } else {
             // Handle "Type-to-search" from main menu (User typed a query directly)
-            if search(Some(selection), &config)? {
+            if search(SearchKind::Name, Some(selection), &config)? {
                 break 'main_menu;
             }
         }
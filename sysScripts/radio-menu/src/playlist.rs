@@ -0,0 +1,138 @@
+//! Favorites import/export (`--export <file>` / `--import <file>`).
+//!
+//! `radio_favorites.json` mirrors the Radio Browser API, so stations are shared as a plain
+//! playlist instead: JSON when the file ends in `.json`, extended M3U otherwise. The M3U
+//! keeps the station UUID as an `#EXTINF` attribute so a round trip de-duplicates exactly;
+//! entries from other players have no UUID and use their stream URL in its place.
+
+use std::fs;
+use std::path::Path;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use crate::{Station, load_favorites, write_favorites};
+
+const UUID_ATTR: &str = "radio-browser-uuid";
+
+#[derive(Deserialize, Serialize)]
+struct PlaylistEntry {
+    name: String,
+    url: String,
+    #[serde(default)]
+    uuid: Option<String>,
+    #[serde(default)]
+    tags: String,
+}
+
+impl From<&Station> for PlaylistEntry {
+    fn from(station: &Station) -> Self {
+        PlaylistEntry {
+            name: station.name.clone(),
+            url: station.url_resolved.clone(),
+            uuid: Some(station.stationuuid.clone()),
+            tags: station.tags.clone(),
+        }
+    }
+}
+
+impl From<PlaylistEntry> for Station {
+    fn from(entry: PlaylistEntry) -> Self {
+        Station {
+            stationuuid: entry.uuid.unwrap_or_else(|| entry.url.clone()),
+            name: entry.name,
+            url_resolved: entry.url,
+            tags: entry.tags,
+        }
+    }
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+fn to_m3u(entries: &[PlaylistEntry]) -> String {
+    let mut out = String::from("#EXTM3U\n");
+    for entry in entries {
+        // One entry per line pair; a stray newline would split it
+        let name = entry.name.replace(['\n', '\r'], " ");
+        let uuid = entry.uuid.as_deref().unwrap_or_default().replace('"', "");
+        out.push_str(&format!("#EXTINF:-1 {}=\"{}\",{}\n{}\n", UUID_ATTR, uuid, name, entry.url));
+    }
+    out
+}
+
+/// Reads `#EXTINF:-1 key="value" ...,Title` followed by the stream URL.
+/// A URL without an `#EXTINF` line is kept, named after itself.
+fn parse_m3u(content: &str) -> Vec<PlaylistEntry> {
+    let mut entries = Vec::new();
+    let mut pending: Option<(String, Option<String>)> = None;
+    for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if let Some(info) = line.strip_prefix("#EXTINF:") {
+            // The title follows the first comma outside the quoted attribute values
+            let mut quoted = false;
+            let split = info.find(|c| {
+                if c == '"' {
+                    quoted = !quoted;
+                }
+                c == ',' && !quoted
+            });
+            let (attrs, title) = match split {
+                Some(i) => (&info[..i], info[i + 1..].trim()),
+                None => (info, ""),
+            };
+            let marker = format!("{}=\"", UUID_ATTR);
+            let uuid = attrs
+                .find(&marker)
+                .map(|start| &attrs[start + marker.len()..])
+                .and_then(|rest| rest.split('"').next())
+                .filter(|uuid| !uuid.is_empty())
+                .map(str::to_string);
+            pending = Some((title.to_string(), uuid));
+        } else if !line.starts_with('#') {
+            let (name, uuid) = pending.take().unwrap_or_default();
+            let name = if name.is_empty() { line.to_string() } else { name };
+            entries.push(PlaylistEntry { name, url: line.to_string(), uuid, tags: String::new() });
+        }
+    }
+    entries
+}
+
+/// --export: writes every favorite to `path`.
+pub fn export(path: &Path) -> Result<()> {
+    let entries: Vec<PlaylistEntry> = load_favorites()?.iter().map(PlaylistEntry::from).collect();
+    let content = if is_json(path) {
+        serde_json::to_string_pretty(&entries)?
+    } else {
+        to_m3u(&entries)
+    };
+    fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    println!("Exported {} stations to {}", entries.len(), path.display());
+    Ok(())
+}
+
+/// --import: adds the playlist's stations that aren't favorites yet (same UUID check as
+/// `save_favorite`).
+pub fn import(path: &Path) -> Result<()> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let entries = if is_json(path) {
+        serde_json::from_str(&content)
+            .with_context(|| format!("{} is not a JSON playlist", path.display()))?
+    } else {
+        parse_m3u(&content)
+    };
+
+    let mut favorites = load_favorites()?;
+    let total = entries.len();
+    let mut added = 0;
+    for station in entries.into_iter().map(Station::from) {
+        if !favorites.iter().any(|s| s.stationuuid == station.stationuuid) {
+            favorites.push(station);
+            added += 1;
+        }
+    }
+    if added > 0 {
+        write_favorites(&favorites)?;
+    }
+    println!("Imported {} new stations ({} already saved)", added, total - added);
+    Ok(())
+}
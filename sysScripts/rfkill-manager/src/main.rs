@@ -6,25 +6,73 @@
 //! 2. Output simple JSON for bars (class: "on"/"off").
 //! 3. Send system notification on toggle.
 //! 4. Signal Waybar (SIGRTMIN+10) to update immediately.
+//! 5. Cache the last known state so bar polls rarely have to spawn `rfkill`.
+//!
+//! Usage: rfkill-manager [--status] [--wifi | --bluetooth] [-v | -vv] [--version]
+//! Without `--wifi`/`--bluetooth` everything acts on all radios (Airplane Mode).
 
 use anyhow::{anyhow, Context, Result};
 use notify_rust::Notification;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::env;
+use std::fs;
+use std::path::PathBuf;
 use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 // --- HARDCODED DEFAULTS ---
 // No need to configure these. They are standard.
 const WAYBAR_SIGNAL: i32 = 10; 
 const NOTIFICATION_ICON: &str = "airplane-mode-symbolic"; // Uses system theme icon
+// Toggles through us rewrite the cache right away, so the TTL only bounds how long a
+// change made behind our back (plain `rfkill`, a hardware switch) can go unnoticed.
+const CACHE_FILE: &str = "rfkill_state.json";
+const CACHE_TTL_SECS: u64 = 30;
+
+/// Which radios a command acts on. Maps to rfkill's device types.
+#[derive(Clone, Copy)]
+enum Radio {
+    All,
+    Wifi,
+    Bluetooth,
+}
+
+impl Radio {
+    fn rfkill_type(self) -> &'static str {
+        match self {
+            Radio::All => "all",
+            Radio::Wifi => "wifi",
+            Radio::Bluetooth => "bluetooth",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Radio::All => "Airplane Mode",
+            Radio::Wifi => "Wi-Fi",
+            Radio::Bluetooth => "Bluetooth",
+        }
+    }
+
+    fn icon(self) -> &'static str {
+        match self {
+            Radio::All => NOTIFICATION_ICON,
+            Radio::Wifi => "network-wireless-symbolic",
+            Radio::Bluetooth => "bluetooth-active-symbolic",
+        }
+    }
+}
 
 // --- System Logic ---
 
-/// Queries `rfkill`. Returns true if Airplane Mode is ON (Soft blocked).
-fn is_blocked() -> Result<bool> {
+/// Queries `rfkill`. Returns true if the radio is soft blocked
+/// (for `All`: if anything is, i.e. Airplane Mode is ON).
+fn is_blocked(radio: Radio) -> Result<bool> {
+    log::debug!("Running rfkill list {}", radio.rfkill_type());
     let output = Command::new("rfkill")
         .arg("list")
-        .arg("all")
+        .arg(radio.rfkill_type())
         .output()
         .context("Failed to run 'rfkill list'")?;
 
@@ -36,43 +84,157 @@ fn is_blocked() -> Result<bool> {
     Ok(stdout.contains("Soft blocked: yes"))
 }
 
+// --- State Cache ---
+
+/// Soft-block state of every radio we report on, as of `checked_at` (unix seconds).
+#[derive(Serialize, Deserialize, Clone, Copy)]
+struct RadioState {
+    all: bool,
+    wifi: bool,
+    bluetooth: bool,
+    checked_at: u64,
+}
+
+impl RadioState {
+    fn get(&self, radio: Radio) -> bool {
+        match radio {
+            Radio::All => self.all,
+            Radio::Wifi => self.wifi,
+            Radio::Bluetooth => self.bluetooth,
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn cache_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join(CACHE_FILE))
+}
+
+/// Asks rfkill for every radio and records the answer.
+fn refresh_state() -> RadioState {
+    // A failed query reads as unblocked, as the bar showed before the cache existed
+    let query = |radio: Radio| {
+        is_blocked(radio).unwrap_or_else(|e| {
+            log::warn!("{}", e);
+            false
+        })
+    };
+    let state = RadioState {
+        all: query(Radio::All),
+        wifi: query(Radio::Wifi),
+        bluetooth: query(Radio::Bluetooth),
+        checked_at: unix_now(),
+    };
+    // A cache we can't write just means the next poll asks rfkill again
+    if let Some(path) = cache_path()
+        && let Ok(json) = serde_json::to_string(&state)
+        && let Err(e) = fs::write(&path, json)
+    {
+        log::warn!("Failed to write {}: {}", path.display(), e);
+    }
+    state
+}
+
+/// The cached state while it is younger than CACHE_TTL_SECS, otherwise a fresh query.
+fn current_state() -> RadioState {
+    let cached: Option<RadioState> = cache_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok());
+    match cached {
+        Some(state) if unix_now().saturating_sub(state.checked_at) < CACHE_TTL_SECS => {
+            log::debug!("Using cached state from {}s ago", unix_now().saturating_sub(state.checked_at));
+            state
+        }
+        Some(_) => {
+            log::debug!("Cached state is older than {}s, asking rfkill", CACHE_TTL_SECS);
+            refresh_state()
+        }
+        None => {
+            log::debug!("No cached state, asking rfkill");
+            refresh_state()
+        }
+    }
+}
+
 // --- Modes ---
 
+fn blocked_word(blocked: bool) -> &'static str {
+    if blocked { "blocked" } else { "unblocked" }
+}
+
 fn run_status() -> Result<()> {
-    let blocked = is_blocked().unwrap_or(false);
+    let state = current_state();
+    let (blocked, wifi, bluetooth) = (state.all, state.wifi, state.bluetooth);
     
     // Simple output. The Sidebar/Waybar handles the visuals via CSS classes (.on / .off)
     let class = if blocked { "on" } else { "off" };
     let text = if blocked { "✈" } else { "" };
-    let tooltip = if blocked { "Airplane Mode: Active" } else { "Airplane Mode: Inactive" };
+    let tooltip = format!(
+        "Airplane Mode: {}\nWi-Fi: {}\nBluetooth: {}",
+        if blocked { "Active" } else { "Inactive" },
+        blocked_word(wifi),
+        blocked_word(bluetooth)
+    );
 
     println!("{}", json!({
         "text": text,
         "class": class,
-        "tooltip": tooltip
+        "tooltip": tooltip,
+        "wifi": blocked_word(wifi),
+        "bluetooth": blocked_word(bluetooth)
     }));
     Ok(())
 }
 
-fn run_toggle() -> Result<()> {
-    let blocked = is_blocked().context("Failed to check state")?;
-    let (action, body) = if blocked {
-        ("unblock", "Airplane Mode: OFF")
-    } else {
-        ("block", "Airplane Mode: ON")
+/// `--status --wifi` / `--status --bluetooth`: one radio, classes `wifi-blocked`,
+/// `bluetooth-unblocked`, etc.
+fn run_radio_status(radio: Radio) -> Result<()> {
+    let blocked = current_state().get(radio);
+    let class = format!("{}-{}", radio.rfkill_type(), blocked_word(blocked));
+    let text = match (radio, blocked) {
+        (Radio::Wifi, true) => "󰖪",
+        (Radio::Wifi, false) => "󰖩",
+        (_, true) => "󰂲",
+        (_, false) => "󰂯",
+    };
+
+    println!("{}", json!({
+        "text": text,
+        "class": class,
+        "tooltip": format!("{}: {}", radio.label(), blocked_word(blocked))
+    }));
+    Ok(())
+}
+
+fn run_toggle(radio: Radio) -> Result<()> {
+    let blocked = is_blocked(radio).context("Failed to check state")?;
+    let action = if blocked { "unblock" } else { "block" };
+    // Airplane Mode reads inverted: blocking everything turns it ON.
+    let body = match radio {
+        Radio::All => format!("{}: {}", radio.label(), if blocked { "OFF" } else { "ON" }),
+        _ => format!("{}: {}", radio.label(), if blocked { "ON" } else { "OFF" }),
     };
 
     // 1. Execute
-    let status = Command::new("rfkill").arg(action).arg("all").status()?;
+    log::info!("rfkill {} {}", action, radio.rfkill_type());
+    let status = Command::new("rfkill").arg(action).arg(radio.rfkill_type()).status()?;
     if !status.success() {
         return Err(anyhow!("Failed to {}", action));
     }
+    // Blocking one radio can change the Airplane Mode reading too, so re-read them all
+    refresh_state();
 
     // 2. Notify
     let _ = Notification::new()
         .summary("Network Manager")
-        .body(body)
-        .icon(NOTIFICATION_ICON)
+        .body(&body)
+        .icon(radio.icon())
         .show();
 
     // 3. Signal Waybar (Harmless if Waybar isn't running)
@@ -91,19 +253,34 @@ fn run_toggle() -> Result<()> {
 // --- Main ---
 
 fn main() -> Result<()> {
-    let args: Vec<String> = env::args().collect();
-    match args.get(1).map(|s| s.as_str()) {
-        Some("--status") => run_status(),
-        Some("--toggle") | None => {
-            if let Err(e) = run_toggle() {
-                eprintln!("Error: {}", e);
-                let _ = Notification::new().summary("Error").body(&e.to_string()).show();
-            }
-            Ok(())
-        }
-        _ => {
-            println!("Usage: rfkill-manager [--status | --toggle]");
-            Ok(())
-        }
+    common::version::exit_if_requested(env!("CARGO_BIN_NAME"), env!("CARGO_PKG_VERSION"));
+    let args: Vec<String> = env::args().skip(1).collect();
+    let known = ["--status", "--toggle", "--wifi", "--bluetooth", "--verbose", "-v", "-vv", "-vvv"];
+    if args.iter().any(|a| !known.contains(&a.as_str())) {
+        println!("Usage: rfkill-manager [--status | --toggle] [--wifi | --bluetooth] [-v | -vv] [--version]");
+        return Ok(());
+    }
+    common::logging::init(module_path!(), common::logging::verbosity_from_args());
+
+    let has = |flag: &str| args.iter().any(|a| a == flag);
+    let radio = if has("--wifi") {
+        Radio::Wifi
+    } else if has("--bluetooth") {
+        Radio::Bluetooth
+    } else {
+        Radio::All
+    };
+
+    if has("--status") {
+        return match radio {
+            Radio::All => run_status(),
+            _ => run_radio_status(radio),
+        };
     }
+
+    if let Err(e) = run_toggle(radio) {
+        log::error!("{}", e);
+        let _ = Notification::new().summary("Error").body(&e.to_string()).show();
+    }
+    Ok(())
 }
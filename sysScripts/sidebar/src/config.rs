@@ -0,0 +1,82 @@
+//! Sidebar settings.
+//!
+//! Reads the optional `[sidebar]` section of `~/.config/rust-dotfiles/config.toml`.
+//! Every field has a default, so a missing section (or config file) keeps the stock layout.
+//! The notifications card also reads `history_file` from `[notification_center]`.
+
+use serde::Deserialize;
+use std::path::PathBuf;
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct SidebarConfig {
+    pub monitor: Option<String>, // Connector name, e.g. "DP-1"
+    pub show_media: bool,        // Now Playing card
+    pub show_notifications: bool, // swaync notifications card
+    pub show_sysinfo: bool,      // Host/Kernel/Uptime card
+    pub show_finance: bool,      // Market ticker (runs waybar-finance)
+    pub show_calendar: bool,     // Month/Day calendar zone
+    pub update_poll_secs: u64,   // How often the update badge runs update-check
+    pub slider_poll_secs: u32,   // How often sliders sync with external changes
+    #[serde(skip)]
+    pub notification_history: PathBuf, // From [notification_center], see load()
+}
+
+impl Default for SidebarConfig {
+    fn default() -> Self {
+        Self {
+            monitor: None,
+            show_media: true,
+            show_notifications: true,
+            show_sysinfo: true,
+            show_finance: true,
+            show_calendar: true,
+            update_poll_secs: 1800,
+            slider_poll_secs: 1,
+            notification_history: PathBuf::new(),
+        }
+    }
+}
+
+/// The part of notification-center's section the sidebar needs.
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct NotificationCenterConfig {
+    history_file: String,
+}
+
+impl Default for NotificationCenterConfig {
+    fn default() -> Self {
+        Self { history_file: "~/.local/share/notification-center/history.jsonl".to_string() }
+    }
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+struct GlobalConfig {
+    sidebar: SidebarConfig,
+    notification_center: NotificationCenterConfig,
+}
+
+/// Loads the sidebar section; a missing or unreadable config just means defaults.
+pub fn load() -> SidebarConfig {
+    let Some(home) = std::env::var_os("HOME").map(PathBuf::from) else {
+        return SidebarConfig::default();
+    };
+    let path = home.join(".config/rust-dotfiles/config.toml");
+    let config = match std::fs::read_to_string(&path) {
+        Ok(config_str) => toml::from_str::<GlobalConfig>(&config_str).unwrap_or_else(|e| {
+            eprintln!("Failed to parse {}: {}", path.display(), e);
+            GlobalConfig::default()
+        }),
+        Err(_) => GlobalConfig::default(),
+    };
+    let history_file = config.notification_center.history_file;
+    SidebarConfig {
+        notification_history: match history_file.strip_prefix("~/") {
+            Some(rest) => home.join(rest),
+            None => PathBuf::from(history_file),
+        },
+        ..config.sidebar
+    }
+}
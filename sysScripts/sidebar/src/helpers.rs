@@ -27,6 +27,19 @@ struct CalendarAppointment {
     rule: Option<Recurrence>,
     #[serde(default)]
     exceptions: Vec<DateTime<Utc>>,
+    // Set for entries from the exported calendar (no cal-tui id to select).
+    #[serde(skip)]
+    exported: bool,
+}
+
+/// An entry from an exported calendar (`~/.config/rust-dotfiles/appointments.json`):
+/// `[{"summary": "Dentist", "start": "2026-10-20T14:00:00+02:00", "duration_minutes": 30}]`
+#[derive(Debug, Deserialize, Clone)]
+struct ExportedAppointment {
+    summary: String,
+    start: DateTime<Utc>,
+    #[serde(default)]
+    duration_minutes: i64,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -41,7 +54,7 @@ enum Recurrence {
 }
 
 pub struct DayAppointment {
-    pub id: u32,
+    pub id: Option<u32>, // cal-tui id; None for exported entries
     pub summary: String,
     pub time: String,
     pub duration_minutes: i64,
@@ -52,7 +65,12 @@ fn calendar_data_path() -> Option<PathBuf> {
     Some(PathBuf::from(home).join(".local/share/cal-tui/calendar_data.json"))
 }
 
-fn load_calendar_data() -> Vec<CalendarAppointment> {
+fn exported_calendar_path() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME")?;
+    Some(PathBuf::from(home).join(".config/rust-dotfiles/appointments.json"))
+}
+
+fn load_cal_tui_data() -> Vec<CalendarAppointment> {
     let Some(path) = calendar_data_path() else {
         return Vec::new();
     };
@@ -68,6 +86,40 @@ fn load_calendar_data() -> Vec<CalendarAppointment> {
     storage.appointments.into_values().collect()
 }
 
+fn load_exported_data() -> Vec<CalendarAppointment> {
+    let Some(path) = exported_calendar_path() else {
+        return Vec::new();
+    };
+
+    let Ok(raw) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    let Ok(entries) = serde_json::from_str::<Vec<ExportedAppointment>>(&raw) else {
+        return Vec::new();
+    };
+
+    entries
+        .into_iter()
+        .map(|e| CalendarAppointment {
+            id: 0,
+            summary: e.summary,
+            start: e.start,
+            duration: Duration::minutes(e.duration_minutes),
+            rule: None,
+            exceptions: Vec::new(),
+            exported: true,
+        })
+        .collect()
+}
+
+/// All appointments: cal-tui's own store plus the optional exported calendar.
+fn load_calendar_data() -> Vec<CalendarAppointment> {
+    let mut all = load_cal_tui_data();
+    all.extend(load_exported_data());
+    all
+}
+
 fn occurs_on(appointment: &CalendarAppointment, target_date: NaiveDate) -> bool {
     let start_date = appointment.start.date_naive();
     if start_date > target_date {
@@ -110,7 +162,7 @@ pub fn get_day_appointments(date: NaiveDate) -> Vec<DayAppointment> {
     matches
         .into_iter()
         .map(|appt| DayAppointment {
-            id: appt.id,
+            id: (!appt.exported).then_some(appt.id),
             summary: appt.summary,
             time: appt.start.format("%H:%M").to_string(),
             duration_minutes: appt.duration.num_minutes(),
@@ -329,7 +381,8 @@ pub fn build_calendar_grid(year: i32, month: u32) -> gtk4::Grid {
 
 /// Creates a standardized Slider Row (Icon + Scale).
 /// Returns (Container Box, The Scale Widget).
-/// Note: The caller must attach the `value_changed` signal to the returned Scale.
+/// Note: The caller must attach the `value_changed` signal to the returned Scale
+/// (see `ui::connect_slider_command`).
 pub fn make_slider_row(icon_name: &str) -> (gtk4::Box, gtk4::Scale) {
     let box_row = gtk4::Box::new(gtk4::Orientation::Horizontal, 10);
 
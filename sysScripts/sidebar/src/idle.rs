@@ -0,0 +1,154 @@
+//! Timed idle inhibit.
+//!
+//! Right-click (or long-press) the idle button to pause hypridle/swayidle for a fixed time.
+//! The sidebar closes on focus loss, so the expiry can't live in a glib timer alone: a
+//! detached `sleep` job sends CONT when time is up, and `/tmp/sidebar_idle.lock` records
+//! "<expiry unix secs> <job pid>" so a reopened sidebar can show the time left or cancel it.
+
+use gtk4::prelude::*;
+use gtk4::{Align, Button, Orientation, Popover};
+use crate::helpers;
+use std::fs;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const IDLE_LOCK: &str = "/tmp/sidebar_idle.lock";
+const DURATIONS: &[(&str, u64)] = &[
+    ("30 minutes", 30 * 60),
+    ("1 hour", 60 * 60),
+    ("2 hours", 2 * 60 * 60),
+];
+const IDLE_TOOLTIP: &str = "Idle Inhibit (right-click for a timer)";
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// (expiry, pid) of the running timer, if any.
+fn read_lock() -> Option<(u64, u32)> {
+    let content = fs::read_to_string(IDLE_LOCK).ok()?;
+    let mut parts = content.split_whitespace();
+    let expiry = parts.next()?.parse().ok()?;
+    let pid = parts.next()?.parse().ok()?;
+    Some((expiry, pid))
+}
+
+/// Seconds left on the timer, or None if there is no (unexpired) timer.
+fn remaining_secs() -> Option<u64> {
+    let (expiry, _) = read_lock()?;
+    expiry.checked_sub(unix_now()).filter(|left| *left > 0)
+}
+
+/// Stops a pending expiry job (e.g. when the user toggles inhibit manually).
+pub fn cancel_timer() {
+    if let Some((_, pid)) = read_lock() {
+        helpers::run_command("kill", &[pid.to_string().as_str()]);
+    }
+    let _ = fs::remove_file(IDLE_LOCK);
+}
+
+/// Pauses the idle daemons now and schedules the CONT `secs` from now.
+fn start_timer(secs: u64) {
+    cancel_timer();
+    helpers::run_command("pkill", &["-STOP", "hypridle"]);
+    helpers::run_command("pkill", &["-STOP", "swayidle"]);
+    let script = format!(
+        "sleep {}; pkill -CONT hypridle; pkill -CONT swayidle; rm -f {}",
+        secs, IDLE_LOCK
+    );
+    match Command::new("sh").arg("-c").arg(script).spawn().map(|child| child.id()) {
+        Ok(pid) => {
+            let _ = fs::write(IDLE_LOCK, format!("{} {}", unix_now() + secs, pid));
+        }
+        Err(e) => eprintln!("Failed to start idle timer: {}", e),
+    }
+}
+
+fn format_remaining(secs: u64) -> String {
+    let minutes = secs.div_ceil(60);
+    if minutes >= 60 {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// Keeps the button's tooltip counting down while the sidebar is open, and clears the
+/// `.active` class once the background job has resumed the daemons.
+fn watch(btn: &Button) {
+    let Some((expiry, _)) = read_lock() else { return };
+    let tooltip = |left: u64| format!("Idle inhibited ({} left)", format_remaining(left));
+    btn.set_tooltip_text(Some(&tooltip(expiry.saturating_sub(unix_now()))));
+
+    let btn = btn.clone();
+    glib::timeout_add_seconds_local(1, move || {
+        let now = unix_now();
+        match read_lock() {
+            Some((current, _)) if current == expiry && now < expiry => {
+                btn.set_tooltip_text(Some(&tooltip(expiry - now)));
+                glib::ControlFlow::Continue
+            }
+            // A newer timer replaced this one; its own watcher takes over
+            Some((current, _)) if current != expiry => glib::ControlFlow::Break,
+            _ => {
+                // Time is up. A lock removed early means a manual toggle cancelled
+                // the timer, and that handler already set the class.
+                if now >= expiry {
+                    btn.remove_css_class("active");
+                }
+                btn.set_tooltip_text(Some(IDLE_TOOLTIP));
+                glib::ControlFlow::Break
+            }
+        }
+    });
+}
+
+/// Adds the duration menu to the idle button and resumes the countdown of a running timer.
+pub fn attach_timer_menu(btn: &Button) {
+    btn.set_tooltip_text(Some(IDLE_TOOLTIP));
+
+    let menu = gtk4::Box::new(Orientation::Vertical, 2);
+    let popover = Popover::builder().child(&menu).build();
+    popover.set_parent(btn);
+
+    for (label, secs) in DURATIONS {
+        let item = Button::builder()
+            .label(format!("Inhibit for {}", label))
+            .css_classes(vec!["flat".to_string()])
+            .halign(Align::Fill)
+            .build();
+        let btn_target = btn.clone();
+        let popover_close = popover.clone();
+        let secs = *secs;
+        item.connect_clicked(move |_| {
+            start_timer(secs);
+            btn_target.add_css_class("active");
+            popover_close.popdown();
+            watch(&btn_target);
+        });
+        menu.append(&item);
+    }
+
+    let right_click = gtk4::GestureClick::new();
+    right_click.set_button(3);
+    let popover_right = popover.clone();
+    right_click.connect_pressed(move |_, _, _, _| popover_right.popup());
+    btn.add_controller(right_click);
+
+    let long_press = gtk4::GestureLongPress::new();
+    let popover_long = popover.clone();
+    long_press.connect_pressed(move |_, _, _| popover_long.popup());
+    btn.add_controller(long_press);
+
+    // Reopened while a timer is running: show the time left (the daemons are still STOPped,
+    // so the startup `ps` check has already marked the button active).
+    // A lock past its expiry is stale (job killed externally), so drop it.
+    if remaining_secs().is_some() {
+        watch(btn);
+    } else {
+        let _ = fs::remove_file(IDLE_LOCK);
+    }
+}
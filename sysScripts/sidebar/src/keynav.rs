@@ -0,0 +1,89 @@
+//! Keyboard navigation for the quick-toggle zone.
+//!
+//! GTK's default focus chain follows widget allocation order and wanders into the
+//! calendar and media card. We treat the top zone as a grid instead: Up/Down move
+//! between rows (keeping the relative column), Left/Right move within a row,
+//! Enter/Space activate the focused control, and Escape closes the panel.
+
+use std::rc::Rc;
+use gtk4::prelude::*;
+use gtk4::gdk::Key;
+use gtk4::{ApplicationWindow, Widget};
+
+/// Finds the (row, column) of the control that owns the current keyboard focus.
+/// Focus can sit on an inner child (e.g. a Scale's slider), so we match ancestors too.
+fn locate(rows: &[Vec<Widget>], focused: &Widget) -> Option<(usize, usize)> {
+    rows.iter().enumerate().find_map(|(r, row)| {
+        row.iter()
+            .position(|w| focused == w || focused.is_ancestor(w))
+            .map(|c| (r, c))
+    })
+}
+
+/// Moves focus to `rows[row]`, mapping the old column proportionally so that moving
+/// from the 6-button session row to the 7-button toggle row lands on the nearest button.
+fn focus_row(rows: &[Vec<Widget>], row: usize, from: (usize, usize)) {
+    let (old_row, old_col) = from;
+    let Some(target) = rows.get(row) else { return };
+    if target.is_empty() {
+        return;
+    }
+    let old_len = rows[old_row].len().max(1);
+    let col = (old_col * target.len() / old_len).min(target.len() - 1);
+    target[col].grab_focus();
+}
+
+/// Installs the grid navigation on `window`. `rows` is ordered top to bottom.
+pub fn install(window: &ApplicationWindow, rows: Vec<Vec<Widget>>) {
+    let rows = Rc::new(rows);
+
+    // Capture phase so we see arrows before the focused button/scale does.
+    let controller = gtk4::EventControllerKey::new();
+    controller.set_propagation_phase(gtk4::PropagationPhase::Capture);
+
+    let win = window.clone();
+    let rows_nav = rows.clone();
+    controller.connect_key_pressed(move |_, key, _, _| {
+        if key == Key::Escape {
+            win.close();
+            return glib::Propagation::Stop;
+        }
+
+        let focused = GtkWindowExt::focus(&win);
+        let position = focused.as_ref().and_then(|f| locate(&rows_nav, f));
+
+        // Nothing of ours is focused yet: the first arrow press lands on the first control.
+        let Some((row, col)) = position else {
+            if matches!(key, Key::Up | Key::Down | Key::Left | Key::Right)
+                && let Some(first) = rows_nav.first().and_then(|r| r.first())
+            {
+                first.grab_focus();
+                return glib::Propagation::Stop;
+            }
+            return glib::Propagation::Proceed;
+        };
+
+        let current = &rows_nav[row][col];
+        match key {
+            Key::Up if row > 0 => focus_row(&rows_nav, row - 1, (row, col)),
+            Key::Down if row + 1 < rows_nav.len() => focus_row(&rows_nav, row + 1, (row, col)),
+            // Sliders keep Left/Right for adjusting their value.
+            Key::Left | Key::Right if current.is::<gtk4::Scale>() => return glib::Propagation::Proceed,
+            Key::Left if col > 0 => { rows_nav[row][col - 1].grab_focus(); }
+            Key::Right if col + 1 < rows_nav[row].len() => { rows_nav[row][col + 1].grab_focus(); }
+            Key::Return | Key::KP_Enter | Key::space if current.is::<gtk4::Button>() => {
+                current.activate();
+            }
+            // Edges and unhandled keys fall through to GTK (Tab still works as usual).
+            _ => return glib::Propagation::Proceed,
+        }
+        glib::Propagation::Stop
+    });
+    window.add_controller(controller);
+
+    // Start on the first control with a visible focus ring so keyboard users know where they are.
+    if let Some(first) = rows.first().and_then(|r| r.first()) {
+        window.set_focus_visible(true);
+        first.grab_focus();
+    }
+}
@@ -4,21 +4,41 @@
 //!
 //! Architecture:
 //! - **main.rs**: Entry point, environment setup, and module registration.
+//! - **config.rs**: Optional `[sidebar]` settings from the central config.toml.
 //! - **ui.rs**: Main widget layout, window creation, and event wiring.
 //! - **style.rs**: CSS styling and theming (Catppuccin/Glassmorphism).
+//! - **idle.rs**: Timed idle inhibit (auto-expiring pause of the idle daemons).
 //! - **helpers.rs**: Shared utilities (command execution, button factories).
+//! - **keynav.rs**: Arrow-key navigation across the quick toggles and sliders.
 //! - **media.rs**: Dynamic "Now Playing" widget (Playerctl integration).
+//! - **notifications.rs**: Open notifications card (swaync-client integration).
+//! - **screenshot.rs**: Region/full-screen capture button (grim + slurp).
 //! - **sysinfo.rs**: System status widget (Static snapshot).
+//! - **wifi.rs**: Wi-Fi network picker popover (nmcli integration).
 
 use gtk4::Application;
 use gtk4::prelude::*;
 
 // --- Module Registration ---
+mod config; // [sidebar] settings
 mod helpers; // Utility functions
+mod idle; // Timed idle inhibit
+mod keynav; // Keyboard navigation
 mod media; // Media player logic
+mod notifications; // swaync notifications card
+mod screenshot; // grim + slurp capture button
 mod style; // CSS provider
 mod sysinfo;
 mod ui; // The layout builder // System fetch widget
+mod wifi; // Wi-Fi network picker
+
+/// Splits our own `--monitor <name>` flag out of argv.
+/// The rest is handed to GTK, which rejects options it doesn't know.
+fn take_monitor_arg(args: &mut Vec<String>) -> Option<String> {
+    let idx = args.iter().position(|a| a == "--monitor")?;
+    args.remove(idx);
+    (idx < args.len()).then(|| args.remove(idx))
+}
 
 fn main() {
     // 1. Environment Configuration
@@ -45,16 +65,20 @@ fn main() {
     // DBus uniqueness checks, allowing multiple instances if needed (though rare).
     let app = Application::builder().build();
 
+    // Output selection: CLI flag wins over config (resolved in build_ui).
+    let mut args: Vec<String> = std::env::args().collect();
+    let monitor = take_monitor_arg(&mut args);
+
     // 3. Connect the UI
     // When the app starts ('activate'), run the build_ui function from ui.rs
-    app.connect_activate(|app| {
+    app.connect_activate(move |app| {
         if let Some(settings) = gtk4::Settings::default() {
             settings.set_gtk_icon_theme_name(Some("Adwaita"));
         }
-        ui::build_ui(app);
+        ui::build_ui(app, monitor.as_deref());
     });
 
     // 4. Run Event Loop
     // This blocks the main thread until the window is closed.
-    app.run();
+    app.run_with_args(&args);
 }
@@ -0,0 +1,183 @@
+//! Notifications widget backed by swaync.
+//!
+//! swaync-client can report how many notifications are open but not list them, so the cards
+//! come from the `notification-center` history (same Notify calls, newest last): the newest
+//! `swaync-client -c` entries are the ones still in swaync. swaync-client can only close the
+//! newest notification, so that card gets the dismiss button; "Clear" closes them all.
+//! The card stays hidden while there is nothing to show and refreshes every 2 seconds.
+
+use gtk4::prelude::*;
+use gtk4::{Align, Box, Button, Label, Orientation, ScrolledWindow};
+use crate::helpers;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+
+const POLL_SECS: u32 = 2;
+const MAX_CARDS: usize = 20; // Enough for the list to scroll without rebuilding a huge one
+
+/// One line of notification-center's history.jsonl (only the fields shown here).
+#[derive(Deserialize, Clone, PartialEq)]
+struct Notification {
+    timestamp: i64,
+    app: String,
+    summary: String,
+}
+
+/// Newest-first notifications still open in swaync. None = swaync-client isn't available.
+fn fetch(history: &Path) -> Option<Vec<Notification>> {
+    let out = helpers::get_output("swaync-client", &["-c", "-sw"])?;
+    let count: usize = String::from_utf8_lossy(&out).trim().parse().ok()?;
+    let content = std::fs::read_to_string(history).unwrap_or_default();
+    Some(
+        content
+            .lines()
+            .rev()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .take(count.min(MAX_CARDS))
+            .collect(),
+    )
+}
+
+fn build_card(notification: &Notification, newest: bool) -> Box {
+    let card = Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .css_classes(vec!["notif-item"])
+        .build();
+
+    let text = Box::builder()
+        .orientation(Orientation::Vertical)
+        .hexpand(true)
+        .build();
+    let when = chrono::DateTime::from_timestamp(notification.timestamp, 0)
+        .map(|t| t.with_timezone(&chrono::Local).format("%H:%M").to_string())
+        .unwrap_or_default();
+    let app = Label::builder()
+        .label(format!("{}  {}", notification.app, when))
+        .css_classes(vec!["notif-app"])
+        .ellipsize(gtk4::pango::EllipsizeMode::End)
+        .halign(Align::Start)
+        .build();
+    let summary = Label::builder()
+        .label(&notification.summary)
+        .css_classes(vec!["notif-summary"])
+        .wrap(true)
+        .max_width_chars(30)
+        .ellipsize(gtk4::pango::EllipsizeMode::End)
+        .lines(2)
+        .halign(Align::Start)
+        .xalign(0.0)
+        .build();
+    text.append(&app);
+    text.append(&summary);
+    card.append(&text);
+
+    if newest {
+        let dismiss = Button::builder()
+            .label("✕")
+            .tooltip_text("Dismiss")
+            .css_classes(vec!["notif-dismiss"])
+            .valign(Align::Start)
+            .build();
+        let card_dismiss = card.clone();
+        dismiss.connect_clicked(move |_| {
+            helpers::run_command("swaync-client", &["--close-latest", "-sw"]);
+            // Hide right away; the next poll rebuilds the list from swaync's count
+            card_dismiss.set_visible(false);
+        });
+        card.append(&dismiss);
+    }
+    card
+}
+
+/// Builds the Notifications card. `history` is notification-center's history file.
+pub fn build(history: PathBuf) -> Box {
+    // Hidden until the first poll finds open notifications
+    let container = Box::builder()
+        .orientation(Orientation::Vertical)
+        .css_classes(vec!["notif-card"])
+        .visible(false)
+        .halign(Align::Fill)
+        .build();
+
+    let header = Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(10)
+        .build();
+    let title = Label::builder()
+        .label("🔔 Notifications")
+        .css_classes(vec!["notif-title"])
+        .halign(Align::Start)
+        .hexpand(true)
+        .build();
+    let btn_clear = Button::builder()
+        .label("Clear")
+        .css_classes(vec!["notif-clear"])
+        .build();
+    header.append(&title);
+    header.append(&btn_clear);
+
+    // Scrolls inside the card so a burst of notifications doesn't push the other zones away
+    let list = Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(6)
+        .build();
+    let scroller = ScrolledWindow::builder()
+        .child(&list)
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .vscrollbar_policy(gtk4::PolicyType::Automatic)
+        .propagate_natural_height(true)
+        .max_content_height(260)
+        .build();
+
+    container.append(&header);
+    container.append(&scroller);
+
+    let container_clear = container.clone();
+    btn_clear.connect_clicked(move |_| {
+        helpers::run_command("swaync-client", &["-C", "-sw"]);
+        container_clear.set_visible(false);
+    });
+
+    // Same pattern as media.rs: poll from the GTK loop, run swaync-client on a worker thread.
+    let container_poll = container.clone();
+    let (tx, rx) = mpsc::channel::<Option<Vec<Notification>>>();
+    let in_flight = Arc::new(AtomicBool::new(false));
+    let mut shown: Option<Vec<Notification>> = None;
+
+    let mut poll = move || {
+        if let Ok(snapshot) = rx.try_recv() {
+            let notifications = snapshot.unwrap_or_default();
+            // Only rebuild on change so the scroll position survives quiet polls
+            if shown.as_ref() != Some(&notifications) {
+                while let Some(child) = list.first_child() {
+                    list.remove(&child);
+                }
+                for (i, notification) in notifications.iter().enumerate() {
+                    list.append(&build_card(notification, i == 0));
+                }
+            }
+            container_poll.set_visible(!notifications.is_empty());
+            shown = Some(notifications);
+        }
+
+        // Keep at most one fetch in flight, like the media poll.
+        if !in_flight.swap(true, Ordering::AcqRel) {
+            let tx_bg = tx.clone();
+            let in_flight_bg = Arc::clone(&in_flight);
+            let history_bg = history.clone();
+            std::thread::spawn(move || {
+                let _ = tx_bg.send(fetch(&history_bg));
+                in_flight_bg.store(false, Ordering::Release);
+            });
+        }
+        glib::ControlFlow::Continue
+    };
+    // Fetch immediately instead of waiting a full interval for the first cards
+    poll();
+    glib::timeout_add_seconds_local(POLL_SECS, poll);
+
+    container
+}
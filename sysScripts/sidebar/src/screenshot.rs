@@ -0,0 +1,75 @@
+//! Screenshot button (grim + slurp).
+//!
+//! A quick-toggle whose popover offers a region or full-screen capture. The shot is saved
+//! to `~/Pictures/Screenshots/` and copied to the clipboard with wl-copy. The sidebar is
+//! closed first so neither slurp's overlay nor grim sees it; the capture runs in a detached
+//! shell because closing the window also ends this process.
+
+use gtk4::prelude::*;
+use gtk4::{Align, ApplicationWindow, Button, Orientation, Popover};
+use crate::helpers;
+use std::path::PathBuf;
+
+// Lets the compositor unmap the sidebar before the capture starts
+const UNMAP_DELAY: &str = "0.3";
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Region,
+    FullScreen,
+}
+
+fn screenshot_dir() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME")?;
+    Some(PathBuf::from(home).join("Pictures/Screenshots"))
+}
+
+/// Closes the sidebar and starts the capture. A cancelled slurp (Escape) saves nothing.
+fn capture(window: &ApplicationWindow, mode: Mode) {
+    let Some(dir) = screenshot_dir() else { return };
+    let _ = std::fs::create_dir_all(&dir);
+    let file = dir.join(format!(
+        "Screenshot_{}.png",
+        chrono::Local::now().format("%Y-%m-%d_%H-%M-%S")
+    ));
+
+    let grim = match mode {
+        Mode::Region => r#"geometry=$(slurp) || exit 0; grim -g "$geometry" "$1""#,
+        Mode::FullScreen => r#"grim "$1""#,
+    };
+    // The file path is passed as $1 so it never needs shell quoting
+    let script = format!(
+        r#"sleep {}; {} && wl-copy --type image/png < "$1""#,
+        UNMAP_DELAY, grim
+    );
+
+    window.close();
+    helpers::run_command("sh", &["-c", &script, "sh", &file.to_string_lossy()]);
+}
+
+/// Builds the quick-toggle button. Clicking it offers region or full-screen capture.
+pub fn build_button(window: &ApplicationWindow) -> Button {
+    let button = helpers::make_icon_button("camera-photo-symbolic", "Screenshot");
+
+    let menu = gtk4::Box::new(Orientation::Vertical, 2);
+    let popover = Popover::builder().child(&menu).build();
+    popover.set_parent(&button);
+
+    for (label, mode) in [("Region", Mode::Region), ("Full screen", Mode::FullScreen)] {
+        let item = Button::builder()
+            .label(label)
+            .css_classes(vec!["flat".to_string()])
+            .halign(Align::Fill)
+            .build();
+        let window = window.clone();
+        let popover_close = popover.clone();
+        item.connect_clicked(move |_| {
+            popover_close.popdown();
+            capture(&window, mode);
+        });
+        menu.append(&item);
+    }
+
+    button.connect_clicked(move |_| popover.popup());
+    button
+}
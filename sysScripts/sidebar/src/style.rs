@@ -57,6 +57,12 @@ pub fn load_css() {
             background-color: rgba(255, 255, 255, 0.2); /* Lighten on hover */
         }
 
+        /* Keyboard Focus (arrow-key navigation) */
+        .circular-btn:focus-visible, .squared-btn:focus-visible, scale:focus-visible {
+            outline: 2px solid #89b4fa;
+            outline-offset: 1px;
+        }
+
         /* Active/Toggled States (e.g., Airplane Mode ON) */
         .circular-btn.active, .squared-btn.active {
             background-color: #3584e4; /* Gnome Blue */
@@ -166,6 +172,50 @@ pub fn load_css() {
             box-shadow: none;
         }
 
+        /* --- NOTIFICATIONS CARD --- */
+        .notif-card {
+            background-color: rgba(255, 255, 255, 0.08);
+            border-radius: 16px;
+            padding: 12px 16px;
+            margin: 10px 20px;
+            border: 1px solid rgba(255, 255, 255, 0.1);
+        }
+
+        .notif-title {
+            font-size: 14px;
+            font-weight: bold;
+            color: #89b4fa;
+            margin-bottom: 6px;
+        }
+
+        .notif-clear, .notif-dismiss {
+            background: transparent;
+            color: #cccccc;
+            border: none;
+            box-shadow: none;
+            padding: 2px 8px;
+            border-radius: 8px;
+        }
+
+        .notif-clear:hover, .notif-dismiss:hover {
+            background-color: rgba(255, 255, 255, 0.2);
+        }
+
+        .notif-item {
+            padding: 6px 0;
+            border-top: 1px solid rgba(255, 255, 255, 0.08);
+        }
+
+        .notif-app {
+            font-size: 12px;
+            color: #a6adc8;
+        }
+
+        .notif-summary {
+            font-size: 14px;
+            color: white;
+        }
+
         /* --- MEDIA PLAYER CARD --- */
         .media-card {
             background-color: rgba(255, 255, 255, 0.08); /* Subtle glass effect */
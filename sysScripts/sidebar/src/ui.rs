@@ -4,7 +4,8 @@
 //! polling so command I/O does not block the main loop.
 
 use std::rc::Rc;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc, Arc};
 use gtk4::prelude::*;
@@ -13,10 +14,16 @@ use gtk4_layer_shell::{Edge, Layer, LayerShell};
 use serde_json::Value;
 use chrono::{Datelike, Local};
 
+use crate::config;
 use crate::style;
 use crate::helpers;
+use crate::keynav;
 use crate::media;
+use crate::notifications;
 use crate::sysinfo;
+use crate::screenshot;
+use crate::wifi;
+use crate::idle;
 
 struct SliderSnapshot {
     brightness: Option<f64>,
@@ -40,12 +47,81 @@ fn parse_volume_pct(out: &[u8]) -> Option<f64> {
         .map(|v| v * 100.0)
 }
 
-pub fn build_ui(app: &Application) {
+// Parse `bluetoothctl show` output: true if the default controller is powered.
+fn parse_bt_powered(out: &[u8]) -> bool {
+    String::from_utf8_lossy(out)
+        .lines()
+        .any(|l| l.trim() == "Powered: yes")
+}
+
+// Slider commands are coalesced: a drag spawns at most one process per window.
+const SLIDER_DEBOUNCE_MS: u64 = 80;
+// After a user-initiated change, external sync leaves the sliders alone this long.
+const SLIDER_GUARD_MS: u128 = 500;
+
+/// Attaches a debounced `value_changed` handler that pushes the slider value to the system.
+/// Programmatic updates (made while `syncing` is set) are ignored so sync can't echo back.
+fn connect_slider_command(
+    scale: &gtk4::Scale,
+    last_interaction: Rc<RefCell<Instant>>,
+    syncing: Rc<Cell<bool>>,
+    apply: impl Fn(f64) + 'static,
+) {
+    let pending: Rc<Cell<Option<f64>>> = Rc::new(Cell::new(None));
+    let apply = Rc::new(apply);
+    scale.connect_value_changed(move |s| {
+        if syncing.get() {
+            return;
+        }
+        *last_interaction.borrow_mut() = Instant::now();
+        // Queue one flush per window; it sends whatever the latest value is by then.
+        if pending.replace(Some(s.value())).is_none() {
+            let pending = pending.clone();
+            let apply = apply.clone();
+            glib::timeout_add_local_once(Duration::from_millis(SLIDER_DEBOUNCE_MS), move || {
+                if let Some(v) = pending.take() {
+                    apply(v);
+                }
+            });
+        }
+    });
+}
+
+/// Moves a slider to reflect system state without triggering its command handler.
+fn sync_slider(scale: &gtk4::Scale, syncing: &Cell<bool>, value: f64) {
+    syncing.set(true);
+    scale.set_value(value);
+    syncing.set(false);
+}
+
+/// Looks up a monitor by its connector name (e.g. "DP-1").
+fn find_monitor(name: &str) -> Option<gtk4::gdk::Monitor> {
+    let monitors = gtk4::gdk::Display::default()?.monitors();
+    (0..monitors.n_items())
+        .filter_map(|i| monitors.item(i).and_downcast::<gtk4::gdk::Monitor>())
+        .find(|m| m.connector().is_some_and(|c| c == name))
+}
+
+pub fn build_ui(app: &Application, monitor_arg: Option<&str>) {
+    // 0. Settings ([sidebar] in config.toml). `--monitor` overrides the configured output.
+    let config = config::load();
+    let monitor_name = monitor_arg.or(config.monitor.as_deref());
+
     // 1. Setup Window (Fixed Width)
-    // Since we rely on the compositor (Sway/Niri) to place the window on the active monitor,
-    // we cannot easily query the screen dimensions beforehand.
-    // 400px is a safe, usable default for a sidebar.
-    let final_width = 400; 
+    // Unless an output was requested, we rely on the compositor (Sway/Niri) to place the
+    // window on the active monitor, so we cannot query the screen dimensions beforehand.
+    // 400px is a safe, usable default for a sidebar (clamped to the chosen monitor).
+    let monitor = monitor_name.and_then(|name| {
+        let found = find_monitor(name);
+        if found.is_none() {
+            eprintln!("Monitor '{}' not found, using the active one.", name);
+        }
+        found
+    });
+    let final_width = monitor
+        .as_ref()
+        .map(|m| m.geometry().width().min(400))
+        .unwrap_or(400);
 
     let window = ApplicationWindow::builder()
         .application(app)
@@ -62,10 +138,11 @@ pub fn build_ui(app: &Application) {
     window.set_keyboard_mode(gtk4_layer_shell::KeyboardMode::OnDemand);
     window.set_layer(Layer::Overlay);
     
-    // Monitor Auto-Detection
+    // Monitor Selection
     // Passing `None` tells the protocol to assign this window to the monitor 
     // containing the active mouse pointer. This solves multi-monitor focus issues natively.
-    window.set_monitor(None);
+    // `--monitor <name>` / `[sidebar] monitor` pin it to a specific output instead.
+    window.set_monitor(monitor.as_ref());
 
     // --- HOVER GUARD (Sway Focus Fix) ---
     // In tiling WMs like Sway, clicking a button inside this window might momentarily
@@ -160,25 +237,31 @@ pub fn build_ui(app: &Application) {
     row_session.append(&btn_restart);
     row_session.append(&btn_power);
 
-    // Row 2: Feature Toggles
-    let row_toggles = gtk4::Box::new(gtk4::Orientation::Horizontal, 15);
+    // Row 2: Feature Toggles (tighter spacing so ten buttons fit the 400px width)
+    let row_toggles = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
     row_toggles.set_homogeneous(true);
 
     let btn_radio = helpers::make_icon_button("multimedia-player-symbolic", "Internet Radio");
     // Returns a button AND its badge label so we can update the number later
     let (btn_update, lbl_update_badge) = helpers::make_badged_button("software-update-available-symbolic", "0", "Update System");
+    let btn_wifi = wifi::build_button();
     let btn_air = helpers::make_icon_button("airplane-mode-symbolic", "Airplane Mode");
+    let btn_bt = helpers::make_icon_button("bluetooth-active-symbolic", "Bluetooth");
     let btn_dns = helpers::make_icon_button("weather-overcast-symbolic", "Cloudflare DNS");
     let btn_mute = helpers::make_icon_button("audio-volume-muted-symbolic", "Mute Audio");
     let btn_wall = helpers::make_icon_button("image-x-generic-symbolic", "Change Wallpaper");
     let btn_hint = helpers::make_icon_button("emoji-objects-symbolic", "Show Keyhints");
+    let btn_shot = screenshot::build_button(&window);
     
     row_toggles.append(&btn_radio);
     row_toggles.append(&btn_wall);
     row_toggles.append(&btn_dns);
     row_toggles.append(&btn_update);
+    row_toggles.append(&btn_wifi);
     row_toggles.append(&btn_air);
+    row_toggles.append(&btn_bt);
     row_toggles.append(&btn_mute);
+    row_toggles.append(&btn_shot);
     row_toggles.append(&btn_hint);
 
     // Sliders (Brightness & Volume)
@@ -189,25 +272,21 @@ pub fn build_ui(app: &Application) {
 
     // --- INTERACTION GUARD ---
     // Tracks the last time the user manually moved a slider.
-    // The background poller checks this timestamp; if it's recent (< 3s),
+    // The background poller checks this timestamp; if it's recent (< 500ms),
     // it skips updating the slider to prevent visual "fighting" or bouncing.
-    let last_interaction = Rc::new(RefCell::new(std::time::Instant::now()));
+    // `slider_syncing` marks programmatic updates so they don't re-run the commands.
+    let last_interaction = Rc::new(RefCell::new(Instant::now()));
+    let slider_syncing = Rc::new(Cell::new(false));
 
     // BRIGHTNESS HANDLER
-    let last_interaction_b = last_interaction.clone();
-    scale_brightness.connect_value_changed(move |s| {
-        let val = s.value() as i32;
-        *last_interaction_b.borrow_mut() = std::time::Instant::now();
-        let level = format!("{}%", val);
-        helpers::run_command("brightnessctl", &["s", level.as_str()]);
+    connect_slider_command(&scale_brightness, last_interaction.clone(), slider_syncing.clone(), |v| {
+        let level = format!("{}%", v.round() as i32);
+        helpers::run_command("brightnessctl", &["set", level.as_str()]);
     });
 
     // VOLUME HANDLER
-    let last_interaction_v = last_interaction.clone();
-    scale_volume.connect_value_changed(move |s| {
-        let val = s.value() / 100.0; 
-        *last_interaction_v.borrow_mut() = std::time::Instant::now();
-        let level = val.to_string();
+    connect_slider_command(&scale_volume, last_interaction.clone(), slider_syncing.clone(), |v| {
+        let level = format!("{:.2}", v / 100.0);
         helpers::run_command("wpctl", &["set-volume", "@DEFAULT_AUDIO_SINK@", level.as_str()]);
     });
 
@@ -216,7 +295,7 @@ pub fn build_ui(app: &Application) {
     top_box.append(&box_brightness);
     top_box.append(&box_volume);
 
-    // --- ZONE 2: MIDDLE (Media & SysInfo) ---
+    // --- ZONE 2: MIDDLE (Media, Notifications & SysInfo) ---
     // This box expands to fill all available vertical space, pushing Top and Bottom zones apart.
     let middle_box = Box::builder()
         .orientation(Orientation::Vertical)
@@ -226,12 +305,22 @@ pub fn build_ui(app: &Application) {
         .build();
 
     // Dynamic Media Player (Slides in via `media.rs` logic only when playing)
-    let media_widget = media::build();
-    middle_box.append(&media_widget);
+    if config.show_media {
+        let media_widget = media::build();
+        middle_box.append(&media_widget);
+    }
+
+    // Open swaync notifications (hidden while there are none)
+    if config.show_notifications {
+        let notif_widget = notifications::build(config.notification_history.clone());
+        middle_box.append(&notif_widget);
+    }
 
     // Static System Information (Host, Kernel, Uptime)
-    let sys_widget = sysinfo::build();
-    middle_box.append(&sys_widget);
+    if config.show_sysinfo {
+        let sys_widget = sysinfo::build();
+        middle_box.append(&sys_widget);
+    }
     
     // --- ZONE 3: FINANCE TICKER ---
     let finance_box = gtk4::Box::new(gtk4::Orientation::Vertical, 0);
@@ -435,12 +524,18 @@ pub fn build_ui(app: &Application) {
             let date_copy = date;
             button.connect_clicked(move |_| {
                 let date_arg = format!("{}-{}-{}", date_copy.year(), date_copy.month(), date_copy.day());
-                let id_arg = appt.id.to_string();
-                helpers::run_in_ghostty(
-                    "calendar-tui",
-                    "cal-tui",
-                    &["--date", date_arg.as_str(), "--select-id", id_arg.as_str()],
-                );
+                // Exported entries have no cal-tui id; just open that day.
+                match appt.id {
+                    Some(id) => {
+                        let id_arg = id.to_string();
+                        helpers::run_in_ghostty(
+                            "calendar-tui",
+                            "cal-tui",
+                            &["--date", date_arg.as_str(), "--select-id", id_arg.as_str()],
+                        );
+                    }
+                    None => helpers::run_in_ghostty("calendar-tui", "cal-tui", &["--date", date_arg.as_str()]),
+                }
             });
             agenda_list_clone.append(&button);
         }
@@ -480,8 +575,8 @@ pub fn build_ui(app: &Application) {
     // Assemble Main Window
     main_box.append(&top_box);
     main_box.append(&middle_box);
-    main_box.append(&finance_box);
-    main_box.append(&bottom_box);
+    if config.show_finance { main_box.append(&finance_box); }
+    if config.show_calendar { main_box.append(&bottom_box); }
 
     let scroll_wrapper = gtk4::ScrolledWindow::builder()
         .child(&main_box)
@@ -534,8 +629,9 @@ pub fn build_ui(app: &Application) {
         btn_idle.add_css_class("active");
     }
 
-    // Toggle logic
+    // Toggle logic. A manual toggle always overrides a running timer.
     btn_idle.connect_clicked(move |btn| {
+        idle::cancel_timer();
         if btn.has_css_class("active") {
             // It's currently paused. Remove UI highlight and resume the daemon.
             btn.remove_css_class("active");
@@ -549,6 +645,9 @@ pub fn build_ui(app: &Application) {
         }
     });
 
+    // Right-click / long-press: inhibit for a fixed time
+    idle::attach_timer_menu(&btn_idle);
+
     // Launchers
     btn_wall.connect_clicked(move |_| helpers::run_home_bin("wp-select", &[]));
     btn_hint.connect_clicked(move |_| helpers::run_home_bin("kb-launcher", &[]));
@@ -599,15 +698,16 @@ pub fn build_ui(app: &Application) {
     });
 
     // Update Checker (Background Thread)
-    // Runs checkupdates/yay every 30 minutes to avoid spamming the CPU/Network.
+    // Runs checkupdates/yay every 30 minutes (update_poll_secs) to avoid spamming the CPU/Network.
     let (update_tx, update_rx) = std::sync::mpsc::channel();
     let lbl_update_target = lbl_update_badge.clone();
+    let update_poll = std::time::Duration::from_secs(config.update_poll_secs.max(60));
     std::thread::spawn(move || {
         loop {
             if let Some(stdout) = helpers::get_output_home_bin("update-check", &[]) {
                 let _ = update_tx.send(stdout);
             }
-            std::thread::sleep(std::time::Duration::from_secs(1800));
+            std::thread::sleep(update_poll);
         }
     });
 
@@ -630,6 +730,57 @@ pub fn build_ui(app: &Application) {
         else { btn_air_clone.add_css_class("active"); }
     });
 
+    // Bluetooth (Optimistic UI, then verify)
+    // Flip the class immediately, then poll `bluetoothctl show` for a few seconds
+    // (powering the controller takes a moment) and settle on the real state.
+    // Only one poll runs at a time: a quick second click replaces the first one's timer.
+    let btn_bt_clone = btn_bt.clone();
+    let bt_poll: Rc<RefCell<Option<glib::SourceId>>> = Rc::new(RefCell::new(None));
+    btn_bt.connect_clicked(move |_| {
+        let turning_on = !btn_bt_clone.has_css_class("active");
+        helpers::run_command("bluetoothctl", &["power", if turning_on { "on" } else { "off" }]);
+        if turning_on { btn_bt_clone.add_css_class("active"); }
+        else { btn_bt_clone.remove_css_class("active"); }
+
+        if let Some(previous) = bt_poll.borrow_mut().take() {
+            previous.remove();
+        }
+        let btn_target = btn_bt_clone.clone();
+        let (bt_tx, bt_rx) = mpsc::channel::<Option<Vec<u8>>>();
+        let bt_in_flight = Arc::new(AtomicBool::new(false));
+        let bt_poll_done = bt_poll.clone();
+        let mut attempts = 0;
+        let id = glib::timeout_add_local(std::time::Duration::from_secs(1), move || {
+            if let Ok(Some(stdout)) = bt_rx.try_recv() {
+                if parse_bt_powered(&stdout) {
+                    btn_target.add_css_class("active");
+                } else {
+                    btn_target.remove_css_class("active");
+                }
+            }
+
+            attempts += 1;
+            if !bt_in_flight.swap(true, Ordering::AcqRel) {
+                let bt_tx_bg = bt_tx.clone();
+                let bt_in_flight_bg = Arc::clone(&bt_in_flight);
+                std::thread::spawn(move || {
+                    let out = helpers::get_output("bluetoothctl", &["show"]);
+                    let _ = bt_tx_bg.send(out);
+                    bt_in_flight_bg.store(false, Ordering::Release);
+                });
+            }
+
+            if attempts >= 5 {
+                // The source is gone after Break; forget its id so it isn't removed twice
+                bt_poll_done.borrow_mut().take();
+                glib::ControlFlow::Break
+            } else {
+                glib::ControlFlow::Continue
+            }
+        });
+        *bt_poll.borrow_mut() = Some(id);
+    });
+
     // Audio Mute (Optimistic UI)
     let btn_mute_clone = btn_mute.clone();
     btn_mute.connect_clicked(move |_| {
@@ -645,14 +796,22 @@ pub fn build_ui(app: &Application) {
     });
 
     let (sender, receiver) = std::sync::mpsc::channel();
-    std::thread::spawn(move || {
-        let output = helpers::get_output_home_bin("waybar-finance", &[]);
-        let _ = sender.send(output);
-    });
+    // Hidden ticker: skip the fetch entirely (the receiver below just sees a closed channel).
+    if config.show_finance {
+        std::thread::spawn(move || {
+            let output = helpers::get_output_home_bin("waybar-finance", &[]);
+            let _ = sender.send(output);
+        });
+    }
 
     let finance_label_update = finance_label.clone();
     glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
-        if let Ok(Some(stdout)) = receiver.try_recv() {
+        let msg = receiver.try_recv();
+        // Sender dropped without a result (ticker hidden): stop waiting.
+        if matches!(msg, Err(mpsc::TryRecvError::Disconnected)) {
+            return glib::ControlFlow::Break;
+        }
+        if let Ok(Some(stdout)) = msg {
             if let Ok(json) = serde_json::from_slice::<Value>(&stdout)
                 && let Some(text) = json.get("text").and_then(|v| v.as_str()) {
                     // Manual HTML/Pango parsing to format the grid 
@@ -678,25 +837,28 @@ pub fn build_ui(app: &Application) {
 
     // ================= MASTER STATUS LOADER =================
     // To ensure the sidebar opens INSTANTLY, we don't block the main thread checking states.
-    // Instead, we spawn one worker thread to check DNS, Airplane, Mute, Volume, and Brightness
+    // Instead, we spawn one worker thread to check DNS, Airplane, Bluetooth, Mute, Volume, and Brightness
     // in parallel, then update the UI once the data arrives (approx 50-100ms later).
     let btn_dns_load = btn_dns.clone();
     let btn_air_load = btn_air.clone();
+    let btn_bt_load = btn_bt.clone();
     let btn_mute_load = btn_mute.clone();
     let scale_bright_load = scale_brightness.clone();
     let scale_vol_load = scale_volume.clone();
+    let slider_syncing_load = slider_syncing.clone();
 
     let (status_tx, status_rx) = std::sync::mpsc::channel();
     std::thread::spawn(move || {
         let dns_o = helpers::get_output_home_bin("cf-status", &[]);
         let air_o = helpers::get_output("rfkill", &["list", "wlan"]);
+        let bt_o = helpers::get_output("bluetoothctl", &["show"]);
         let mute_o = helpers::get_output("wpctl", &["get-volume", "@DEFAULT_AUDIO_SINK@"]);
         let bright_o = helpers::get_output("brightnessctl", &["i", "-m"]);
-        let _ = status_tx.send((dns_o, air_o, mute_o, bright_o));
+        let _ = status_tx.send((dns_o, air_o, bt_o, mute_o, bright_o));
     });
 
     glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
-        if let Ok((dns_o, air_o, mute_o, bright_o)) = status_rx.try_recv() {
+        if let Ok((dns_o, air_o, bt_o, mute_o, bright_o)) = status_rx.try_recv() {
             // Apply DNS State
             if let Some(out) = dns_o
                 && let Ok(json) = serde_json::from_slice::<Value>(&out)
@@ -704,18 +866,21 @@ pub fn build_ui(app: &Application) {
             // Apply Airplane State
             if let Some(out) = air_o
                 && String::from_utf8_lossy(&out).contains("Soft blocked: yes") { btn_air_load.add_css_class("active"); }
+            // Apply Bluetooth State
+            if let Some(out) = bt_o
+                && parse_bt_powered(&out) { btn_bt_load.add_css_class("active"); }
             // Apply Mute/Volume State
             if let Some(out) = mute_o {
                 let s = String::from_utf8_lossy(&out);
                 if s.contains("[MUTED]") { btn_mute_load.add_css_class("active"); }
                 if let Some(vol_str) = s.split_whitespace().nth(1)
-                    && let Ok(vol) = vol_str.parse::<f64>() { scale_vol_load.set_value(vol * 100.0); }
+                    && let Ok(vol) = vol_str.parse::<f64>() { sync_slider(&scale_vol_load, &slider_syncing_load, vol * 100.0); }
             }
             // Apply Brightness State
             if let Some(out) = bright_o
                 && let Some(p) = String::from_utf8_lossy(&out).split(',').nth(3)
                      && let Ok(val) = p.replace("%", "").replace("\n", "").parse::<f64>() {
-                         scale_bright_load.set_value(val);
+                         sync_slider(&scale_bright_load, &slider_syncing_load, val);
                      }
             glib::ControlFlow::Break
         } else {
@@ -729,25 +894,30 @@ pub fn build_ui(app: &Application) {
     let scale_bright_watch = scale_brightness.clone();
     let scale_vol_watch = scale_volume.clone();
     let last_interaction_watch = last_interaction.clone();
+    let slider_syncing_watch = slider_syncing.clone();
 
     // Background poll result channel for external brightness/volume changes.
     let (slider_tx, slider_rx) = mpsc::channel::<SliderSnapshot>();
     let slider_in_flight = Arc::new(AtomicBool::new(false));
 
-    glib::timeout_add_seconds_local(1, move || {
-        if let Ok(snapshot) = slider_rx.try_recv() {
-            if let Some(sys_val) = snapshot.brightness
-                && (scale_bright_watch.value() - sys_val).abs() > 1.0 {
-                    scale_bright_watch.set_value(sys_val);
-                }
-            if let Some(sys_val) = snapshot.volume
-                && (scale_vol_watch.value() - sys_val).abs() > 1.0 {
-                    scale_vol_watch.set_value(sys_val);
-                }
-        }
+    glib::timeout_add_seconds_local(config.slider_poll_secs.max(1), move || {
+        // Guard: If user touched slider < 500ms ago, skip external refresh.
+        // Checked before applying too, since a poll may have started before the drag.
+        let user_active = last_interaction_watch.borrow().elapsed().as_millis() < SLIDER_GUARD_MS;
+
+        if let Ok(snapshot) = slider_rx.try_recv()
+            && !user_active {
+                if let Some(sys_val) = snapshot.brightness
+                    && (scale_bright_watch.value() - sys_val).abs() > 1.0 {
+                        sync_slider(&scale_bright_watch, &slider_syncing_watch, sys_val);
+                    }
+                if let Some(sys_val) = snapshot.volume
+                    && (scale_vol_watch.value() - sys_val).abs() > 1.0 {
+                        sync_slider(&scale_vol_watch, &slider_syncing_watch, sys_val);
+                    }
+            }
 
-        // Guard: If user touched slider < 3 seconds ago, skip external refresh.
-        if last_interaction_watch.borrow().elapsed().as_secs() < 3 {
+        if user_active {
             return glib::ControlFlow::Continue;
         }
 
@@ -771,5 +941,22 @@ pub fn build_ui(app: &Application) {
         glib::ControlFlow::Continue
     });
 
+    // ================= KEYBOARD NAVIGATION =================
+    // Arrow keys walk the quick-toggle grid; Enter/Space activate; Escape closes.
+    let session_row: Vec<gtk4::Widget> = [&btn_idle, &btn_suspend, &btn_lock, &btn_logout, &btn_restart, &btn_power]
+        .into_iter()
+        .map(|b| b.clone().upcast())
+        .collect();
+    let toggle_row: Vec<gtk4::Widget> = [&btn_radio, &btn_wall, &btn_dns, &btn_update, &btn_wifi, &btn_air, &btn_bt, &btn_mute, &btn_shot, &btn_hint]
+        .into_iter()
+        .map(|b| b.clone().upcast())
+        .collect();
+    keynav::install(&window, vec![
+        session_row,
+        toggle_row,
+        vec![scale_brightness.clone().upcast()],
+        vec![scale_volume.clone().upcast()],
+    ]);
+
     window.present();
 }
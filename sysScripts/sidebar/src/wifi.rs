@@ -0,0 +1,298 @@
+//! Wi-Fi network picker backed by nmcli.
+//!
+//! A quick-toggle button whose popover lists nearby networks with signal bars.
+//! Scans and connection attempts run on worker threads and report back over mpsc,
+//! polled from a glib timer like the other background checks in `ui.rs`.
+
+use gtk4::prelude::*;
+use gtk4::{Align, Box, Button, Entry, Label, Orientation, Popover, ScrolledWindow};
+use crate::helpers;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::time::Duration;
+
+// Seconds nmcli waits for a connection to come up before giving up.
+const CONNECT_WAIT_SECS: &str = "30";
+
+struct Network {
+    ssid: String,
+    signal: u8,
+    secured: bool,
+    in_use: bool,
+    saved: Option<String>, // UUID of a saved connection for this SSID (nmcli has the password)
+}
+
+/// Widgets the scan/connect callbacks update. All GTK handles, so clones are cheap.
+#[derive(Clone)]
+struct Picker {
+    popover: Popover,
+    status: Label,
+    list: Box,
+    password_row: Box,
+    password_entry: Entry,
+    pending_ssid: Rc<RefCell<Option<String>>>,
+}
+
+/// Splits one `nmcli -t` line on unescaped ':' (nmcli escapes ':' and '\' inside values).
+fn split_terse(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    fields.last_mut().unwrap().push(next);
+                }
+            }
+            ':' => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+fn signal_bars(signal: u8) -> &'static str {
+    match signal {
+        0..=24 => "▂___",
+        25..=49 => "▂▄__",
+        50..=74 => "▂▄▆_",
+        _ => "▂▄▆█",
+    }
+}
+
+/// SSID -> UUID of every saved Wi-Fi connection. Profiles can be renamed, so the SSID
+/// comes from each profile's `802-11-wireless.ssid` rather than its name.
+fn saved_connections() -> HashMap<String, String> {
+    let Some(list) = helpers::get_output("nmcli", &["-t", "-f", "UUID,TYPE", "connection", "show"]) else {
+        return HashMap::new();
+    };
+    String::from_utf8_lossy(&list)
+        .lines()
+        .map(split_terse)
+        .filter(|f| f.get(1).is_some_and(|t| t == "802-11-wireless"))
+        .filter_map(|f| {
+            let uuid = f[0].clone();
+            let out = helpers::get_output("nmcli", &["-g", "802-11-wireless.ssid", "connection", "show", "uuid", &uuid])?;
+            let ssid = split_terse(String::from_utf8_lossy(&out).trim_end()).swap_remove(0);
+            (!ssid.is_empty()).then_some((ssid, uuid))
+        })
+        .collect()
+}
+
+/// Lists visible networks, one entry per SSID (strongest access point wins).
+/// Hidden networks (empty SSID) are skipped. The connected network sorts first.
+fn scan() -> Option<Vec<Network>> {
+    let out = helpers::get_output("nmcli", &["-t", "-f", "IN-USE,SSID,SIGNAL,SECURITY", "dev", "wifi", "list"])?;
+    let saved = saved_connections();
+
+    let mut networks: Vec<Network> = Vec::new();
+    for line in String::from_utf8_lossy(&out).lines() {
+        let fields = split_terse(line);
+        let [in_use, ssid, signal, security] = fields.as_slice() else { continue };
+        if ssid.is_empty() {
+            continue;
+        }
+        let network = Network {
+            saved: saved.get(ssid).cloned(),
+            ssid: ssid.clone(),
+            signal: signal.parse().unwrap_or(0),
+            secured: !security.is_empty() && security != "--",
+            in_use: in_use == "*",
+        };
+        match networks.iter_mut().find(|n| n.ssid == network.ssid) {
+            Some(existing) => {
+                existing.in_use |= network.in_use;
+                existing.signal = existing.signal.max(network.signal);
+            }
+            None => networks.push(network),
+        }
+    }
+    networks.sort_by(|a, b| b.in_use.cmp(&a.in_use).then(b.signal.cmp(&a.signal)));
+    Some(networks)
+}
+
+/// Blocking connect: brings up the saved connection if there is one, otherwise
+/// `nmcli dev wifi connect`. Returns nmcli's own error text on failure.
+/// The password goes through `--ask` on stdin, never argv, where `ps` would show it.
+fn connect(ssid: &str, saved: Option<&str>, password: Option<&str>) -> Result<(), String> {
+    let mut cmd = Command::new("nmcli");
+    if password.is_some() {
+        cmd.arg("--ask");
+    }
+    match saved {
+        Some(uuid) => cmd.args(["-w", CONNECT_WAIT_SECS, "connection", "up", "uuid", uuid]),
+        None => cmd.args(["-w", CONNECT_WAIT_SECS, "dev", "wifi", "connect", ssid]),
+    };
+    cmd.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+
+    let mut child = cmd.spawn().map_err(|e| format!("Failed to run nmcli: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take()
+        && let Some(password) = password
+    {
+        let _ = writeln!(stdin, "{}", password);
+    }
+    match child.wait_with_output() {
+        Ok(out) if out.status.success() => Ok(()),
+        Ok(out) => Err(String::from_utf8_lossy(&out.stderr).trim().to_string()),
+        Err(e) => Err(format!("Failed to run nmcli: {}", e)),
+    }
+}
+
+fn start_scan(picker: &Picker) {
+    picker.status.set_label("Scanning…");
+    picker.password_row.set_visible(false);
+    while let Some(child) = picker.list.first_child() {
+        picker.list.remove(&child);
+    }
+
+    let (tx, rx) = mpsc::channel::<Option<Vec<Network>>>();
+    std::thread::spawn(move || {
+        let _ = tx.send(scan());
+    });
+
+    let picker = picker.clone();
+    glib::timeout_add_local(Duration::from_millis(100), move || match rx.try_recv() {
+        Ok(Some(networks)) => {
+            show_networks(&picker, networks);
+            glib::ControlFlow::Break
+        }
+        Ok(None) | Err(mpsc::TryRecvError::Disconnected) => {
+            picker.status.set_label("Wi-Fi scan failed (is NetworkManager running?)");
+            glib::ControlFlow::Break
+        }
+        Err(mpsc::TryRecvError::Empty) => glib::ControlFlow::Continue,
+    });
+}
+
+fn show_networks(picker: &Picker, networks: Vec<Network>) {
+    picker.status.set_label(if networks.is_empty() { "No networks found" } else { "Wi-Fi Networks" });
+    for network in networks {
+        let lock = if network.secured { " 🔒" } else { "" };
+        let check = if network.in_use { "  ✓" } else { "" };
+        let row = Button::builder()
+            .label(format!("{}  {}{}{}", signal_bars(network.signal), network.ssid, lock, check))
+            .css_classes(vec!["flat".to_string()])
+            .halign(Align::Fill)
+            .build();
+        if let Some(label) = row.child().and_downcast::<Label>() {
+            label.set_halign(Align::Start);
+            label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+        }
+
+        let picker_row = picker.clone();
+        row.connect_clicked(move |_| {
+            if network.in_use {
+                return;
+            }
+            if network.secured && network.saved.is_none() {
+                // New secured network: ask for the password first
+                *picker_row.pending_ssid.borrow_mut() = Some(network.ssid.clone());
+                picker_row.status.set_label(&format!("Password for {}", network.ssid));
+                picker_row.password_entry.set_text("");
+                picker_row.password_row.set_visible(true);
+                picker_row.password_entry.grab_focus();
+            } else {
+                start_connect(&picker_row, network.ssid.clone(), network.saved.clone(), None);
+            }
+        });
+        picker.list.append(&row);
+    }
+}
+
+fn start_connect(picker: &Picker, ssid: String, saved: Option<String>, password: Option<String>) {
+    picker.status.set_label(&format!("Connecting to {}…", ssid));
+    picker.password_row.set_visible(false);
+
+    let (tx, rx) = mpsc::channel::<Result<(), String>>();
+    let ssid_bg = ssid.clone();
+    std::thread::spawn(move || {
+        let _ = tx.send(connect(&ssid_bg, saved.as_deref(), password.as_deref()));
+    });
+
+    let picker = picker.clone();
+    glib::timeout_add_local(Duration::from_millis(200), move || match rx.try_recv() {
+        Ok(Ok(())) => {
+            picker.status.set_label(&format!("Connected to {}", ssid));
+            let popover = picker.popover.clone();
+            glib::timeout_add_local_once(Duration::from_secs(1), move || popover.popdown());
+            glib::ControlFlow::Break
+        }
+        Ok(Err(e)) => {
+            picker.status.set_label(&e);
+            glib::ControlFlow::Break
+        }
+        Err(mpsc::TryRecvError::Disconnected) => glib::ControlFlow::Break,
+        Err(mpsc::TryRecvError::Empty) => glib::ControlFlow::Continue,
+    });
+}
+
+/// Builds the quick-toggle button. Clicking it opens the picker and starts a fresh scan.
+pub fn build_button() -> Button {
+    let button = helpers::make_icon_button("network-wireless-symbolic", "Wi-Fi Networks");
+
+    let status = Label::builder()
+        .label("Wi-Fi Networks")
+        .css_classes(vec!["hint-text".to_string()])
+        .wrap(true)
+        .max_width_chars(30)
+        .halign(Align::Start)
+        .build();
+    let list = Box::new(Orientation::Vertical, 2);
+    let scroller = ScrolledWindow::builder()
+        .child(&list)
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .min_content_height(200)
+        .min_content_width(260)
+        .build();
+
+    let password_entry = Entry::builder()
+        .visibility(false)
+        .placeholder_text("Password")
+        .hexpand(true)
+        .build();
+    let btn_join = Button::with_label("Connect");
+    let password_row = Box::new(Orientation::Horizontal, 6);
+    password_row.append(&password_entry);
+    password_row.append(&btn_join);
+    password_row.set_visible(false);
+
+    let content = Box::new(Orientation::Vertical, 6);
+    content.append(&status);
+    content.append(&scroller);
+    content.append(&password_row);
+
+    let popover = Popover::builder().child(&content).build();
+    popover.set_parent(&button);
+
+    let picker = Picker {
+        popover,
+        status,
+        list,
+        password_row,
+        password_entry,
+        pending_ssid: Rc::new(RefCell::new(None)),
+    };
+
+    // Enter in the entry and the Connect button both submit the password
+    let picker_submit = picker.clone();
+    let submit = Rc::new(move || {
+        let Some(ssid) = picker_submit.pending_ssid.borrow_mut().take() else { return };
+        let password = picker_submit.password_entry.text().to_string();
+        start_connect(&picker_submit, ssid, None, Some(password));
+    });
+    let submit_entry = submit.clone();
+    picker.password_entry.connect_activate(move |_| submit_entry());
+    btn_join.connect_clicked(move |_| submit());
+
+    let picker_open = picker.clone();
+    button.connect_clicked(move |_| {
+        picker_open.popover.popup();
+        start_scan(&picker_open);
+    });
+
+    button
+}
@@ -0,0 +1,8 @@
+//! Workspace Reporter
+//!
+//! Prints the focused workspace (or every workspace with `--all`) for Sway, Hyprland and Niri.
+//! See the library crate for the details.
+
+fn main() -> anyhow::Result<()> {
+    sway_workspace::run()
+}
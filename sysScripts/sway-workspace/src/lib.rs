@@ -0,0 +1,271 @@
+//! Workspace Reporter
+//!
+//! A minimal IPC client that queries the running compositor (Sway, Hyprland or Niri) for the
+//! currently focused workspace. Designed for use in status bars (like Waybar) or shell scripts
+//! that need context awareness of the window manager's state, with one module config shared
+//! by all three sessions.
+//!
+//! With `--all` it prints every workspace as a JSON array instead, for building a
+//! clickable workspace switcher. With `--watch` it stays connected and prints a new line
+//! on every workspace event, so Waybar doesn't have to poll.
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::io::{BufRead, BufReader};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use swayipc::{Connection, EventType};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Compositor {
+    Sway,
+    Hyprland,
+    Niri,
+}
+
+/// Identifies the compositor by its IPC socket variables, the same checks the other tools use.
+fn detect_compositor() -> Option<Compositor> {
+    if env::var("NIRI_SOCKET").is_ok() {
+        return Some(Compositor::Niri);
+    }
+    if env::var("HYPRLAND_INSTANCE_SIGNATURE").is_ok() {
+        return Some(Compositor::Hyprland);
+    }
+    if env::var("SWAYSOCK").is_ok() {
+        return Some(Compositor::Sway);
+    }
+    None
+}
+
+/// The workspace fields `--all` exposes. Every backend is mapped onto this shape.
+#[derive(Serialize)]
+struct WorkspaceInfo {
+    name: String,
+    num: i32,
+    focused: bool,
+    urgent: bool,
+    visible: bool,
+    output: String,
+}
+
+// --- Hyprland (`hyprctl -j`) ---
+
+#[derive(Deserialize)]
+struct HyprWorkspace {
+    id: i32,
+    name: String,
+    monitor: String,
+}
+
+#[derive(Deserialize)]
+struct HyprMonitor {
+    focused: bool,
+    #[serde(rename = "activeWorkspace")]
+    active_workspace: HyprWorkspaceRef,
+}
+
+#[derive(Deserialize)]
+struct HyprWorkspaceRef {
+    id: i32,
+}
+
+// --- Niri (`niri msg --json`) ---
+
+#[derive(Deserialize)]
+struct NiriWorkspace {
+    idx: u8,
+    name: Option<String>,
+    output: Option<String>,
+    is_active: bool,
+    is_focused: bool,
+    #[serde(default)]
+    is_urgent: bool, // Only reported by newer niri releases
+}
+
+/// Runs a compositor CLI and parses its JSON output.
+fn command_json<T: serde::de::DeserializeOwned>(program: &str, args: &[&str]) -> Result<T> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run {}", program))?;
+    if !output.status.success() {
+        return Err(anyhow!("{} {} failed", program, args.join(" ")));
+    }
+    serde_json::from_slice(&output.stdout).with_context(|| format!("Failed to parse {} output", program))
+}
+
+/// Holds whatever a query needs: a live IPC connection for Sway, nothing for the CLI-based ones.
+enum Backend {
+    Sway(Connection),
+    Hyprland,
+    Niri,
+}
+
+impl Backend {
+    fn connect(compositor: Compositor) -> Result<Self> {
+        Ok(match compositor {
+            // Connects to the Unix socket defined in the $SWAYSOCK environment variable.
+            // Use the `swayipc` crate to abstract the low-level JSON-IPC protocol.
+            Compositor::Sway => Backend::Sway(
+                Connection::new().context("Failed to connect to sway IPC. Is sway running?")?,
+            ),
+            Compositor::Hyprland => Backend::Hyprland,
+            Compositor::Niri => Backend::Niri,
+        })
+    }
+
+    fn workspaces(&mut self) -> Result<Vec<WorkspaceInfo>> {
+        match self {
+            Backend::Sway(connection) => {
+                let workspaces = connection.get_workspaces()
+                    .context("Failed to fetch workspaces")?;
+                Ok(workspaces.into_iter()
+                    .map(|ws| WorkspaceInfo {
+                        name: ws.name,
+                        num: ws.num,
+                        focused: ws.focused,
+                        urgent: ws.urgent,
+                        visible: ws.visible,
+                        output: ws.output,
+                    })
+                    .collect())
+            }
+            Backend::Hyprland => {
+                // hyprctl has no per-workspace focus/visibility, so derive it from the monitors.
+                // Hyprland doesn't expose urgency here either.
+                let monitors: Vec<HyprMonitor> = command_json("hyprctl", &["-j", "monitors"])?;
+                let mut workspaces: Vec<HyprWorkspace> = command_json("hyprctl", &["-j", "workspaces"])?;
+                workspaces.retain(|ws| ws.id > 0); // Negative ids are special (scratchpad) workspaces
+                workspaces.sort_by_key(|ws| ws.id);
+                Ok(workspaces.into_iter()
+                    .map(|ws| {
+                        let shown_on = monitors.iter().find(|m| m.active_workspace.id == ws.id);
+                        WorkspaceInfo {
+                            focused: shown_on.is_some_and(|m| m.focused),
+                            visible: shown_on.is_some(),
+                            urgent: false,
+                            num: ws.id,
+                            name: ws.name,
+                            output: ws.monitor,
+                        }
+                    })
+                    .collect())
+            }
+            Backend::Niri => {
+                let mut workspaces: Vec<NiriWorkspace> = command_json("niri", &["msg", "--json", "workspaces"])?;
+                workspaces.sort_by(|a, b| a.output.cmp(&b.output).then(a.idx.cmp(&b.idx)));
+                Ok(workspaces.into_iter()
+                    .map(|ws| WorkspaceInfo {
+                        // Unnamed niri workspaces are only known by their index
+                        name: ws.name.unwrap_or_else(|| ws.idx.to_string()),
+                        num: i32::from(ws.idx),
+                        focused: ws.is_focused,
+                        urgent: ws.is_urgent,
+                        visible: ws.is_active,
+                        output: ws.output.unwrap_or_default(),
+                    })
+                    .collect())
+            }
+        }
+    }
+
+    /// Queries the workspaces and renders them in the requested format.
+    fn render(&mut self, all: bool) -> Result<String> {
+        let workspaces = self.workspaces()?;
+        if all {
+            serde_json::to_string(&workspaces).context("Failed to serialize workspaces")
+        } else {
+            // Use a functional iterator chain to find the single workspace marked as focused.
+            Ok(workspaces
+                .into_iter()
+                .find(|ws| ws.focused)               // Predicate: Is this the active one?
+                .map(|ws| ws.name)                   // Transform: I only care about the name string
+                .unwrap_or_else(|| "?".to_string())) // Fallback for transient states (e.g. during startup)
+        }
+    }
+}
+
+/// Hyprland's event socket. Newer releases live under $XDG_RUNTIME_DIR, older ones in /tmp.
+fn hyprland_event_socket() -> Result<PathBuf> {
+    let signature = env::var("HYPRLAND_INSTANCE_SIGNATURE").context("HYPRLAND_INSTANCE_SIGNATURE is not set")?;
+    let runtime = env::var("XDG_RUNTIME_DIR").map(|d| PathBuf::from(d).join("hypr").join(&signature).join(".socket2.sock"));
+    match runtime {
+        Ok(path) if path.exists() => Ok(path),
+        _ => Ok(PathBuf::from("/tmp/hypr").join(&signature).join(".socket2.sock")),
+    }
+}
+
+/// Prints `line` unless it repeats the previous output.
+fn print_if_changed(line: String, last: &mut Option<String>) {
+    if last.as_ref() != Some(&line) {
+        println!("{}", line);
+        *last = Some(line);
+    }
+}
+
+/// Prints the current state, then again after every workspace event.
+/// Events also fire for changes we don't print (e.g. renames of other workspaces),
+/// so repeated lines are dropped.
+fn watch(compositor: Compositor, backend: &mut Backend, all: bool) -> Result<()> {
+    let mut last = None;
+    print_if_changed(backend.render(all)?, &mut last);
+
+    match compositor {
+        Compositor::Sway => {
+            // `subscribe` consumes its connection, so queries go over the backend's one.
+            let events = Connection::new()
+                .context("Failed to connect to sway IPC. Is sway running?")?
+                .subscribe([EventType::Workspace])
+                .context("Failed to subscribe to workspace events")?;
+            for event in events {
+                event.context("Lost the sway IPC event stream")?;
+                print_if_changed(backend.render(all)?, &mut last);
+            }
+        }
+        Compositor::Hyprland => {
+            // One "event>>data" line per event
+            let socket = UnixStream::connect(hyprland_event_socket()?)
+                .context("Failed to connect to the Hyprland event socket")?;
+            for line in BufReader::new(socket).lines() {
+                let line = line.context("Lost the Hyprland event stream")?;
+                let event = line.split(">>").next().unwrap_or_default();
+                if event.contains("workspace") || event == "focusedmon" {
+                    print_if_changed(backend.render(all)?, &mut last);
+                }
+            }
+        }
+        Compositor::Niri => {
+            // One JSON object per line, e.g. {"WorkspaceActivated":{...}}
+            let mut child = Command::new("niri")
+                .args(["msg", "--json", "event-stream"])
+                .stdout(Stdio::piped())
+                .spawn()
+                .context("Failed to run niri msg event-stream")?;
+            let stdout = child.stdout.take().context("No niri event stream")?;
+            for line in BufReader::new(stdout).lines() {
+                let line = line.context("Lost the niri event stream")?;
+                if line.starts_with("{\"Workspace") {
+                    print_if_changed(backend.render(all)?, &mut last);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Shared entry point of `workspace-reporter` and the older `sway-workspace` name.
+pub fn run() -> Result<()> {
+    let all = env::args().any(|a| a == "--all");
+    let compositor = detect_compositor()
+        .context("Could not detect the compositor (checked NIRI_SOCKET, HYPRLAND_INSTANCE_SIGNATURE, SWAYSOCK)")?;
+    let mut backend = Backend::connect(compositor)?;
+
+    if env::args().any(|a| a == "--watch") {
+        return watch(compositor, &mut backend, all);
+    }
+    // Print strictly to stdout so this binary can be used as a `custom/script` source in Waybar.
+    println!("{}", backend.render(all)?);
+    Ok(())
+}
@@ -6,30 +6,53 @@
 //! 1. **Speed:** Checks must be fast to avoid blocking the bar startup.
 //! 2. **Resilience:** If the check fails (e.g., no internet), it falls back to the last known cached count instead of crashing or showing "Error".
 //! 3. **Visual Feedback:** Distinct JSON classes ("updates", "synced", "stale", "error") allow CSS styling in Waybar (e.g., turning red if stale).
+//! 4. **Arch News:** Optionally flags unread archlinux.org news (manual interventions) with a ⚠ and an extra "news" class.
+//!
+//! Usage:
+//! - `update-check`                  One check, one JSON line (Waybar polls via "interval").
+//! - `update-check --watch`          Stays alive, re-checks every `interval_secs` and prints a new line each time.
+//! - `update-check --mark-news-read` Clears the news flag once the latest post has been read.
+//! - `update-check --count-only`     For scripts: prints just the count. Exit code 0 = up to date,
+//!   1 = updates pending, 2 = check failed (the cached count is printed if there is one).
+//! - `-v` / `-vv`                    Logs the check and cache decisions to stderr.
+//! - `--version`                     Prints the version and the git commit it was built from.
 
+use std::env;
 use std::fs;
-use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::path::Path;
+use std::process::{Command, ExitCode};
+use std::thread;
+use std::time::Duration;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
-
-fn expand_path(path: &str) -> PathBuf {
-    if let Some(stripped) = path.strip_prefix("~/")
-        && let Some(home) = dirs::home_dir() {
-            return home.join(stripped);
-        }
-    PathBuf::from(path)
-}
+use serde_json::{Value, json};
+use common::{expand_path, load_config};
 
 // --- Config Models ---
 
 #[derive(Deserialize, Debug)]
 struct UpdateCheckConfig {
     command_string: String,  // The shell command to count updates (e.g., "checkupdates | wc -l")
+    #[serde(default)]
+    official_command: Option<String>, // If set, counts repo updates separately (e.g., "checkupdates")
+    #[serde(default)]
+    aur_command: Option<String>,      // Optional AUR half of the split (e.g., "yay -Qua")
     cache_file: String,      // Path to store the last successful count
     stale_icon: String,      // Icon to append if data is old 
     error_icon: String,      // Icon for total failure
+    #[serde(default = "default_interval_secs")]
+    interval_secs: u64,      // --watch: seconds between checks
+    #[serde(default)]
+    bar_signal_num: Option<i32>, // --watch: send SIGRTMIN+N to waybar when the output changes
+    #[serde(default)]
+    arch_news: bool,         // Also check the Arch news feed for unread posts
+}
+
+const ARCH_NEWS_URL: &str = "https://archlinux.org/feeds/news/";
+const NEWS_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn default_interval_secs() -> u64 {
+    1800
 }
 
 #[derive(Deserialize, Debug)]
@@ -38,23 +61,45 @@ struct GlobalConfig {
 }
 
 // --- Persistence Model ---
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 struct Cache {
     count: usize,
+    // Only present when the split official/AUR commands are configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    breakdown: Option<Breakdown>,
+    // Only present with `arch_news`: the newest post, and the pubDate of the newest one read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    news_latest: Option<NewsItem>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    news_seen: Option<String>,
 }
 
-fn load_config() -> Result<GlobalConfig> {
-    let config_path = dirs::home_dir()
-        .context("Cannot find home dir")?
-        .join(".config/rust-dotfiles/config.toml");
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct NewsItem {
+    title: String,
+    date: String, // RSS pubDate, compared verbatim
+}
 
-    let config_str = fs::read_to_string(&config_path)
-        .with_context(|| format!("Failed to read config: {}", config_path.display()))?;
-    
-    let config: GlobalConfig = toml::from_str(&config_str)
-        .context("Failed to parse config.toml")?;
-    
-    Ok(config)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+struct Breakdown {
+    official: usize,
+    aur: usize,
+}
+
+impl Cache {
+    /// Tooltip detail, e.g. "12 official, 3 AUR". Empty for single-command checks.
+    fn detail(&self) -> String {
+        match self.breakdown {
+            Some(b) => format!("{} official, {} AUR", b.official, b.aur),
+            None => String::new(),
+        }
+    }
+
+    /// The newest news post, if it hasn't been marked read yet.
+    fn unread_news(&self) -> Option<&NewsItem> {
+        let latest = self.news_latest.as_ref()?;
+        (self.news_seen.as_deref() != Some(latest.date.as_str())).then_some(latest)
+    }
 }
 
 // --- Persistence Logic ---
@@ -67,9 +112,8 @@ fn read_cache(cache_path: &Path) -> Result<Cache> {
     Ok(cache)
 }
 
-fn save_cache(count: usize, cache_path: &Path) -> Result<()> {
-    let cache = Cache { count };
-    let json_data = serde_json::to_string(&cache)?;
+fn save_cache(cache: &Cache, cache_path: &Path) -> Result<()> {
+    let json_data = serde_json::to_string(cache)?;
     if let Some(parent) = cache_path.parent() {
         fs::create_dir_all(parent)?;
     }
@@ -80,9 +124,10 @@ fn save_cache(count: usize, cache_path: &Path) -> Result<()> {
 
 // --- Core Logic ---
 
-/// Executes the update check command defined in config.toml.
-/// Returns the number of updates found.
-fn run_check(command_string: &str) -> Result<usize> {
+/// Executes one update check command.
+/// Returns the number of updates found (one per output line).
+fn run_command(command_string: &str) -> Result<usize> {
+    log::debug!("Running '{}'", command_string);
     let output = Command::new("bash")
         .arg("-c")
         .arg(command_string)
@@ -91,14 +136,16 @@ fn run_check(command_string: &str) -> Result<usize> {
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let count = stdout.trim().lines().count();
+    log::debug!("'{}' exited with {} and listed {} update(s)", command_string, output.status, count);
     // Exit Code 0: Success.
     if output.status.success() {
         return Ok(count);
     }
 
-    // Exit Code 1: 'checkupdates' returns 1 if NO updates are found (not an error).
-    // We handle this edge case specifically.
-    if output.status.code() == Some(1) && count == 0 {
+    // Exit Code 1: 'yay -Qua' returns 1 if NO updates are found (not an error).
+    // Exit Code 2: 'checkupdates' does the same when run on its own.
+    // We handle these edge cases specifically.
+    if matches!(output.status.code(), Some(1) | Some(2)) && count == 0 {
         return Ok(0);
     }
     // Any other exit code is a legitimate failure (e.g., DB lock, no network).
@@ -109,50 +156,261 @@ fn run_check(command_string: &str) -> Result<usize> {
     );
 }
 
+/// Runs the configured check(s). With `official_command` set, repo and AUR updates are
+/// counted separately; otherwise the single `command_string` is used.
+fn run_check(config: &UpdateCheckConfig) -> Result<Cache> {
+    let Some(official_command) = &config.official_command else {
+        let count = run_command(&config.command_string)?;
+        return Ok(Cache { count, ..Default::default() });
+    };
+
+    let official = run_command(official_command).context("Official repo check failed")?;
+    let aur = match &config.aur_command {
+        Some(cmd) => run_command(cmd).context("AUR check failed")?,
+        None => 0,
+    };
+    Ok(Cache {
+        count: official + aur,
+        breakdown: Some(Breakdown { official, aur }),
+        ..Default::default()
+    })
+}
+
+// --- Arch News ---
+
+/// Text between the first `<tag>` and `</tag>` in `xml`.
+fn tag_text<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    let start = xml.find(&format!("<{}>", tag))? + tag.len() + 2;
+    let end = start + xml[start..].find(&format!("</{}>", tag))?;
+    Some(xml[start..end].trim())
+}
+
+/// Fetches the RSS feed and returns its first (newest) item. Titles stay XML-escaped,
+/// which is also what Waybar's Pango tooltips expect.
+fn fetch_latest_news() -> Result<NewsItem> {
+    log::debug!("Fetching {}", ARCH_NEWS_URL);
+    let client = reqwest::blocking::Client::builder()
+        .user_agent(concat!("update-check/", env!("CARGO_PKG_VERSION")))
+        .timeout(NEWS_TIMEOUT)
+        .build()?;
+    let feed = client
+        .get(ARCH_NEWS_URL)
+        .send()
+        .and_then(|r| r.error_for_status())
+        .and_then(|r| r.text())
+        .context("Failed to fetch Arch news feed")?;
+    let item = tag_text(&feed, "item").context("Arch news feed has no items")?;
+    Ok(NewsItem {
+        title: tag_text(item, "title").unwrap_or("Untitled").to_string(),
+        date: tag_text(item, "pubDate").context("News item has no pubDate")?.to_string(),
+    })
+}
+
+/// Fills in the news fields of a fresh check. A failed fetch keeps the last known post.
+/// On the very first fetch the current post counts as read, so only later posts alert.
+fn refresh_news(cache: &mut Cache, previous: Option<&Cache>) {
+    cache.news_latest = match fetch_latest_news() {
+        Ok(item) => Some(item),
+        Err(e) => {
+            log::warn!("Arch news check failed: {:#}", e);
+            previous.and_then(|p| p.news_latest.clone())
+        }
+    };
+    cache.news_seen = previous
+        .and_then(|p| p.news_seen.clone())
+        .or_else(|| cache.news_latest.as_ref().map(|n| n.date.clone()));
+    log::debug!(
+        "Latest news: {:?}, last read: {:?}",
+        cache.news_latest.as_ref().map(|n| &n.date),
+        cache.news_seen
+    );
+}
+
+/// --mark-news-read: records the latest known post as read.
+fn mark_news_read(cache_path: &Path) -> Result<()> {
+    let mut cache = read_cache(cache_path)?;
+    if let Some(latest) = &cache.news_latest {
+        cache.news_seen = Some(latest.date.clone());
+    }
+    save_cache(&cache, cache_path)
+}
+
 // --- Output Formatters (Waybar JSON Protocol) ---
 
 /// Standard success output.
 /// Classes: "updates" (if count > 0), "synced" (if 0).
-fn print_success_json(count: usize) {
+fn success_json(cache: &Cache) -> Value {
+    let count = cache.count;
     if count > 0 {
-        println!("{}", json!({
+        let tooltip = match cache.breakdown {
+            Some(_) => format!("{} Updates Available\n{}", count, cache.detail()),
+            None => format!("{} Updates Available", count),
+        };
+        json!({
             "text": count.to_string(),
-            "tooltip": format!("{} Updates Available", count),
+            "tooltip": tooltip,
             "class": "updates"
-        }));
+        })
     } else {
-        println!("{}", json!({
+        json!({
             "text": "0",
             "tooltip": "System is up to date",
             "class": "synced"
-        }));
+        })
     }
 }
 /// Fallback output when the check fails but cache exists.
 /// Class: "stale". Adds a visual indicator (icon) to the text.
-fn print_stale_json(stale_count: usize, config: &UpdateCheckConfig) {
-    println!("{}", json!({
+fn stale_json(stale: &Cache, config: &UpdateCheckConfig) -> Value {
+    let stale_count = stale.count;
+    let detail = match stale.breakdown {
+        Some(_) => format!(" ({})", stale.detail()),
+        None => String::new(),
+    };
+    json!({
         "text": format!("{} {}", stale_count, config.stale_icon),
         "tooltip": format!(
-            "Update check failed. Showing last known count: {}", 
-            stale_count
+            "Update check failed. Showing last known count: {}{}", 
+            stale_count, detail
         ),
         "class": "stale"
-    }));
+    })
+}
+/// Adds the unread-news marker: ⚠ on the text, the headline in the tooltip and a
+/// "news" class next to the existing one.
+fn with_news(output: Value, cache: &Cache) -> Value {
+    let Some(news) = cache.unread_news() else {
+        return output;
+    };
+    let field = |key: &str| output[key].as_str().unwrap_or_default().to_string();
+    json!({
+        "text": format!("{} ⚠", field("text")),
+        "tooltip": format!(
+            "{}\n\n⚠ Arch News: {}\nRead it before updating, then run `update-check --mark-news-read`",
+            field("tooltip"), news.title
+        ),
+        "class": [field("class"), "news"]
+    })
 }
 /// Total failure output (Check failed AND Cache missing).
 /// Class: "error".
-fn print_error_json(config: &UpdateCheckConfig, error_msg: &str) {
-    println!("{}", json!({
+fn error_json(config: &UpdateCheckConfig, error_msg: &str) -> Value {
+    json!({
         "text": config.error_icon.clone(),
         "tooltip": format!("Update check failed:\n{}", error_msg),
         "class": "error"
-    }));
+    })
+}
+
+/// Result of one check, before it is rendered for Waybar or a script.
+enum Outcome {
+    Fresh(Cache),
+    Stale(Cache),    // Check failed, last known count
+    Failed(String),  // Check failed and no cache
+}
+
+/// Runs one check.
+/// Strategy: Try Live Check -> Fallback to Cache -> Error
+fn check(config: &UpdateCheckConfig, cache_path: &Path) -> Outcome {
+    let previous = read_cache(cache_path);
+    if let Err(e) = &previous {
+        log::debug!("No usable cache at {}: {:#}", cache_path.display(), e);
+    }
+    match run_check(config) {
+        Ok(mut cache) => {
+            if config.arch_news {
+                refresh_news(&mut cache, previous.as_ref().ok());
+            }
+            // Happy Path: Update cache and display fresh data
+            log::info!("{} update(s) available", cache.count);
+            if let Err(e) = save_cache(&cache, cache_path) {
+                log::warn!("Failed to save cache: {:#}", e);
+            }
+            Outcome::Fresh(cache)
+        }
+        Err(check_err) => {
+            // Check failed. Attempt recovery via cache.
+            log::warn!("Update check failed: {:#}", check_err);
+            match previous {
+                Ok(cache) => {
+                    log::info!("Showing the cached count ({}) as stale", cache.count);
+                    Outcome::Stale(cache)
+                }
+                Err(cache_err) => {
+                    // Critical Failure
+                    Outcome::Failed(format!("Check Error: {}\nCache Error: {}", check_err, cache_err))
+                }
+            }
+        }
+    }
+}
+
+/// Runs one check and returns the Waybar JSON for it.
+fn check_once(config: &UpdateCheckConfig, cache_path: &Path) -> Value {
+    match check(config, cache_path) {
+        Outcome::Fresh(cache) => with_news(success_json(&cache), &cache),
+        Outcome::Stale(cache) => with_news(stale_json(&cache, config), &cache),
+        Outcome::Failed(error_msg) => error_json(config, &error_msg),
+    }
+}
+
+/// --count-only: prints the count and returns the exit code for it.
+fn count_only(config: &UpdateCheckConfig, cache_path: &Path) -> u8 {
+    match check(config, cache_path) {
+        Outcome::Fresh(cache) => {
+            println!("{}", cache.count);
+            if cache.count > 0 { 1 } else { 0 }
+        }
+        Outcome::Stale(cache) => {
+            println!("{}", cache.count);
+            2
+        }
+        Outcome::Failed(error_msg) => {
+            log::error!("{}", error_msg);
+            2
+        }
+    }
 }
 
-fn main() -> Result<()> {
-    let config = match load_config() {
+/// Signals Waybar (SIGRTMIN+N) so any module bound to that signal refreshes instantly.
+/// Harmless if Waybar isn't running.
+fn signal_bar(signal_num: i32) {
+    let sig_rtmin = 34;
+    let _ = Command::new("pkill")
+        .arg(format!("-{}", sig_rtmin + signal_num))
+        .arg("-x")
+        .arg("waybar")
+        .status();
+}
+
+/// --watch: re-check forever, printing one JSON line per check.
+fn watch(config: &UpdateCheckConfig, cache_path: &Path) -> ! {
+    let interval = Duration::from_secs(config.interval_secs.max(1));
+    let mut last: Option<Value> = None;
+    loop {
+        let output = check_once(config, cache_path);
+        println!("{}", output);
+
+        if let Some(signal_num) = config.bar_signal_num
+            && last.as_ref().is_some_and(|prev| *prev != output) {
+                signal_bar(signal_num);
+            }
+        last = Some(output);
+        thread::sleep(interval);
+    }
+}
+
+fn main() -> Result<ExitCode> {
+    common::version::exit_if_requested(env!("CARGO_BIN_NAME"), env!("CARGO_PKG_VERSION"));
+    common::logging::init(module_path!(), common::logging::verbosity_from_args());
+    let count_only_mode = env::args().any(|a| a == "--count-only");
+    let config = match load_config::<GlobalConfig>() {
         Ok(global_config) => global_config.update_check,
+        Err(e) if count_only_mode => {
+            // Exit 1 would read as "updates pending"
+            log::error!("Failed to load config.toml: {:#}", e);
+            return Ok(ExitCode::from(2));
+        }
         Err(e) => {
             // Output JSON even on crash so Waybar renders an error icon instead of vanishing
             println!("{}", json!({
@@ -165,30 +423,20 @@ fn main() -> Result<()> {
     };
     
     let cache_path = expand_path(&config.cache_file);
-    // Strategy: Try Live Check -> Fallback to Cache -> Error
-    match run_check(&config.command_string) {
-        Ok(count) => {
-            // Happy Path: Update cache and display fresh data
-            if let Err(e) = save_cache(count, &cache_path) {
-                eprintln!("Warning: Failed to save cache: {}", e);
-            }
-            print_success_json(count);
-        }
-        Err(check_err) => {
-            // Check failed. Attempt recovery via cache.
-            eprintln!("Update check failed: {}", check_err); // For debugging
-            match read_cache(&cache_path) {
-                Ok(cache) => {
-                    print_stale_json(cache.count, &config);
-                }
-                Err(cache_err) => {
-                    // Critical Failure
-                    let combined_err = format!("Check Error: {}\nCache Error: {}", check_err, cache_err);
-                    print_error_json(&config, &combined_err);
-                }
-            }
+    if env::args().any(|a| a == "--mark-news-read") {
+        mark_news_read(&cache_path)?;
+        if let Some(signal_num) = config.bar_signal_num {
+            signal_bar(signal_num);
         }
+        return Ok(ExitCode::SUCCESS);
+    }
+    if count_only_mode {
+        return Ok(ExitCode::from(count_only(&config, &cache_path)));
     }
+    if env::args().any(|a| a == "--watch") {
+        watch(&config, &cache_path);
+    }
+    println!("{}", check_once(&config, &cache_path));
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
@@ -5,7 +5,11 @@
 //! 2. Verifies that necessary binaries (`ghostty`, `yay`, etc.) exist before execution.
 //! 3. Wraps the package manager (`yay`/`pacman`) in a GUI terminal window so the user can see progress and enter `sudo` passwords.
 //! 4. Chains system updates with firmware updates (`fwupdmgr`).
+//!    Optionally takes a Timeshift snapshot first and aborts if it fails.
+//!    Optionally offers a reboot when the kernel or a DKMS driver was upgraded.
 //! 5. Provides desktop notifications on success/failure using `notify-rust`.
+//! 6. Records the whole session to a log file (via `script`, so the terminal output is
+//!    unchanged); failures include its tail in the notification.
 
 use std::fs;
 use std::process::{Command, Stdio};
@@ -13,6 +17,7 @@ use std::path::{Path, PathBuf};
 use anyhow::{anyhow, Context, Result};
 use notify_rust::{Notification, Urgency};
 use serde::Deserialize;
+use common::{expand_path, load_config};
 
 const LOGO: &str = r#"
 "++++++++++
@@ -36,14 +41,6 @@ const LOGO: &str = r#"
 +++++:..............:+++++
 "#;
 
-/// Expands shell-style paths like `~/` to absolute system paths.
-fn expand_path(path: &str) -> PathBuf {
-    if let Some(stripped) = path.strip_prefix("~/")
-        && let Some(home) = dirs::home_dir() {
-            return home.join(stripped);
-        }
-    PathBuf::from(path)
-}
 // 🐧🐧🐧 Config Models 🐧🐧🐧
 
 #[derive(Deserialize, Debug)]
@@ -57,8 +54,27 @@ struct UpdaterConfig {
     icon_success: String,        //Path to success icon
     icon_error: String,          // Path to error icon
     window_title: String,        // Title for the window manager to target rules
+    #[serde(default)]
+    pre_update_snapshot: bool,   // Take a Timeshift snapshot before updating
+    #[serde(default)]
+    reboot_prompt: bool,         // Offer a reboot after kernel/DKMS upgrades
+    #[serde(default = "default_log_file")]
+    log_file: String,            // Full transcript of the last update session
+}
+
+fn default_log_file() -> String {
+    "~/.cache/updater.log".to_string()
 }
 
+/// Lines of the session log included in failure notifications.
+const LOG_TAIL_LINES: usize = 30;
+
+/// Exit code the bash script uses when the pre-update snapshot fails.
+const SNAPSHOT_FAILED_EXIT: i32 = 2;
+
+/// Packages whose upgrade needs a reboot (matched against `pacman -Q` lines).
+const KERNEL_PKG_REGEX: &str = "^(linux(-lts|-zen|-hardened)?|nvidia(-open)?-dkms) ";
+
 #[derive(Deserialize, Debug)]
 struct RepoConfig {
     root: String, // Path to the root of the dotfiles repo 
@@ -71,22 +87,6 @@ struct GlobalConfig {
     repo: Option<RepoConfig>,
 }
 
-/// Loads and parses the TOML configuration file.
-/// Centralizes all settings so recompilation isn't needed for minor changes.
-fn load_config() -> Result<GlobalConfig> {
-    let config_path = dirs::home_dir()
-        .context("Cannot find home dir")?
-        .join(".config/rust-dotfiles/config.toml");
-
-    let config_str = fs::read_to_string(&config_path)
-        .with_context(|| format!("Failed to read config: {}", config_path.display()))?;
-
-    let config: GlobalConfig = toml::from_str(&config_str)
-        .context("Failed to parse config.toml")?;
-
-    Ok(config)
-}
-
 fn resolve_repo_path(repo_cfg: Option<&RepoConfig>) -> Option<PathBuf> {
     if let Some(repo_cfg) = repo_cfg {
         let configured_path = expand_path(&repo_cfg.root);
@@ -134,11 +134,73 @@ fn send_notification(summary: &str, body: &str, icon: &Path, urgency: Urgency) -
     Ok(())
 }
 
+/// Reduces a terminal-recorded line to what was left on screen: the text after the last
+/// carriage return (progress bars redraw that way), without ANSI escape sequences.
+fn plain_line(line: &str) -> String {
+    let visible = line.trim_end_matches('\r').rsplit('\r').next().unwrap_or_default();
+    let mut out = String::new();
+    let mut chars = visible.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // CSI sequences end at the first letter; other escapes are a single character
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Returns the last `n` lines of the session log, or a hint if it can't be read.
+fn log_tail(path: &Path, n: usize) -> String {
+    match fs::read(path) {
+        Ok(log) => {
+            let log = String::from_utf8_lossy(&log);
+            // `script` frames the transcript with "Script started/done on ..." lines
+            let lines: Vec<String> = log
+                .lines()
+                .filter(|l| !l.starts_with("Script started on") && !l.starts_with("Script done on"))
+                .map(plain_line)
+                .filter(|l| !l.trim().is_empty())
+                .collect();
+            lines[lines.len().saturating_sub(n)..].join("\n")
+        }
+        Err(_) => format!("(no log at {})", path.display()),
+    }
+}
+
+/// Critical notification offering to reboot. Blocks until the user picks an action
+/// (or the notification is dismissed).
+fn prompt_reboot(icon: &Path) -> Result<()> {
+    let handle = Notification::new()
+        .summary("Reboot Required")
+        .body("The kernel or a DKMS driver was updated. Reboot to load the new modules.")
+        .icon(icon.to_str().unwrap_or(""))
+        .urgency(Urgency::Critical)
+        .action("reboot", "Reboot Now")
+        .action("later", "Later")
+        .show()
+        .context("Failed to send desktop notification")?;
+
+    handle.wait_for_action(|action| {
+        if action == "reboot" {
+            let _ = Command::new("systemctl").arg("reboot").status();
+        }
+    });
+    Ok(())
+}
+
 // --- Main Execution Flow ---
 
 fn main() -> Result<()> {
     // Load Configuration
-    let config = load_config()?;
+    let config = load_config::<GlobalConfig>()?;
     let global_conf = config.global;
     let updater_conf = config.updater;
     let repo_path = resolve_repo_path(config.repo.as_ref())
@@ -148,6 +210,7 @@ fn main() -> Result<()> {
     // Resolve relative paths
     let icon_error = expand_path(&updater_conf.icon_error);
     let icon_success = expand_path(&updater_conf.icon_success);
+    let log_file = expand_path(&updater_conf.log_file);
     
     // Dependency Verification
     let terminal_cmd = &global_conf.terminal;
@@ -157,14 +220,54 @@ fn main() -> Result<()> {
     if !check_dependency(update_bin) { return Err(anyhow!("Update helper not found: {}", update_bin)); }
     
     let update_cmd_str = updater_conf.update_command.join(" ");
+
+    // Optional rollback point. The update only runs if the snapshot succeeded.
+    let snapshot_step = if updater_conf.pre_update_snapshot {
+        format!(r#"
+        # --- 0. PRE-UPDATE SNAPSHOT ---
+        echo -e "\n📸 Creating Timeshift snapshot..."
+        if ! sudo timeshift --create --comments "pre-update" --yes; then
+            echo -e "\n❌ Snapshot failed, update aborted. Closing in 5s..."
+            sleep 5
+            exit {}
+        fi
+        "#, SNAPSHOT_FAILED_EXIT)
+    } else {
+        String::new()
+    };
+
+    // The script touches this file when a kernel package changed. Empty = check disabled.
+    let reboot_flag = std::env::temp_dir().join(format!("updater-reboot-{}", std::process::id()));
+    let reboot_flag_str = if updater_conf.reboot_prompt {
+        reboot_flag.to_string_lossy().to_string()
+    } else {
+        String::new()
+    };
     
     // --- CONSTRUCT THE BASH SCRIPT ---
     // We use a raw string literal (r#...#) so we can write Bash naturally.
     let bash_script = format!(r#"
+        # Mirror everything to the session log (stdin stays on the terminal for prompts).
+        # On a terminal, re-run this script under `script` so pacman/yay still see a TTY
+        # and keep their progress bars and colour; a plain tee pipe would drop them.
+        LOG_FILE="{}"
+        mkdir -p "$(dirname "$LOG_FILE")"
+        if [ -z "$UPDATER_LOGGED" ]; then
+            export UPDATER_LOGGED=1
+            if [ -t 1 ] && command -v script &> /dev/null; then
+                export UPDATER_SCRIPT="$BASH_EXECUTION_STRING"
+                exec script -qefc 'bash -c "$UPDATER_SCRIPT"' "$LOG_FILE"
+            fi
+            exec > >(tee "$LOG_FILE") 2>&1
+        fi
+
         cat << "EOF"
 {}
 EOF
         echo -e "\n🚀 Starting System Update..."
+        {}
+        KERNEL_REGEX='{}'
+        KERNEL_BEFORE=$(pacman -Q 2>/dev/null | grep -E "$KERNEL_REGEX")
 
         # --- 1. SYSTEM UPDATE ---
         {}
@@ -194,6 +297,16 @@ EOF
             echo -e "\n⚠ System update failed, skipping firmware/scripts."
         fi
 
+        # --- 2b. KERNEL CHECK ---
+        REBOOT_FLAG="{}"
+        if [ -n "$REBOOT_FLAG" ]; then
+            KERNEL_AFTER=$(pacman -Q 2>/dev/null | grep -E "$KERNEL_REGEX")
+            if [ "$KERNEL_BEFORE" != "$KERNEL_AFTER" ]; then
+                echo -e "\n🐧 Kernel or DKMS driver updated. A reboot is recommended."
+                touch "$REBOOT_FLAG"
+            fi
+        fi
+
         # --- 3. SURGICAL REPO SYNC ---
         if [ $sys_exit -eq 0 ]; then
             echo -e "\n\n🦀 Checking for Rust Script Updates..."
@@ -253,9 +366,13 @@ EOF
 
         if [ $sys_exit -ne 0 ]; then exit 1; else exit 0; fi
         "#,
+        log_file.to_string_lossy(),
         LOGO,
+        snapshot_step,
+        KERNEL_PKG_REGEX,
         update_cmd_str,
-        repo_path
+        repo_path,
+        reboot_flag_str
     );  
 
     // Interactive Execution
@@ -271,8 +388,24 @@ EOF
     // Notifications
     if status.success() {
         send_notification("System Update Complete", "All updates applied successfully.", &icon_success, Urgency::Low)?;
+    } else if status.code() == Some(SNAPSHOT_FAILED_EXIT) {
+        let body = format!(
+            "Timeshift could not create a snapshot. No updates were applied.\n\n{}",
+            log_tail(&log_file, LOG_TAIL_LINES)
+        );
+        send_notification("Pre-Update Snapshot Failed", &body, &icon_error, Urgency::Critical)?;
     } else {
-        send_notification("System Update Failed", "The update process encountered an error.", &icon_error, Urgency::Critical)?;
+        let body = format!(
+            "The update process encountered an error. Full log: {}\n\n{}",
+            log_file.display(),
+            log_tail(&log_file, LOG_TAIL_LINES)
+        );
+        send_notification("System Update Failed", &body, &icon_error, Urgency::Critical)?;
+    }
+
+    if reboot_flag.exists() {
+        let _ = fs::remove_file(&reboot_flag);
+        prompt_reboot(&icon_success)?;
     }
     Ok(())
 }
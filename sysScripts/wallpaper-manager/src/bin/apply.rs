@@ -3,13 +3,19 @@
 //! A specialized utility responsible for the side-effects of changing the desktop background.
 //! It abstracts away the differences between Wayland compositors (Hyprland, Sway, Niri)
 //! so the selection tool doesn't need to know the implementation details.
+//! Every applied wallpaper is recorded per monitor so `wp-restore` can bring it back on login.
+//! It also renders a blurred, dimmed copy to `~/.cache/lockscreen-bg.png` for the lock screen.
 
 use anyhow::{Context, Result};
+use common::load_config;
+use image::imageops::FilterType;
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use wallpaper_manager::STATE_FILE;
 
 #[derive(Deserialize, Debug)]
 #[allow(dead_code)]
@@ -17,6 +23,20 @@ struct WallpaperManagerConfig {
     swww_params: Vec<String>,  // Transition effects for swww
     swaybg_cache_file: String, // Where Sway stores its current state
     wallpaper_dir: String,
+    // Gaussian blur sigma for the lock screen copy; 0 turns the copy off
+    #[serde(default = "default_lockscreen_blur")]
+    lockscreen_blur: f32,
+    // Brightness multiplier for the lock screen copy (1.0 = unchanged)
+    #[serde(default = "default_lockscreen_dim")]
+    lockscreen_dim: f32,
+}
+
+fn default_lockscreen_blur() -> f32 {
+    12.0
+}
+
+fn default_lockscreen_dim() -> f32 {
+    0.6
 }
 
 #[derive(Deserialize, Debug)]
@@ -24,22 +44,78 @@ struct GlobalConfig {
     wallpaper_manager: WallpaperManagerConfig,
 }
 
-fn load_config() -> Result<GlobalConfig> {
-    let config_path = dirs::home_dir()
+fn record_state(monitor: &str, wallpaper: &Path) -> Result<()> {
+    let state_path = dirs::home_dir()
         .context("Cannot find home dir")?
-        .join(".config/rust-dotfiles/config.toml");
+        .join(STATE_FILE);
+    // A corrupt state file is simply replaced
+    let mut state: BTreeMap<String, PathBuf> = fs::read_to_string(&state_path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    state.insert(monitor.to_string(), wallpaper.to_path_buf());
+    if let Some(parent) = state_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&state_path, serde_json::to_string_pretty(&state)?)
+        .with_context(|| format!("Failed to write {}", state_path.display()))?;
+    Ok(())
+}
 
-    let config_str = fs::read_to_string(&config_path).with_context(|| {
-        format!(
-            "Failed to read config file from path: {}",
-            config_path.display()
-        )
-    })?;
+// --- Lock Screen Background ---
+const LOCKSCREEN_IMAGE: &str = "lockscreen-bg.png";
+/// Hyprlock-sourceable file defining `$lockscreen_bg`. Its first line names the wallpaper
+/// the image was made from, so applying the same one to several monitors renders once.
+const LOCKSCREEN_CONF: &str = "lockscreen-bg.conf";
+/// Blurring a 4K image is slow and the detail is thrown away anyway, so shrink it first.
+const LOCKSCREEN_MAX_WIDTH: u32 = 1920;
+
+fn write_lockscreen_background(wallpaper: &Path, sigma: f32, dim: f32) -> Result<()> {
+    let cache_dir = dirs::cache_dir().context("Cannot find cache dir")?;
+    let image_path = cache_dir.join(LOCKSCREEN_IMAGE);
+    let conf_path = cache_dir.join(LOCKSCREEN_CONF);
+    let source_line = format!(
+        "# source: {} (blur {}, dim {})",
+        wallpaper.display(),
+        sigma,
+        dim
+    );
+    if image_path.exists()
+        && fs::read_to_string(&conf_path)
+            .is_ok_and(|c| c.lines().next() == Some(source_line.as_str()))
+    {
+        return Ok(());
+    }
 
-    let config: GlobalConfig = toml::from_str(&config_str)
-        .context("Failed to parse config.toml. Check for syntax errors.")?;
+    let img = image::open(wallpaper)
+        .with_context(|| format!("Failed to open {}", wallpaper.display()))?;
+    let img = if img.width() > LOCKSCREEN_MAX_WIDTH {
+        img.resize(LOCKSCREEN_MAX_WIDTH, u32::MAX, FilterType::Triangle)
+    } else {
+        img
+    };
+    let mut blurred = img.fast_blur(sigma).to_rgb8();
+    let dim = dim.clamp(0.0, 1.0);
+    for pixel in blurred.pixels_mut() {
+        for channel in pixel.0.iter_mut() {
+            *channel = (f32::from(*channel) * dim).round() as u8;
+        }
+    }
 
-    Ok(config)
+    fs::create_dir_all(&cache_dir)?;
+    blurred
+        .save(&image_path)
+        .with_context(|| format!("Failed to write {}", image_path.display()))?;
+    fs::write(
+        &conf_path,
+        format!(
+            "{}\n$lockscreen_bg = {}\n",
+            source_line,
+            image_path.display()
+        ),
+    )
+    .with_context(|| format!("Failed to write {}", conf_path.display()))?;
+    Ok(())
 }
 
 // Helper to ensure competing wallpaper daemons are killed before starting a new one.
@@ -108,7 +184,7 @@ fn apply_sway_wallpaper(selected_file: &Path, monitor: &str, cache_filename: &st
 }
 
 fn main() -> Result<()> {
-    let global_config = load_config()?;
+    let global_config: GlobalConfig = load_config()?;
     let config = global_config.wallpaper_manager;
     // Parse CLI arguments passed by `wp-select`
     let args: Vec<String> = env::args().collect();
@@ -130,5 +206,22 @@ fn main() -> Result<()> {
         _ => anyhow::bail!("Compositor argument '{}' is not recognized.", compositor),
     }
 
+    // "*" (all outputs) isn't a monitor; it comes from wp-restore's legacy fallback.
+    if monitor != "*"
+        && let Err(e) = record_state(monitor, &wallpaper_path)
+    {
+        eprintln!("Warning: Failed to save wallpaper state: {}", e);
+    }
+
+    if config.lockscreen_blur > 0.0
+        && let Err(e) = write_lockscreen_background(
+            &wallpaper_path,
+            config.lockscreen_blur,
+            config.lockscreen_dim,
+        )
+    {
+        eprintln!("Warning: Failed to render lock screen background: {}", e);
+    }
+
     Ok(())
 }
@@ -3,28 +3,28 @@
 //! A background service that monitors the wallpaper directory.
 //! 1. Scans for new images recursively.
 //! 2. Generates thumbnails in parallel (using Rayon) to offload CPU work.
-//! 3. Maintains a JSON cache for the selection tool to read instantly.
-//! 4. Uses `notify` to watch for filesystem changes in real-time.
+//! 3. Maintains a JSON cache for the selection tool to read instantly (including each
+//!    file's mtime, so wp-select can sort by date added).
+//! 4. Uses `notify` to watch for filesystem changes in real-time, batching bursts of
+//!    events (e.g. copying in a folder of images) into a single rescan.
+//! 5. Optionally rotates the wallpaper every `rotation_secs` (slideshow). Each rotation
+//!    re-reads the cache, so images picked up by the watcher join the pool.
 
 use anyhow::{Context, Result};
+use common::{expand_path, load_config};
 use image::imageops::FilterType;
 use notify::{RecursiveMode, Watcher};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::sync::mpsc::channel;
+use std::time::{Duration, UNIX_EPOCH};
 use walkdir::WalkDir;
-
-fn expand_path(path: &str) -> PathBuf {
-    if let Some(stripped) = path.strip_prefix("~/")
-        && let Some(home) = dirs::home_dir()
-    {
-        return home.join(stripped);
-    }
-    PathBuf::from(path)
-}
+use wallpaper_manager::{get_compositor, get_monitor_list, load_state};
 
 #[derive(Deserialize, Debug)]
 #[allow(dead_code)]
@@ -35,42 +35,95 @@ struct WallpaperManagerConfig {
     cache_file: String,
     rofi_config_path: String,
     rofi_theme_override: String,
+    #[serde(default = "default_thumb_width")]
+    thumb_width: u32,
+    #[serde(default)]
+    thumb_filter: ThumbFilter,
+    // Slideshow interval; unset or 0 disables rotation
+    #[serde(default)]
+    rotation_secs: Option<u64>,
+    #[serde(default)]
+    rotation_order: RotationOrder,
 }
 
-#[derive(Deserialize, Debug)]
-struct GlobalConfig {
-    wallpaper_manager: WallpaperManagerConfig,
+/// How the slideshow picks the next wallpaper.
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+enum RotationOrder {
+    #[default]
+    Random,
+    Sequential, // Alphabetical by path, wrapping around
 }
-fn load_config() -> Result<GlobalConfig> {
-    let config_path = dirs::home_dir()
-        .context("Cannot find home dir")?
-        .join(".config/rust-dotfiles/config.toml");
 
-    let config_str = fs::read_to_string(&config_path).with_context(|| {
-        format!(
-            "Failed to read config file from path: {}",
-            config_path.display()
-        )
-    })?;
+fn default_thumb_width() -> u32 {
+    500
+}
+
+/// Resize filter for thumbnails, from fastest/blockiest to slowest/smoothest.
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+enum ThumbFilter {
+    #[default]
+    Nearest,
+    Triangle,
+    Lanczos3,
+}
 
-    let config: GlobalConfig = toml::from_str(&config_str)
-        .context("Failed to parse config.toml. Check for syntax errors.")?;
+impl ThumbFilter {
+    fn filter_type(self) -> FilterType {
+        match self {
+            ThumbFilter::Nearest => FilterType::Nearest,
+            ThumbFilter::Triangle => FilterType::Triangle,
+            ThumbFilter::Lanczos3 => FilterType::Lanczos3,
+        }
+    }
 
-    Ok(config)
+    fn name(self) -> &'static str {
+        match self {
+            ThumbFilter::Nearest => "nearest",
+            ThumbFilter::Triangle => "triangle",
+            ThumbFilter::Lanczos3 => "lanczos3",
+        }
+    }
+}
+
+/// How thumbnails are generated. Both values are part of the thumbnail file name,
+/// so changing either regenerates them (and garbage collection drops the old ones).
+#[derive(Debug, Clone, Copy)]
+struct ThumbSettings {
+    width: u32,
+    filter: ThumbFilter,
+}
+
+#[derive(Deserialize, Debug)]
+struct GlobalConfig {
+    wallpaper_manager: WallpaperManagerConfig,
 }
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct Wallpaper {
     name: String,
     path: PathBuf,
     thumb_path: PathBuf,
+    #[serde(default)]
+    modified: u64, // File mtime (Unix secs), for wp-select's date sort
 }
-const THUMB_WIDTH: u32 = 500;
+/// How long the directory must stay quiet before a batch of events triggers a rescan.
+const DEBOUNCE: Duration = Duration::from_millis(500);
 
 /// Generates a thumbnail for a given image if it doesn't exist.
 /// Returns the path to the thumbnail.
-fn ensure_thumbnail(original_path: &Path, thumb_dir: &Path) -> Option<PathBuf> {
-    let file_name = original_path.file_name()?;
-    let thumb_path = thumb_dir.join(file_name);
+fn ensure_thumbnail(
+    original_path: &Path,
+    thumb_dir: &Path,
+    settings: ThumbSettings,
+) -> Option<PathBuf> {
+    let file_name = original_path.file_name()?.to_string_lossy();
+    let thumb_path = thumb_dir.join(format!(
+        "{}-{}-{}",
+        settings.width,
+        settings.filter.name(),
+        file_name
+    ));
     // Cache Hit: If thumbnail exists, skip processing to save CPU/Battery.
     if thumb_path.exists() {
         return Some(thumb_path);
@@ -81,8 +134,8 @@ fn ensure_thumbnail(original_path: &Path, thumb_dir: &Path) -> Option<PathBuf> {
         Err(_) => return None, // Skip unreadable/corrupt files
     };
     // Resize using Nearest Neighbor for speed, or Lanczos3 for quality.
-    // Nearest is chosen here for performance on large directories.
-    let thumb = img.resize(THUMB_WIDTH, u32::MAX, FilterType::Nearest);
+    // Nearest is the default for performance on large directories.
+    let thumb = img.resize(settings.width, u32::MAX, settings.filter.filter_type());
     if let Err(e) = thumb.save(&thumb_path) {
         eprintln!("Failed to save thumb for {:?}: {}", original_path, e);
         return None;
@@ -94,7 +147,11 @@ fn ensure_thumbnail(original_path: &Path, thumb_dir: &Path) -> Option<PathBuf> {
 /// 2. Filters video files.
 /// 3. Generates thumbnails in parallel.
 /// 4. Writes the master JSON index.
-fn scan_and_update_cache(wall_dir: &Path, cache_file: &Path) -> Result<()> {
+fn scan_and_update_cache(
+    wall_dir: &Path,
+    cache_file: &Path,
+    settings: ThumbSettings,
+) -> Result<()> {
     let home = dirs::home_dir().context("Failed to get $HOME")?;
     let thumb_dir = home.join(".cache/wallpaper_thumbs");
     fs::create_dir_all(&thumb_dir)?;
@@ -118,11 +175,17 @@ fn scan_and_update_cache(wall_dir: &Path, cache_file: &Path) -> Result<()> {
                     return None;
                 }
             }
-            let thumb = ensure_thumbnail(path, &thumb_dir)?;
+            let thumb = ensure_thumbnail(path, &thumb_dir, settings)?;
+            let modified = fs::metadata(path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_secs());
             Some(Wallpaper {
                 name: path.file_stem()?.to_string_lossy().to_string(),
                 path: path.clone(),
                 thumb_path: thumb,
+                modified,
             })
         })
         .collect();
@@ -143,18 +206,122 @@ fn scan_and_update_cache(wall_dir: &Path, cache_file: &Path) -> Result<()> {
     println!("Cache update. Found {} wallpapers.", good_thumbs.len());
     Ok(())
 }
+// --- Slideshow ---
+/// Chooses the next slide. `current` is what the first monitor shows now.
+/// Sequential mode continues after it (or starts over if it was removed);
+/// random mode avoids repeating it whenever there is a choice.
+fn next_wallpaper(
+    wallpapers: &[Wallpaper],
+    current: Option<&PathBuf>,
+    order: RotationOrder,
+) -> Option<PathBuf> {
+    let mut paths: Vec<&PathBuf> = wallpapers.iter().map(|w| &w.path).collect();
+    if paths.is_empty() {
+        return None;
+    }
+    match order {
+        RotationOrder::Sequential => {
+            paths.sort();
+            let next = current
+                .and_then(|c| paths.iter().position(|p| *p == c))
+                .map_or(0, |i| (i + 1) % paths.len());
+            Some(paths[next].clone())
+        }
+        RotationOrder::Random => {
+            if paths.len() > 1
+                && let Some(current) = current
+            {
+                paths.retain(|p| *p != current);
+            }
+            Some(paths[fastrand::usize(..paths.len())].clone())
+        }
+    }
+}
+
+/// One slideshow step: same wallpaper on every monitor, applied through `wp-apply`.
+fn rotate(cache_file: &Path, order: RotationOrder) -> Result<()> {
+    let compositor = get_compositor();
+    let monitors = get_monitor_list(&compositor)?;
+    let wallpapers: Vec<Wallpaper> = serde_json::from_str(
+        &fs::read_to_string(cache_file).context("Failed to read wallpaper cache")?,
+    )?;
+    let state = load_state();
+    let current = monitors.first().and_then(|m| state.get(m));
+    let Some(next) = next_wallpaper(&wallpapers, current, order) else {
+        return Ok(()); // Empty cache: nothing to rotate yet
+    };
+    let apply_path = env::current_exe()?.parent().unwrap().join("wp-apply");
+    for monitor in &monitors {
+        let status = Command::new(&apply_path)
+            .arg(&next)
+            .arg(&compositor)
+            .arg(monitor)
+            .status()
+            .context("Failed to run 'wp-apply' command")?;
+        if !status.success() {
+            eprintln!("wp-apply failed for {}", monitor);
+        }
+    }
+    Ok(())
+}
+
+/// Runs the slideshow on its own thread so the watcher loop below stays untouched.
+fn start_slideshow(cache_file: PathBuf, interval: Duration, order: RotationOrder) {
+    println!(
+        "Slideshow enabled: rotating every {}s ({:?}).",
+        interval.as_secs(),
+        order
+    );
+    std::thread::spawn(move || {
+        loop {
+            std::thread::sleep(interval);
+            if let Err(e) = rotate(&cache_file, order) {
+                eprintln!("Slideshow rotation failed: {}", e);
+            }
+        }
+    });
+}
+
+/// FILTER: Ignore access events, metadata changes, or other noise.
+/// We only care if a file was created, modified (content), or removed.
+fn is_relevant(res: notify::Result<notify::Event>) -> bool {
+    use notify::EventKind;
+    match res {
+        Ok(event) => matches!(
+            event.kind,
+            EventKind::Create(_)
+                | EventKind::Modify(notify::event::ModifyKind::Data(_))
+                | EventKind::Remove(_)
+        ),
+        Err(e) => {
+            eprintln!("Watch error {:?}", e);
+            false
+        }
+    }
+}
 fn main() -> Result<()> {
-    let global_config = load_config()?;
+    let global_config: GlobalConfig = load_config()?;
     let config = global_config.wallpaper_manager;
     let wall_dir = expand_path(&config.wallpaper_dir);
     let cache_file = expand_path(&config.cache_file);
+    let settings = ThumbSettings {
+        width: config.thumb_width,
+        filter: config.thumb_filter,
+    };
     if !wall_dir.exists() {
         anyhow::bail!("Wallpaper directory does not exist: {:?}", wall_dir);
     }
     //Initial scan on startup
-    if let Err(e) = scan_and_update_cache(&wall_dir, &cache_file) {
+    if let Err(e) = scan_and_update_cache(&wall_dir, &cache_file, settings) {
         eprintln!("Initial scan failed: {}", e);
     }
+    if let Some(secs) = config.rotation_secs.filter(|s| *s > 0) {
+        start_slideshow(
+            cache_file.clone(),
+            Duration::from_secs(secs),
+            config.rotation_order,
+        );
+    }
     // Real-time Filesystem Watcher
     // Uses inotify (Linux) to trigger updates immediately when files are added/removed.
     let (tx, rx) = channel();
@@ -162,29 +329,25 @@ fn main() -> Result<()> {
     watcher.watch(&wall_dir, RecursiveMode::Recursive)?;
     println!("Daemon started. Watching {:?}...", wall_dir);
     // Event Loop
-    for res in rx {
-        match res {
-            Ok(event) => {
-                // FILTER: Ignore access events, metadata changes, or other noise.
-                // We only care if a file was created, modified (content), or removed.
-                use notify::EventKind;
-                match event.kind {
-                    EventKind::Create(_)
-                    | EventKind::Modify(notify::event::ModifyKind::Data(_))
-                    | EventKind::Remove(_) => {
-                        println!(
-                            "Relevant change detected ({:?}). Refreshing cache...",
-                            event.kind
-                        );
-                        // Debounce? (Optional optimization, but this filter usually fixes the loop)
-                        if let Err(e) = scan_and_update_cache(&wall_dir, &cache_file) {
-                            eprintln!("Error updating cache: {}", e);
-                        }
-                    }
-                    _ => {} // Ignore everything else (Access, Chmod, etc.)
-                }
+    // Block until a relevant event arrives, then keep draining the channel until it has
+    // been quiet for DEBOUNCE, so dropping 50 wallpapers in costs one rescan instead of 50.
+    for res in &rx {
+        if !is_relevant(res) {
+            continue; // Ignore everything else (Access, Chmod, etc.)
+        }
+        let mut changes = 1;
+        // Stops on Timeout (quiet) or Disconnected (watcher gone)
+        while let Ok(res) = rx.recv_timeout(DEBOUNCE) {
+            if is_relevant(res) {
+                changes += 1;
             }
-            Err(e) => eprintln!("Watch error {:?}", e),
+        }
+        println!(
+            "{} relevant change(s) detected. Refreshing cache...",
+            changes
+        );
+        if let Err(e) = scan_and_update_cache(&wall_dir, &cache_file, settings) {
+            eprintln!("Error updating cache: {}", e);
         }
     }
     Ok(())
@@ -0,0 +1,143 @@
+//! Random Wallpaper (wp-random)
+//!
+//! A headless counterpart to `wp-select` for keybinds and systemd timers.
+//! 1. Reads the pre-generated cache (from wp-daemon).
+//! 2. Picks a random wallpaper: one for every monitor, a different one per monitor
+//!    (`--per-monitor`), or just for one (`--monitor DP-1`).
+//! 3. With `--exclude-current`, never picks what a monitor is already showing
+//!    (as recorded by `wp-apply`).
+//! 4. Delegates the final action to `wp-apply`.
+
+use anyhow::{Context, Result};
+use common::{expand_path, load_config};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use wallpaper_manager::{get_compositor, get_monitor_list, load_state};
+
+#[derive(Deserialize, Debug)]
+struct WallpaperManagerConfig {
+    cache_file: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct GlobalConfig {
+    wallpaper_manager: WallpaperManagerConfig,
+}
+
+// --- Cache Structures ---
+#[derive(Deserialize, Debug, Clone)]
+struct Wallpaper {
+    path: PathBuf,
+}
+
+/// Command line options.
+struct Args {
+    monitor: Option<String>, // Only this monitor
+    per_monitor: bool,       // A different pick for each monitor
+    exclude_current: bool,   // Never re-pick what's already showing
+}
+
+fn parse_args() -> Result<Args> {
+    let mut args = Args {
+        monitor: None,
+        per_monitor: false,
+        exclude_current: false,
+    };
+    let mut iter = env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--monitor" => {
+                args.monitor = Some(iter.next().context("--monitor needs a monitor name")?)
+            }
+            "--per-monitor" => args.per_monitor = true,
+            "--exclude-current" => args.exclude_current = true,
+            other => anyhow::bail!(
+                "Unknown argument '{}'. Usage: wp-random [--monitor NAME | --per-monitor] [--exclude-current]",
+                other
+            ),
+        }
+    }
+    Ok(args)
+}
+
+/// Picks a random wallpaper that isn't in `exclude`. `wallpapers` must not be empty.
+/// If everything is excluded (e.g. a single wallpaper), the exclusion is dropped.
+fn pick<'a>(wallpapers: &'a [Wallpaper], exclude: &[&PathBuf]) -> &'a Wallpaper {
+    let candidates: Vec<&Wallpaper> = wallpapers
+        .iter()
+        .filter(|w| !exclude.contains(&&w.path))
+        .collect();
+    if candidates.is_empty() {
+        return &wallpapers[fastrand::usize(..wallpapers.len())];
+    }
+    candidates[fastrand::usize(..candidates.len())]
+}
+
+fn apply(wallpaper: &Wallpaper, compositor: &str, monitor: &str) -> Result<()> {
+    let apply_path = env::current_exe()?.parent().unwrap().join("wp-apply");
+    let status = Command::new(apply_path)
+        .arg(&wallpaper.path)
+        .arg(compositor)
+        .arg(monitor)
+        .status()
+        .context("Failed to run 'wp-apply' command")?;
+    if !status.success() {
+        anyhow::bail!("wp-apply failed for {}", monitor);
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let args = parse_args()?;
+    let config = load_config::<GlobalConfig>()?.wallpaper_manager;
+    // Environment Discovery
+    let compositor = get_compositor();
+    if compositor == "unknown" {
+        anyhow::bail!("No supported compositor running.");
+    }
+
+    // Load Cache (Fast Path)
+    let cache_file = expand_path(&config.cache_file);
+    if !cache_file.exists() {
+        anyhow::bail!("Wallpaper cache missing! Please run 'wp-daemon' first.");
+    }
+    let wallpapers: Vec<Wallpaper> = serde_json::from_str(&fs::read_to_string(&cache_file)?)?;
+    if wallpapers.is_empty() {
+        anyhow::bail!("The wallpaper cache is empty.");
+    }
+
+    // Hardware Discovery
+    let monitors = match args.monitor {
+        Some(monitor) => vec![monitor],
+        None => get_monitor_list(&compositor)?,
+    };
+    if monitors.is_empty() {
+        anyhow::bail!("Could not detect any active monitors.");
+    }
+
+    let state = if args.exclude_current {
+        load_state()
+    } else {
+        BTreeMap::new()
+    };
+    if args.per_monitor {
+        for monitor in &monitors {
+            let exclude: Vec<&PathBuf> = state.get(monitor).into_iter().collect();
+            let wallpaper = pick(&wallpapers, &exclude);
+            apply(wallpaper, &compositor, monitor)?;
+        }
+    } else {
+        // One wallpaper everywhere: skip anything currently on any of these monitors
+        let exclude: Vec<&PathBuf> = monitors.iter().filter_map(|m| state.get(m)).collect();
+        let wallpaper = pick(&wallpapers, &exclude);
+        for monitor in &monitors {
+            apply(wallpaper, &compositor, monitor)?;
+        }
+    }
+
+    Ok(())
+}
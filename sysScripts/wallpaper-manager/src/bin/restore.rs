@@ -0,0 +1,86 @@
+//! Wallpaper Restorer (wp-restore)
+//!
+//! Run once at login. Reads the per-monitor state that `wp-apply` records
+//! (`~/.config/rust-dotfiles/wallpaper_state.json`) and re-applies each monitor's last
+//! wallpaper through `wp-apply`, so multi-monitor setups don't come back black after a reboot.
+//!
+//! Without a state file (setups from before it existed) Sway falls back to the single
+//! `swaybg_cache_file` wallpaper on every output, like the old startup line did.
+
+use anyhow::{Context, Result};
+use common::load_config;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use wallpaper_manager::{get_compositor, load_state};
+
+#[derive(Deserialize, Debug)]
+struct WallpaperManagerConfig {
+    swaybg_cache_file: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct GlobalConfig {
+    wallpaper_manager: WallpaperManagerConfig,
+}
+
+/// The pre-state-file behaviour: Sway's single cached wallpaper on all outputs.
+fn legacy_sway_state() -> Result<BTreeMap<String, PathBuf>> {
+    let config = load_config::<GlobalConfig>()?.wallpaper_manager;
+    let cache_path = dirs::cache_dir()
+        .context("Cannot find cache dir")?
+        .join(&config.swaybg_cache_file);
+    let wallpaper = fs::read_to_string(&cache_path).unwrap_or_default();
+    let wallpaper = wallpaper.trim();
+    if wallpaper.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    Ok(BTreeMap::from([(
+        "*".to_string(),
+        PathBuf::from(wallpaper),
+    )]))
+}
+
+fn main() -> Result<()> {
+    let compositor = get_compositor();
+    if compositor == "unknown" {
+        anyhow::bail!("No supported compositor running.");
+    }
+
+    let mut state = load_state();
+    if state.is_empty() && compositor == "sway" {
+        state = legacy_sway_state()?;
+    }
+    if state.is_empty() {
+        println!("No saved wallpapers to restore.");
+        return Ok(());
+    }
+
+    // Delegate to the sibling `wp-apply`, exactly like `wp-select` does.
+    let apply_path = env::current_exe()?.parent().unwrap().join("wp-apply");
+    for (monitor, wallpaper) in state {
+        // Wallpapers deleted since they were applied are skipped, not fatal
+        if !wallpaper.exists() {
+            eprintln!(
+                "Skipping {}: {} no longer exists",
+                monitor,
+                wallpaper.display()
+            );
+            continue;
+        }
+        let status = Command::new(&apply_path)
+            .arg(&wallpaper)
+            .arg(&compositor)
+            .arg(&monitor)
+            .status()
+            .context("Failed to run 'wp-apply' command")?;
+        if !status.success() {
+            eprintln!("wp-apply failed for {}", monitor);
+        }
+    }
+
+    Ok(())
+}
@@ -5,24 +5,19 @@
 //! 2. Queries active monitors dynamically.
 //! 3. Reads the pre-generated cache (from wp-daemon) for instant startup.
 //! 4. Uses `rofi` as a GUI frontend to display thumbnails and filter results.
+//!    Ctrl+S toggles name/newest-first sorting, Ctrl+T shows only images added this week.
 //! 5. Delegates the final action to `wp-apply`.
 
 use anyhow::{Context, Result, anyhow};
+use common::{expand_path, load_config};
 use serde::Deserialize;
 use std::env;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-
-fn expand_path(path: &str) -> PathBuf {
-    if let Some(stripped) = path.strip_prefix("~/")
-        && let Some(home) = dirs::home_dir()
-    {
-        return home.join(stripped);
-    }
-    PathBuf::from(path)
-}
+use std::time::{SystemTime, UNIX_EPOCH};
+use wallpaper_manager::{get_compositor, get_monitor_list};
 
 #[derive(Deserialize, Debug)]
 #[allow(dead_code)]
@@ -40,103 +35,89 @@ struct GlobalConfig {
     wallpaper_manager: WallpaperManagerConfig,
 }
 
-fn load_config() -> Result<GlobalConfig> {
-    let config_path = dirs::home_dir()
-        .context("Cannot find home dir")?
-        .join(".config/rust-dotfiles/config.toml");
-
-    let config_str = fs::read_to_string(&config_path).with_context(|| {
-        format!(
-            "Failed to read config file from path: {}",
-            config_path.display()
-        )
-    })?;
-
-    let config: GlobalConfig = toml::from_str(&config_str)
-        .context("Failed to parse config.toml. Check for syntax errors.")?;
-
-    Ok(config)
-}
-// --- IPC Structures ---
-// These match the JSON output of and swaymsg
-#[derive(Deserialize, Debug)]
-struct SwayMonitor {
-    name: String,
-    active: bool,
-}
+// --- Cache Structures ---
 #[derive(Deserialize, Debug, Clone)]
 struct Wallpaper {
     name: String,
     path: PathBuf,
     thumb_path: PathBuf,
+    #[serde(default)]
+    modified: u64, // File mtime (Unix secs); 0 in caches from before it was recorded
 }
-/// Heuristic to determine the running Window Manager.
-/// Checks IPC sockets and Environment variables.
-fn get_compositor() -> String {
-    if env::var("NIRI_SOCKET").is_ok() {
-        return "niri".to_string();
-    }
-    if env::var("SWAYSOCK").is_ok() {
-        return "sway".to_string();
+
+/// "Added this week" window for Ctrl+T.
+const RECENT_SECS: u64 = 7 * 24 * 60 * 60;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Sort {
+    Name,
+    Newest,
+}
+
+impl Sort {
+    fn toggle(self) -> Self {
+        match self {
+            Sort::Name => Sort::Newest,
+            Sort::Newest => Sort::Name,
+        }
     }
 
-    if let Ok(desktop) = env::var("XDG_CURRENT_DESKTOP") {
-        let d = desktop.to_lowercase();
-        if d.contains("niri") {
-            return "niri".to_string();
+    fn label(self) -> &'static str {
+        match self {
+            Sort::Name => "Name",
+            Sort::Newest => "Newest first",
         }
-        if d.contains("sway") {
-            return "sway".to_string();
+    }
+
+    fn apply(self, wallpapers: &mut [Wallpaper]) {
+        match self {
+            Sort::Name => wallpapers.sort_by_key(|wp| wp.name.to_lowercase()),
+            Sort::Newest => wallpapers.sort_by_key(|wp| std::cmp::Reverse(wp.modified)),
         }
     }
-    "unknown".to_string()
 }
-/// Queries the compositor for a list of connected screens.
-/// This allows per-monitor wallpaper setting.
-fn get_monitor_list(compositor: &str) -> Result<Vec<String>> {
-    let output;
-    match compositor {
-        "sway" => {
-            // Parse `swaymsg -t get_outputs`
-            output = Command::new("swaymsg")
-                .arg("-t")
-                .arg("get_outputs")
-                .output()?;
-            if !output.status.success() {
-                anyhow::bail!("swaymsg command failed");
-            }
-            let monitors: Vec<SwayMonitor> =
-                serde_json::from_slice(&output.stdout).context("Failed to parse swaymsg JSON")?;
-            Ok(monitors
-                .into_iter()
-                .filter(|m| m.active)
-                .map(|m| m.name)
-                .collect())
+/// The wallpaper menu: like `ask_rofi`, plus the sort/filter keybinds and a status line.
+/// Returns rofi's exit code (0 = Enter, 1 = Esc, 10 = Ctrl+S, 11 = Ctrl+T) and the selection.
+fn ask_wallpaper(
+    items: &[String],
+    conf: &Path,
+    theme: &str,
+    sort: Sort,
+    recent_only: bool,
+) -> Result<(i32, String)> {
+    let message = format!(
+        "<b>Sort:</b> {} (Ctrl+S)  |  <b>Ctrl+T:</b> {}",
+        sort.label(),
+        if recent_only {
+            "Show all"
+        } else {
+            "Added this week"
         }
-        "niri" => {
-            // Niri uses awww-daemon as its "state of truth" for monitors context
-            output = Command::new("awww")
-                .arg("query")
-                .arg("--namespace")
-                .arg("niri")
-                .output()?;
-            if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                anyhow::bail!("awww query failed: {}", stderr);
-            }
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let monitors: Vec<String> = stdout
-                .lines()
-                .filter_map(|line| {
-                    let parts: Vec<&str> = line.split(':').collect();
-                    parts.get(1).map(|s| s.trim().to_string())
-                })
-                .collect();
-            Ok(monitors)
-        }
-        _ => Err(anyhow!("Unknown compositor for monitor detection")),
+    );
+    let mut child = Command::new("rofi")
+        .args(["-dmenu", "-i", "-p", "Select Wallpaper🐧", "-markup-rows"])
+        .arg("-config")
+        .arg(conf)
+        .arg("-theme-str")
+        .arg(theme)
+        .arg("-mesg")
+        .arg(message)
+        .arg("-kb-custom-1")
+        .arg("Control+s") // Exit Code 10
+        .arg("-kb-custom-2")
+        .arg("Control+t") // Exit Code 11
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .context("Failed to spawn rofi")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(items.join("\n").as_bytes())?;
     }
+    let output = child.wait_with_output()?;
+    let code = output.status.code().unwrap_or(1);
+    Ok((code, String::from_utf8(output.stdout)?.trim().to_string()))
 }
+
 /// Wraps the `rofi` command line interface.
 /// Pipes the list of items into rofi's STDIN and captures the selection from STDOUT.
 fn ask_rofi(prompt: &str, items: Vec<String>, config: Option<(&Path, &str)>) -> Result<String> {
@@ -164,7 +145,7 @@ fn ask_rofi(prompt: &str, items: Vec<String>, config: Option<(&Path, &str)>) ->
 }
 
 fn main() -> Result<()> {
-    let global_config = load_config()?;
+    let global_config: GlobalConfig = load_config()?;
     let config = global_config.wallpaper_manager;
     // Environment Discovery
     let compositor = get_compositor();
@@ -189,20 +170,38 @@ fn main() -> Result<()> {
 
     let json_str = fs::read_to_string(&cache_file)?;
     let mut wallpapers: Vec<Wallpaper> = serde_json::from_str(&json_str)?;
-    wallpapers.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-    // Build Rofi Menu with Icons
-    // Rofi supports icons via the `\0icon\x1f` delimiter syntax.
-    let rofi_items: Vec<String> = wallpapers
-        .iter()
-        .map(|wp| format!("{}\0icon\x1f{}", wp.name, wp.thumb_path.to_string_lossy()))
-        .collect();
     let rofi_conf_path = expand_path(&config.rofi_config_path);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+
     // User Interaction (Wallpaper Selection)
-    let selection_name = ask_rofi(
-        "Select Wallpaper🐧",
-        rofi_items,
-        Some((&rofi_conf_path, &config.rofi_theme_override)),
-    )?;
+    // Ctrl+S / Ctrl+T re-open the menu with the new sort or filter.
+    let mut sort = Sort::Name;
+    let mut recent_only = false;
+    let selection_name = loop {
+        sort.apply(&mut wallpapers);
+        // Build Rofi Menu with Icons
+        // Rofi supports icons via the `\0icon\x1f` delimiter syntax.
+        let rofi_items: Vec<String> = wallpapers
+            .iter()
+            .filter(|wp| !recent_only || now.saturating_sub(wp.modified) < RECENT_SECS)
+            .map(|wp| format!("{}\0icon\x1f{}", wp.name, wp.thumb_path.to_string_lossy()))
+            .collect();
+        let (code, selection) = ask_wallpaper(
+            &rofi_items,
+            &rofi_conf_path,
+            &config.rofi_theme_override,
+            sort,
+            recent_only,
+        )?;
+        match code {
+            0 if !selection.is_empty() => break selection,
+            10 => sort = sort.toggle(),
+            11 => recent_only = !recent_only,
+            _ => anyhow::bail!("Rofi was cancelled"),
+        }
+    };
     // Execution
     // Determine the absolute path of the sibling binary `wp-apply` and execute it.
     let selected_wp = wallpapers
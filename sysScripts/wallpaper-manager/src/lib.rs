@@ -0,0 +1,95 @@
+//! Wallpaper Manager (shared library)
+//!
+//! Helpers used by more than one of the `wp-*` binaries: compositor and monitor
+//! discovery, and the per-monitor state file that `wp-apply` writes.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Per-monitor record of the last applied wallpaper (`monitor -> path`), relative to `$HOME`.
+pub const STATE_FILE: &str = ".config/rust-dotfiles/wallpaper_state.json";
+
+// --- IPC Structures ---
+// These match the JSON output of swaymsg
+#[derive(Deserialize, Debug)]
+struct SwayMonitor {
+    name: String,
+    active: bool,
+}
+
+/// Heuristic to determine the running Window Manager.
+/// Checks IPC sockets and Environment variables.
+pub fn get_compositor() -> String {
+    if env::var("NIRI_SOCKET").is_ok() {
+        return "niri".to_string();
+    }
+    if env::var("SWAYSOCK").is_ok() {
+        return "sway".to_string();
+    }
+
+    if let Ok(desktop) = env::var("XDG_CURRENT_DESKTOP") {
+        let d = desktop.to_lowercase();
+        if d.contains("niri") {
+            return "niri".to_string();
+        }
+        if d.contains("sway") {
+            return "sway".to_string();
+        }
+    }
+    "unknown".to_string()
+}
+
+/// Queries the compositor for a list of connected screens.
+pub fn get_monitor_list(compositor: &str) -> Result<Vec<String>> {
+    match compositor {
+        "sway" => {
+            // Parse `swaymsg -t get_outputs`
+            let output = Command::new("swaymsg")
+                .arg("-t")
+                .arg("get_outputs")
+                .output()?;
+            if !output.status.success() {
+                anyhow::bail!("swaymsg command failed");
+            }
+            let monitors: Vec<SwayMonitor> =
+                serde_json::from_slice(&output.stdout).context("Failed to parse swaymsg JSON")?;
+            Ok(monitors
+                .into_iter()
+                .filter(|m| m.active)
+                .map(|m| m.name)
+                .collect())
+        }
+        "niri" => {
+            // Niri uses awww-daemon as its "state of truth" for monitors context
+            let output = Command::new("awww")
+                .arg("query")
+                .arg("--namespace")
+                .arg("niri")
+                .output()?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                anyhow::bail!("awww query failed: {}", stderr);
+            }
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            Ok(stdout
+                .lines()
+                .filter_map(|line| line.split(':').nth(1).map(|s| s.trim().to_string()))
+                .collect())
+        }
+        _ => anyhow::bail!("Unknown compositor for monitor detection"),
+    }
+}
+
+/// What each monitor is showing, as recorded by `wp-apply`.
+/// Missing or unreadable state yields an empty map.
+pub fn load_state() -> BTreeMap<String, PathBuf> {
+    dirs::home_dir()
+        .and_then(|h| fs::read_to_string(h.join(STATE_FILE)).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
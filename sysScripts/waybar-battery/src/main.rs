@@ -0,0 +1,329 @@
+//! Waybar Battery Module (waybar-battery)
+//!
+//! Reads `/sys/class/power_supply/BAT*` and prints battery state as Waybar JSON.
+//!
+//! Design Priorities:
+//! 1. **Multi-Battery:** Laptops with two packs (e.g. ThinkPads) are summed into a single
+//!    percentage weighted by capacity (in energy), not averaged.
+//! 2. **Estimates:** Time-to-empty/full comes from the kernel's power/current readings.
+//! 3. **Visual Feedback:** JSON classes ("charging", "full", "discharging", "low", "critical")
+//!    allow CSS styling in Waybar.
+//! 4. **Alerts:** Optional low/critical notifications, fired once per threshold crossing.
+//!    Waybar re-runs us every interval, so the last alert is persisted in a small cache file.
+
+use anyhow::{Context, Result};
+use common::{expand_path, load_config};
+use notify_rust::{Notification, Urgency};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+
+const POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
+
+// Nerd Font battery glyphs, empty -> full (10% steps).
+const ICONS: &[&str] = &["󰂎", "󰁺", "󰁻", "󰁼", "󰁽", "󰁾", "󰁿", "󰂀", "󰂁", "󰂂", "󰁹"];
+const ICON_CHARGING: &str = "󰂄";
+
+// --- Config Models ---
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct BatteryConfig {
+    low_threshold: u8,      // Percentage at which the "low" class (and alert) kicks in
+    critical_threshold: u8, // Percentage at which the "critical" class (and alert) kicks in
+    notify: bool,           // Send desktop notifications on threshold crossings
+    cache_file: String,     // Remembers the last alert so we don't re-notify every poll
+}
+
+impl Default for BatteryConfig {
+    fn default() -> Self {
+        Self {
+            low_threshold: 20,
+            critical_threshold: 10,
+            notify: false,
+            cache_file: "~/.cache/waybar-battery.json".to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct GlobalConfig {
+    // Optional so existing config.toml files keep working without the new section.
+    #[serde(default)]
+    waybar_battery: BatteryConfig,
+}
+
+// --- Persistence Model ---
+
+/// The last threshold we alerted for ("low" / "critical"). Cleared once charging.
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct AlertCache {
+    last_alert: Option<String>,
+}
+
+fn read_cache(path: &Path) -> AlertCache {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_cache(path: &Path, cache: &AlertCache) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string(cache)?).context("Failed to write cache file")?;
+    Ok(())
+}
+
+// --- Sysfs Reading ---
+
+/// A single battery as reported by the kernel.
+#[derive(Debug)]
+struct Battery {
+    name: String,
+    status: String,
+    percent: f64,
+    energy: Option<Energy>, // None when only the capacity percentage is known
+}
+
+/// Energy readings in µWh (now/full) and µW (rate). Packs that only expose charge
+/// (µAh/µA) are converted with their voltage, so packs of both kinds can be summed.
+#[derive(Debug, Clone, Copy)]
+struct Energy {
+    now: f64,
+    full: f64,
+    rate: f64,
+}
+
+fn read_value(dir: &Path, file: &str) -> Option<f64> {
+    fs::read_to_string(dir.join(file)).ok()?.trim().parse().ok()
+}
+
+fn read_battery(dir: &Path) -> Option<Battery> {
+    let name = dir.file_name()?.to_string_lossy().to_string();
+    let status = fs::read_to_string(dir.join("status"))
+        .map(|s| s.trim().to_string())
+        .unwrap_or_else(|_| "Unknown".to_string());
+
+    // Prefer energy_*, fall back to charge_* times voltage (µAh * µV / 1e6 = µWh).
+    let energy = if let (Some(now), Some(full)) =
+        (read_value(dir, "energy_now"), read_value(dir, "energy_full"))
+    {
+        let rate = read_value(dir, "power_now").unwrap_or(0.0);
+        Some(Energy { now, full, rate: rate.abs() })
+    } else if let (Some(now), Some(full), Some(volts)) = (
+        read_value(dir, "charge_now"),
+        read_value(dir, "charge_full"),
+        read_value(dir, "voltage_min_design").or_else(|| read_value(dir, "voltage_now")),
+    ) {
+        let rate = read_value(dir, "current_now").unwrap_or(0.0);
+        let to_energy = volts / 1_000_000.0;
+        Some(Energy {
+            now: now * to_energy,
+            full: full * to_energy,
+            rate: rate.abs() * to_energy,
+        })
+    } else {
+        None
+    };
+
+    // Without energy readings, fall back to the bare capacity percentage.
+    let percent = match energy {
+        Some(e) if e.full > 0.0 => e.now / e.full * 100.0,
+        Some(_) => 0.0,
+        None => read_value(dir, "capacity")?,
+    };
+
+    Some(Battery { name, status, percent, energy })
+}
+
+fn read_batteries() -> Result<Vec<Battery>> {
+    let entries = fs::read_dir(POWER_SUPPLY_DIR)
+        .with_context(|| format!("Failed to read {}", POWER_SUPPLY_DIR))?;
+
+    let mut batteries: Vec<Battery> = entries
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().starts_with("BAT"))
+        .filter_map(|e| read_battery(&e.path()))
+        .collect();
+    batteries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(batteries)
+}
+
+// --- Aggregation ---
+
+#[derive(Debug, PartialEq)]
+enum ChargeState {
+    Charging,
+    Discharging,
+    Full,
+}
+
+/// Combines all batteries into one state. Any charging pack means we're on AC;
+/// any discharging pack means we're draining.
+fn charge_state(batteries: &[Battery]) -> ChargeState {
+    if batteries.iter().any(|b| b.status == "Charging") {
+        ChargeState::Charging
+    } else if batteries.iter().any(|b| b.status == "Discharging") {
+        ChargeState::Discharging
+    } else {
+        // "Full" / "Not charging" (threshold-limited by TLP) both mean plugged in and idle.
+        ChargeState::Full
+    }
+}
+
+/// Energy of every pack, or None if any pack only reports a percentage.
+fn energies(batteries: &[Battery]) -> Option<Vec<Energy>> {
+    batteries.iter().map(|b| b.energy).collect()
+}
+
+/// Overall percentage. Weighted by energy when every pack reports it; otherwise the
+/// per-pack percentages are averaged, since there is nothing to weight them by.
+fn total_percent(batteries: &[Battery]) -> u8 {
+    let percent = match energies(batteries) {
+        Some(energies) => {
+            let now: f64 = energies.iter().map(|e| e.now).sum();
+            let full: f64 = energies.iter().map(|e| e.full).sum();
+            if full > 0.0 { now / full * 100.0 } else { 0.0 }
+        }
+        None => batteries.iter().map(|b| b.percent).sum::<f64>() / batteries.len() as f64,
+    };
+    percent.round().clamp(0.0, 100.0) as u8
+}
+
+/// Hours until empty (discharging) or full (charging). None if the rate is unknown.
+fn time_remaining(batteries: &[Battery], state: &ChargeState) -> Option<f64> {
+    let energies = energies(batteries)?;
+    let now: f64 = energies.iter().map(|e| e.now).sum();
+    let full: f64 = energies.iter().map(|e| e.full).sum();
+    let rate: f64 = energies.iter().map(|e| e.rate).sum();
+    if rate <= 0.0 {
+        return None;
+    }
+    match state {
+        ChargeState::Discharging => Some(now / rate),
+        ChargeState::Charging => Some((full - now).max(0.0) / rate),
+        ChargeState::Full => None,
+    }
+}
+
+fn format_hours(hours: f64) -> String {
+    let total_minutes = (hours * 60.0).round() as u64;
+    format!("{}h {:02}m", total_minutes / 60, total_minutes % 60)
+}
+
+// --- Alerts ---
+
+/// Alert severity, ordered so a "critical" alert supersedes an earlier "low" one.
+fn alert_rank(level: Option<&str>) -> u8 {
+    match level {
+        Some("critical") => 2,
+        Some("low") => 1,
+        _ => 0,
+    }
+}
+
+/// Sends a notification when crossing into low/critical, once per crossing.
+fn maybe_notify(config: &BatteryConfig, percent: u8, state: &ChargeState) {
+    let cache_path = expand_path(&config.cache_file);
+    let mut cache = read_cache(&cache_path);
+
+    if *state != ChargeState::Discharging {
+        // Re-arm once we're back on AC.
+        if cache.last_alert.take().is_some()
+            && let Err(e) = save_cache(&cache_path, &cache) {
+                eprintln!("Warning: Failed to save alert cache: {}", e);
+            }
+        return;
+    }
+
+    let (level, summary, urgency) = if percent <= config.critical_threshold {
+        ("critical", "Battery Critical", Urgency::Critical)
+    } else if percent <= config.low_threshold {
+        ("low", "Battery Low", Urgency::Normal)
+    } else {
+        return;
+    };
+
+    if alert_rank(Some(level)) <= alert_rank(cache.last_alert.as_deref()) {
+        return;
+    }
+
+    let _ = Notification::new()
+        .summary(summary)
+        .body(&format!("{}% remaining. Plug in soon.", percent))
+        .icon("battery-caution")
+        .urgency(urgency)
+        .show();
+
+    cache.last_alert = Some(level.to_string());
+    if let Err(e) = save_cache(&cache_path, &cache) {
+        eprintln!("Warning: Failed to save alert cache: {}", e);
+    }
+}
+
+// --- Main (Waybar JSON Protocol) ---
+
+fn main() -> Result<()> {
+    let config = match load_config::<GlobalConfig>() {
+        Ok(global) => global.waybar_battery,
+        Err(e) => {
+            eprintln!("Using default battery settings: {}", e);
+            BatteryConfig::default()
+        }
+    };
+
+    let batteries = read_batteries()?;
+    if batteries.is_empty() {
+        // Empty text hides the module on desktops.
+        println!("{}", json!({ "text": "", "tooltip": "No battery", "class": "none" }));
+        return Ok(());
+    }
+
+    let percent = total_percent(&batteries);
+
+    let state = charge_state(&batteries);
+    let remaining = time_remaining(&batteries, &state);
+
+    let class = match state {
+        ChargeState::Charging => "charging",
+        ChargeState::Full => "full",
+        ChargeState::Discharging if percent <= config.critical_threshold => "critical",
+        ChargeState::Discharging if percent <= config.low_threshold => "low",
+        ChargeState::Discharging => "discharging",
+    };
+
+    let icon = if state == ChargeState::Charging {
+        ICON_CHARGING
+    } else {
+        ICONS[(percent as usize * (ICONS.len() - 1) + 50) / 100]
+    };
+
+    // Tooltip: overall state, estimate, then each pack when there's more than one.
+    let mut tooltip = match (&state, remaining) {
+        (ChargeState::Charging, Some(h)) => format!("Charging: {}%\n{} until full", percent, format_hours(h)),
+        (ChargeState::Discharging, Some(h)) => format!("Discharging: {}%\n{} remaining", percent, format_hours(h)),
+        (ChargeState::Charging, None) => format!("Charging: {}%", percent),
+        (ChargeState::Discharging, None) => format!("Discharging: {}%", percent),
+        (ChargeState::Full, _) => format!("Plugged in: {}%", percent),
+    };
+    if batteries.len() > 1 {
+        for b in &batteries {
+            tooltip.push_str(&format!("\n{}: {:.0}% ({})", b.name, b.percent.round(), b.status));
+        }
+    }
+
+    if config.notify {
+        maybe_notify(&config, percent, &state);
+    }
+
+    println!("{}", json!({
+        "text": format!("{} {}%", icon, percent),
+        "tooltip": tooltip,
+        "class": class,
+        "percentage": percent
+    }));
+    Ok(())
+}
@@ -3,10 +3,11 @@ use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc::Sender;
 use crate::ui::AppEvent;
+use crate::fx::Converter;
 
 use crate::network::{FinnhubQuote, YahooSearchResult};
 use crate::app::InputMode::Normal;
-use crate::config::StockStruct;
+use crate::config::{AlertConfig, StockStruct};
 
 /// Defines the input state of the TUI.
 /// We use a state machine approach to change keybindings based on context.
@@ -20,6 +21,41 @@ pub enum InputMode {
 pub struct Config {
     pub stocks: Vec<StockStruct>,
     pub api_key: Option<String>,
+    // ISO code (e.g. "EUR") to convert all prices into. None keeps each listing's own currency.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_currency: Option<String>,
+    // How long Waybar mode reuses a cached quote. None = quotes::DEFAULT_TTL_SECS.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_ttl_secs: Option<u64>,
+    // Price-move notifications for Waybar mode
+    #[serde(default, rename = "alert", skip_serializing_if = "Vec::is_empty")]
+    pub alerts: Vec<AlertConfig>,
+    // Intraday sparkline after each price in the bar (one extra request per symbol)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub show_sparkline: bool,
+    // Extra named lists; `stocks` above is the main one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watchlists: Vec<Watchlist>,
+}
+/// A named watchlist, switchable in the TUI with Tab / Shift+Tab.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Watchlist {
+    pub name: String,
+    pub stocks: Vec<StockStruct>,
+}
+/// Display name of the list stored in `Config.stocks`.
+pub const MAIN_WATCHLIST: &str = "Main";
+impl Config {
+    /// Symbols flagged for the bar across every watchlist, without duplicates.
+    pub fn sidebar_symbols(&self) -> Vec<String> {
+        let mut symbols: Vec<String> = Vec::new();
+        for stock in self.stocks.iter().chain(self.watchlists.iter().flat_map(|w| &w.stocks)) {
+            if stock.sidebar && !symbols.contains(&stock.symbol) {
+                symbols.push(stock.symbol.clone());
+            }
+        }
+        symbols
+    }
 }
 // Default configuration for new users
 impl Default for Config {
@@ -31,6 +67,11 @@ impl Default for Config {
                 StockStruct { symbol: "BTC-USD".into(), sidebar: true },
             ],
             api_key: None,
+            base_currency: None,
+            cache_ttl_secs: None,
+            alerts: Vec::new(),
+            show_sparkline: false,
+            watchlists: Vec::new(),
         }
     }
 }
@@ -44,6 +85,45 @@ pub struct StockDetails {
     pub low_52w: f64,
     pub year_return: Option<f64>,
 }
+/// Lookback window of the TUI chart, picked with 1/3/6/y/5 in Normal mode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartPeriod {
+    OneMonth,
+    ThreeMonths,
+    SixMonths,
+    OneYear,
+    FiveYears,
+}
+impl ChartPeriod {
+    pub fn from_key(key: char) -> Option<Self> {
+        match key {
+            '1' => Some(Self::OneMonth),
+            '3' => Some(Self::ThreeMonths),
+            '6' => Some(Self::SixMonths),
+            'y' => Some(Self::OneYear),
+            '5' => Some(Self::FiveYears),
+            _ => None,
+        }
+    }
+    pub fn days(self) -> i64 {
+        match self {
+            Self::OneMonth => 30,
+            Self::ThreeMonths => 91,
+            Self::SixMonths => 182,
+            Self::OneYear => 365,
+            Self::FiveYears => 5 * 365,
+        }
+    }
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::OneMonth => "1 Month History",
+            Self::ThreeMonths => "3 Month History",
+            Self::SixMonths => "6 Month History",
+            Self::OneYear => "1 Year History",
+            Self::FiveYears => "5 Year History",
+        }
+    }
+}
 /// Defines the current market status (bond yields, yield curve etc)
 #[derive(Debug, Clone)]
 pub struct MarketStatus {
@@ -60,18 +140,27 @@ impl MarketStatus {
 }
 /// Holds the runtime state of the TUI application.
 pub struct App {
-    pub stocks: Vec<StockStruct>,
+    pub stocks: Vec<StockStruct>, // The active watchlist
+    // Every watchlist (index 0 = main). The active slot is empty while its stocks live in `stocks`.
+    pub watchlists: Vec<Watchlist>,
+    pub active_list: usize,
     pub should_quit: bool,
     pub state: ListState, // tracks the selected item in the stock list
     pub api_key: Option<String>,
 
-    // Cached Data
-    pub current_quote: Option<FinnhubQuote>,
-    pub stock_history: Option<Vec<(f64, f64)>>,
-    pub details: Option<StockDetails>,
+    // Cached Data, each tagged with the symbol it was fetched for
+    pub current_quote: Option<(String, FinnhubQuote)>,
+    pub stock_history: Option<(String, Vec<(f64, f64)>)>,
+    pub chart_period: ChartPeriod,
+    pub details: Option<(String, StockDetails)>,
     pub search_results: Vec<YahooSearchResult>,
     pub search_state: ListState,
     pub market_status: Option<MarketStatus>,
+    pub base_currency: Option<String>,
+    pub cache_ttl_secs: Option<u64>,
+    pub alerts: Vec<AlertConfig>,
+    pub show_sparkline: bool,
+    pub fx: Converter,
     
     // Input Handling
     pub input: String,
@@ -86,7 +175,7 @@ pub struct App {
 
 
 impl App {
-    pub fn new(config: Config, message: String, message_color: Color, stock_history: Option<Vec<(f64, f64)>>) -> Self {
+    pub fn new(config: Config, message: String, message_color: Color, stock_history: Option<(String, Vec<(f64, f64)>)>) -> Self {
         let mut state = ListState::default();
         state.select(Some(0));
         // Detect if this is a first run (missing API key) and force KeyEntry mode.
@@ -99,8 +188,12 @@ impl App {
                 Color::Yellow
             )
         };
+        let mut watchlists = vec![Watchlist { name: MAIN_WATCHLIST.to_string(), stocks: Vec::new() }];
+        watchlists.extend(config.watchlists);
         Self {
             stocks: config.stocks,
+            watchlists,
+            active_list: 0,
             should_quit: false,
             state,
             api_key: config.api_key,
@@ -110,10 +203,16 @@ impl App {
             message: msg,
             message_color: color,
             stock_history,
+            chart_period: ChartPeriod::OneYear,
             details: None,
             search_results: vec![],
             search_state: ListState::default(),
             market_status: None,
+            base_currency: config.base_currency,
+            cache_ttl_secs: config.cache_ttl_secs,
+            alerts: config.alerts,
+            show_sparkline: config.show_sparkline,
+            fx: Converter::default(),
         }
     }
     /// Moves the selection index down, wrapping around if necessary.
@@ -135,11 +234,40 @@ impl App {
         self.state.select(Some(i));
     }
 
-    /// Helper to export state for saving
+    /// Switches to the next (`step` = 1) or previous (`step` = -1) watchlist, wrapping around.
+    pub fn cycle_watchlist(&mut self, step: isize) {
+        if self.watchlists.len() < 2 { return; }
+        self.watchlists[self.active_list].stocks = std::mem::take(&mut self.stocks);
+        self.active_list = (self.active_list as isize + step).rem_euclid(self.watchlists.len() as isize) as usize;
+        self.stocks = std::mem::take(&mut self.watchlists[self.active_list].stocks);
+
+        // The panels belong to the old selection
+        self.state.select(if self.stocks.is_empty() { None } else { Some(0) });
+        self.current_quote = None;
+        self.stock_history = None;
+        self.details = None;
+        self.message = format!("Watchlist: {}", self.watchlist_name());
+        self.message_color = Color::Cyan;
+    }
+
+    pub fn watchlist_name(&self) -> &str {
+        &self.watchlists[self.active_list].name
+    }
+
+    /// Helper to export state for saving. Writes every watchlist back, not just the active one.
     pub fn to_config(&self) -> Config {
+        let mut lists = self.watchlists.clone();
+        lists[self.active_list].stocks = self.stocks.clone();
+        let mut lists = lists.into_iter();
+        let main = lists.next().map(|w| w.stocks).unwrap_or_default();
         Config {
-            stocks: self.stocks.clone(),
+            stocks: main,
             api_key: self.api_key.clone(),
+            base_currency: self.base_currency.clone(),
+            cache_ttl_secs: self.cache_ttl_secs,
+            alerts: self.alerts.clone(),
+            show_sparkline: self.show_sparkline,
+            watchlists: lists.collect(),
         }
     }
 
@@ -175,10 +303,10 @@ impl App {
     }
 
     pub fn toggle_sidebar_view(&mut self) {
-        if let Some(selected) = self.state.selected() {
-            if let Some(stock) = self.stocks.get_mut(selected) {
-                stock.sidebar = !stock.sidebar;
-            }
+        if let Some(selected) = self.state.selected()
+            && let Some(stock) = self.stocks.get_mut(selected)
+        {
+            stock.sidebar = !stock.sidebar;
         }
     }
 
@@ -204,6 +332,7 @@ impl App {
             // Trigger background work
             self.trigger_fetch(new_symbol.clone(), tx, client);
             self.stocks.push(StockStruct { symbol: new_symbol, sidebar: true });
+            self.trigger_fx_load(tx, client);
             let tx_clone = tx.clone();
             tokio::spawn(async move {
                 let _ = tx_clone.send(AppEvent::SaveConfig).await;
@@ -221,16 +350,54 @@ impl App {
         let tx = tx.clone();
         let api_key = self.api_key.clone().unwrap_or_default();
         let symbol = symbol.clone();
+        let days = self.chart_period.days();
 
         tokio::spawn(async move {
             let q_res = crate::network::fetch_quote(&client, &symbol, &api_key).await;
             let _ = tx.send(AppEvent::QuoteFetched(symbol.clone(), q_res)).await;
 
-            let h_res = crate::network::fetch_history(&client, &symbol, &api_key).await;
+            let h_res = crate::network::fetch_history(&client, &symbol, &api_key, days).await;
             let _ = tx.send(AppEvent::HistoryFetched(symbol.clone(), h_res)).await;
 
             let d_res = crate::network::fetch_details(&client, &symbol, &api_key).await;
             let _ = tx.send(AppEvent::DetailsFetched(symbol.clone(), d_res)).await;
         });
     }
+
+    /// Switches the chart range and reloads the selected symbol's history for it.
+    pub fn set_chart_period(&mut self, period: ChartPeriod, tx: &Sender<AppEvent>, client: &reqwest::Client) {
+        if self.chart_period == period {
+            return;
+        }
+        self.chart_period = period;
+        // Nothing charted yet: the next Enter fetches with the new range
+        if self.stock_history.is_none() {
+            return;
+        }
+        let Some(symbol) = self.state.selected().and_then(|i| self.stocks.get(i)).map(|s| s.symbol.clone()) else {
+            return;
+        };
+        let client = client.clone();
+        let tx = tx.clone();
+        let api_key = self.api_key.clone().unwrap_or_default();
+        let days = period.days();
+
+        tokio::spawn(async move {
+            let h_res = crate::network::fetch_history(&client, &symbol, &api_key, days).await;
+            let _ = tx.send(AppEvent::HistoryFetched(symbol, h_res)).await;
+        });
+    }
+
+    /// Loads the FX converter for the current watchlist (no-op without `base_currency`).
+    pub fn trigger_fx_load(&self, tx: &Sender<AppEvent>, client: &reqwest::Client) {
+        let client = client.clone();
+        let tx = tx.clone();
+        let base = self.base_currency.clone();
+        let symbols: Vec<String> = self.stocks.iter().map(|s| s.symbol.clone()).collect();
+
+        tokio::spawn(async move {
+            let fx = crate::fx::load(&client, base.as_deref(), &symbols).await;
+            let _ = tx.send(AppEvent::FxLoaded(fx)).await;
+        });
+    }
 }
@@ -2,7 +2,7 @@ use std::fs;
 use std::path::PathBuf;
 use anyhow::{Result, Context};
 use serde::{Serialize, Deserialize};
-use crate::app::Config;
+use crate::app::{Config, Watchlist};
 
 // Struct to parse the central TOML
 #[derive(Deserialize)]
@@ -28,17 +28,80 @@ pub struct StockStruct {
 struct ParsedConfig {
     api_key: String,
     stocks: Option<StockConfig>,
+    #[serde(default)]
+    base_currency: Option<String>,
+    #[serde(default)]
+    crypto: Vec<String>,
+    #[serde(default)]
+    cache_ttl_secs: Option<u64>,
+    #[serde(default, rename = "alert")]
+    alerts: Vec<AlertConfig>,
+    #[serde(default)]
+    show_sparkline: bool,
+    #[serde(default)]
+    watchlists: Vec<WatchlistConfig>,
 }
 
 #[derive(Deserialize)]
 struct FinanceConfig {
     api_key: String,
     stocks: Option<StockConfig>,
+    #[serde(default)]
+    base_currency: Option<String>,
+    #[serde(default)]
+    crypto: Vec<String>,
+    #[serde(default)]
+    cache_ttl_secs: Option<u64>,
+    #[serde(default, rename = "alert")]
+    alerts: Vec<AlertConfig>,
+    #[serde(default)]
+    show_sparkline: bool,
+    #[serde(default, rename = "watchlist")]
+    watchlists: Vec<WatchlistConfig>,
+}
+
+/// `[[waybar_finance.watchlist]]`: an extra named list next to the main `stocks`.
+/// Takes the same legacy/V2 stock formats as `stocks`.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct WatchlistConfig {
+    name: String,
+    stocks: Option<StockConfig>,
+}
+
+/// `[[waybar_finance.alert]]`: notify when `symbol` moves at least `percent` (either way) on the day.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct AlertConfig {
+    pub symbol: String,
+    pub percent: f64,
 }
 
 fn set_sidebar_default() -> bool {
     true
 }
+fn unify_watchlists(lists: Vec<WatchlistConfig>) -> Vec<Watchlist> {
+    lists.into_iter()
+        .map(|l| Watchlist {
+            name: l.name,
+            stocks: match l.stocks {
+                Some(StockConfig::Legacy(stocks)) => {
+                    stocks.unwrap_or_default().into_iter().map(|s| StockStruct { symbol: s, sidebar: true }).collect()
+                },
+                Some(StockConfig::V2(stocks)) => stocks,
+                None => Vec::new(),
+            },
+        })
+        .collect()
+}
+/// Appends the dedicated `crypto` list to the watchlist.
+/// Bare pairs like "BTCUSDT" default to Binance, Finnhub's most complete crypto exchange.
+fn merge_crypto(stocks: &mut Vec<StockStruct>, crypto: Vec<String>) {
+    for pair in crypto {
+        let symbol = if pair.contains(':') { pair } else { format!("BINANCE:{}", pair) }.to_uppercase();
+        if !stocks.iter().any(|s| s.symbol == symbol) {
+            stocks.push(StockStruct { symbol, sidebar: true });
+        }
+    }
+}
 /// Resolves the XDG-compliant configuration path.
 /// Usually ~/.config/waybar-finance/config.json on Linux.
 pub fn get_config_path() -> Result<PathBuf> {
@@ -57,7 +120,7 @@ pub fn load_config(path: &PathBuf) -> Result<Config> {
     if path.exists() {
         let content = fs::read_to_string(path).context("Failed to read config file")?;
         if let Ok(parsed) = serde_json::from_str::<ParsedConfig>(&content) {
-            let unified_stocks: Vec<StockStruct> = match parsed.stocks {
+            let mut unified_stocks: Vec<StockStruct> = match parsed.stocks {
                 Some(StockConfig::Legacy(stocks)) => {
                     stocks.unwrap_or_default().into_iter().map(|s| StockStruct { symbol: s, sidebar: true }).collect()
                 },
@@ -70,9 +133,15 @@ pub fn load_config(path: &PathBuf) -> Result<Config> {
                     StockStruct { symbol: "BTC-USD".into(), sidebar: true },
                 ] },
             };
+            merge_crypto(&mut unified_stocks, parsed.crypto);
             return Ok(Config {
                 api_key: Some(parsed.api_key),
                 stocks: unified_stocks,
+                base_currency: parsed.base_currency,
+                cache_ttl_secs: parsed.cache_ttl_secs,
+                alerts: parsed.alerts,
+                show_sparkline: parsed.show_sparkline,
+                watchlists: unify_watchlists(parsed.watchlists),
             });
         }
     }
@@ -83,7 +152,7 @@ pub fn load_config(path: &PathBuf) -> Result<Config> {
             && let Ok(content) = fs::read_to_string(&central_path)
                 && let Ok(global) = toml::from_str::<GlobalConfig>(&content)
                     && let Some(finance) = global.waybar_finance {
-                        let unified_stocks: Vec<StockStruct> = match finance.stocks {
+                        let mut unified_stocks: Vec<StockStruct> = match finance.stocks {
                             Some(StockConfig::Legacy(stocks)) => {
                                 stocks.unwrap_or_default().into_iter().map(|s| StockStruct { symbol: s, sidebar: true }).collect()
                             },
@@ -96,9 +165,15 @@ pub fn load_config(path: &PathBuf) -> Result<Config> {
                                 StockStruct { symbol: "BTC-USD".into(), sidebar: true },
                             ] },
                         };
+                        merge_crypto(&mut unified_stocks, finance.crypto);
                         return Ok(Config {
                             api_key: Some(finance.api_key),
                             stocks: unified_stocks,
+                            base_currency: finance.base_currency,
+                            cache_ttl_secs: finance.cache_ttl_secs,
+                            alerts: finance.alerts,
+                            show_sparkline: finance.show_sparkline,
+                            watchlists: unify_watchlists(finance.watchlists),
                         });
                     }
 
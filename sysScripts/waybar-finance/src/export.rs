@@ -0,0 +1,71 @@
+//! CSV export of the watchlist (`--export <path>`).
+//!
+//! Fetches a live quote and the Yahoo fundamentals for every symbol in the active
+//! watchlist and writes one row per symbol, so the numbers can go into a spreadsheet.
+//! Prices are in each listing's own currency; `base_currency` only applies to display.
+
+use std::fs;
+use std::path::Path;
+use anyhow::{Result, Context};
+use futures::future::join_all;
+use crate::app::App;
+use crate::network::{fetch_details, fetch_quote};
+
+const HEADER: &str = "symbol,price,percent,market_cap,pe_ratio,dividend_yield,high_52w,low_52w,year_return";
+
+/// Quotes a field if it contains a comma, quote or newline (RFC 4180).
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Empty cell for a missing value.
+fn opt(value: Option<f64>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+pub async fn run_export(client: &reqwest::Client, app: &App, path: &Path) -> Result<()> {
+    let api_key = app.api_key.as_deref()
+        .context("No Finnhub API key configured. Run with --tui to set one up.")?;
+
+    let rows = join_all(app.stocks.iter().map(|stock| async move {
+        let symbol = &stock.symbol;
+        let (quote, details) = tokio::join!(
+            fetch_quote(client, symbol, api_key),
+            fetch_details(client, symbol, api_key)
+        );
+        // A failed request leaves its columns empty rather than dropping the row
+        if let Err(e) = &quote {
+            log::warn!("{}: quote failed: {}", symbol, e);
+        }
+        if let Err(e) = &details {
+            log::warn!("{}: details failed: {}", symbol, e);
+        }
+        let quote = quote.ok();
+        let details = details.ok();
+        [
+            csv_field(symbol),
+            opt(quote.as_ref().map(|q| q.price)),
+            opt(quote.as_ref().map(|q| q.percent)),
+            details.as_ref().map(|d| d.market_cap.to_string()).unwrap_or_default(),
+            opt(details.as_ref().and_then(|d| d.pe_ratio)),
+            opt(details.as_ref().and_then(|d| d.dividend_yield)),
+            opt(details.as_ref().map(|d| d.high_52w)),
+            opt(details.as_ref().map(|d| d.low_52w)),
+            opt(details.as_ref().and_then(|d| d.year_return)),
+        ].join(",")
+    })).await;
+
+    let mut csv = String::from(HEADER);
+    csv.push('\n');
+    for row in &rows {
+        csv.push_str(row);
+        csv.push('\n');
+    }
+    fs::write(path, csv).with_context(|| format!("Failed to write {}", path.display()))?;
+    println!("Exported {} symbols to {}", rows.len(), path.display());
+    Ok(())
+}
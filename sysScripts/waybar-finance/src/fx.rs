@@ -0,0 +1,169 @@
+//! Base-currency conversion.
+//!
+//! Quotes arrive in each listing's own currency (USD for US tickers, EUR for Xetra, pence
+//! for most of the LSE). When `base_currency` is configured, prices are converted with
+//! Yahoo FX pairs. Rates are cached per day in `~/.cache/waybar-finance/fx.json` because
+//! Waybar re-runs us every minute and a daily rate is plenty for a watchlist.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use anyhow::{Result, Context};
+use chrono::Local;
+use serde::{Serialize, Deserialize};
+use crate::network::{fetch_currencies, fetch_fx_rates, is_crypto};
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct FxCache {
+    date: String,                          // Day the rates were fetched (YYYY-MM-DD)
+    base: String,                          // Currency the rates convert into
+    rates: HashMap<String, f64>,           // Currency -> base multiplier
+    currencies: HashMap<String, String>,   // Symbol -> trading currency (doesn't expire)
+}
+
+fn get_cache_path() -> Result<PathBuf> {
+    let cache_dir = dirs::cache_dir().context("Could not find cache directory")?;
+    Ok(cache_dir.join("waybar-finance/fx.json"))
+}
+
+fn read_cache() -> Option<FxCache> {
+    let content = fs::read_to_string(get_cache_path().ok()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn write_cache(cache: &FxCache) -> Result<()> {
+    let path = get_cache_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("Failed to create cache directory")?;
+    }
+    fs::write(path, serde_json::to_string(cache)?).context("Failed to write FX cache")?;
+    Ok(())
+}
+
+/// Yahoo reports some listings in minor units (GBp = pence, ILA = agorot, ZAc = cents).
+/// Returns the ISO code and the multiplier to get to major units.
+fn normalize(currency: &str) -> (String, f64) {
+    match currency {
+        "GBp" | "GBX" => ("GBP".to_string(), 0.01),
+        "ILA" => ("ILS".to_string(), 0.01),
+        "ZAc" => ("ZAR".to_string(), 0.01),
+        other => (other.to_uppercase(), 1.0),
+    }
+}
+
+/// Display prefix for a currency. Unknown codes fall back to "CODE ".
+fn prefix(code: &str) -> String {
+    match code {
+        "USD" => "$".to_string(),
+        "EUR" => "€".to_string(),
+        "GBP" => "£".to_string(),
+        "JPY" | "CNY" => "¥".to_string(),
+        "INR" => "₹".to_string(),
+        "KRW" => "₩".to_string(),
+        "CAD" => "C$".to_string(),
+        "AUD" => "A$".to_string(),
+        other => format!("{} ", other),
+    }
+}
+
+/// Converts and formats prices for display. The default converter is a no-op that keeps
+/// the historical `$` formatting, so users without `base_currency` see no change.
+#[derive(Debug, Clone, Default)]
+pub struct Converter {
+    base: Option<String>,
+    rates: HashMap<String, f64>,
+    currencies: HashMap<String, String>,
+}
+
+impl Converter {
+    /// Returns (multiplier, display currency) for a symbol.
+    /// Falls back to the native currency when the rate is missing, and to no conversion
+    /// and no currency at all when the symbol's currency couldn't be looked up.
+    fn resolve(&self, symbol: &str) -> (f64, Option<String>) {
+        let Some(base) = &self.base else {
+            return (1.0, Some("USD".to_string()));
+        };
+        let native = match self.currencies.get(symbol) {
+            Some(native) => native.as_str(),
+            None if is_crypto(symbol) => "USD",
+            None => return (1.0, None),
+        };
+        let (code, scale) = normalize(native);
+        if *base == code {
+            return (scale, Some(code));
+        }
+        match self.rates.get(&code) {
+            Some(rate) => (scale * rate, Some(base.clone())),
+            None => (scale, Some(code)),
+        }
+    }
+
+    pub fn convert(&self, symbol: &str, price: f64) -> f64 {
+        price * self.resolve(symbol).0
+    }
+
+    /// Formats a price with the right currency prefix, e.g. "€123.45".
+    pub fn format(&self, symbol: &str, price: f64) -> String {
+        let (rate, code) = self.resolve(symbol);
+        let prefix = code.map(|c| prefix(&c)).unwrap_or_default();
+        format!("{}{:.2}", prefix, price * rate)
+    }
+}
+
+/// Builds a converter for `symbols`. Lookup failures are logged and degrade to
+/// native-currency display rather than failing the whole module.
+pub async fn load(client: &reqwest::Client, base: Option<&str>, symbols: &[String]) -> Converter {
+    let Some(base) = base.map(|b| b.trim().to_uppercase()).filter(|b| !b.is_empty()) else {
+        return Converter::default();
+    };
+
+    let mut cache = read_cache().unwrap_or_default();
+    let today = Local::now().format("%Y-%m-%d").to_string();
+    if cache.date != today || cache.base != base {
+        cache.rates.clear();
+        cache.date = today;
+        cache.base = base.clone();
+    }
+
+    let mut changed = false;
+    // Crypto pairs have no Yahoo listing under their Finnhub name; they stay in USD.
+    let unknown: Vec<String> = symbols.iter()
+        .filter(|s| !is_crypto(s) && !cache.currencies.contains_key(*s))
+        .cloned()
+        .collect();
+    if !unknown.is_empty() {
+        match fetch_currencies(client, &unknown).await {
+            Ok(found) => {
+                changed |= !found.is_empty();
+                cache.currencies.extend(found);
+            }
+            Err(e) => log::warn!("FX: currency lookup failed: {}", e),
+        }
+    }
+
+    let missing_rates: Vec<String> = cache.currencies.values()
+        .map(|c| normalize(c).0)
+        .filter(|c| *c != base && !cache.rates.contains_key(c))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    if !missing_rates.is_empty() {
+        match fetch_fx_rates(client, &missing_rates, &base).await {
+            Ok(rates) => {
+                changed |= !rates.is_empty();
+                cache.rates.extend(rates);
+            }
+            Err(e) => log::warn!("FX: rate lookup failed: {}", e),
+        }
+    }
+
+    if changed && let Err(e) = write_cache(&cache) {
+        log::warn!("FX: {}", e);
+    }
+
+    Converter {
+        base: Some(base),
+        rates: cache.rates,
+        currencies: cache.currencies,
+    }
+}
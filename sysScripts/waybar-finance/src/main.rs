@@ -1,31 +1,44 @@
 //! Application entry point.
 //! 
 //! Handles command-line argument parsing, configuration loading, and 
-//! dispatching the application to either "Waybar Mode" (one-shot JSON output) 
-//! or "TUI Mode" (interactive terminal UI).
+//! dispatching the application to either "Waybar Mode" (one-shot JSON output),
+//! "TUI Mode" (interactive terminal UI) or a one-shot CSV export.
 
 mod app;
 mod ui;
 mod config;
 mod network;
+mod fx;
+mod quotes;
+mod export;
 
 use anyhow::Result;
 use clap::Parser;
 use ratatui::style::Color;
 use app::App;
 use config::{get_config_path, load_config};
-use network::run_waybar_mode;
+use network::{FIXTURES, run_waybar_mode};
 use ui::run_tui;
+use export::run_export;
+use std::path::PathBuf;
 use reqwest::Client;
 
 /// Command line arguments.
 #[derive(Debug, Parser)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version = common::version::version_string(env!("CARGO_PKG_VERSION")), about, long_about = None)]
 struct Args {
     /// Launch the interactive Terminal User Interface (TUI).
     /// If omitted, outputs JSON for Waybar.
     #[arg(short, long)]
     tui: bool,
+
+    /// Write the watchlist with quotes and fundamentals to a CSV file, then exit.
+    #[arg(long, value_name = "PATH")]
+    export: Option<PathBuf>,
+
+    /// Log to stderr: -v for the major steps, -vv for request and cache decisions.
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
 }
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -41,14 +54,20 @@ async fn main() -> Result<()> {
     // "Warm up" the client by hitting the homepage.
     // This is required to acquire the initial session cookies and "crumb" 
     // needed for subsequent API calls to the v7/v10 endpoints.
-    let _ = client.get("https://finance.yahoo.com").send().await;
+    // Skipped in fixture mode, where nothing may touch the network.
+    if !FIXTURES.enabled() {
+        let _ = client.get("https://finance.yahoo.com").send().await;
+    }
     let args = Args::parse();
+    common::logging::init(module_path!(), args.verbose);
     // Load user configuration (API keys, watchlist)
     let config_path = get_config_path()?;
     let config = load_config(&config_path)?;
     let mut app = App::new(config, String::from("Ready"), Color::Gray, None);
     // Dispatch based on mode
-    if args.tui {
+    if let Some(path) = args.export {
+        run_export(&client, &app, &path).await?
+    } else if args.tui {
         println!("Initializing TUI mode...");
         run_tui(&client, &mut app).await?
     } else {
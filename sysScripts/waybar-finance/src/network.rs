@@ -1,13 +1,22 @@
+use std::collections::HashMap;
 use std::sync::OnceLock;
 use tokio::sync::Mutex;
 use anyhow::{Result, Context};
 use yahoo_finance_api::YahooConnector;
-use time::OffsetDateTime;
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, UtcOffset, Weekday};
+use time::macros::time;
 use serde::{Deserialize, Serialize};
 use futures::future::join_all;
+use notify_rust::Notification;
 use crate::config::{get_config_path, load_config};
 use crate::app::{StockDetails, MarketStatus};
+use crate::config::AlertConfig;
+use crate::fx::Converter;
+use common::fixtures::{self, Fixtures};
+use crate::quotes;
 
+/// Canned responses under `$RUST_DOTFILES_FIXTURES/waybar-finance/` (see docs/FIXTURES.md).
+pub const FIXTURES: Fixtures = Fixtures::new("waybar-finance");
 
 #[derive(Debug, Deserialize)]
 pub struct FinnhubQuote {
@@ -18,6 +27,50 @@ pub struct FinnhubQuote {
     pub percent: f64,
 }
 
+/// Finnhub's answer once the free tier's calls per minute are used up: HTTP 429 and
+/// `{"error":"API limit reached. ..."}` (the body has also been seen with a 200).
+#[derive(Debug)]
+pub struct RateLimited;
+
+impl std::fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Finnhub rate limit reached")
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+pub fn is_rate_limited(e: &anyhow::Error) -> bool {
+    e.downcast_ref::<RateLimited>().is_some()
+}
+
+/// Reads a Finnhub response, turning throttling into `RateLimited` and other failures into
+/// an HTTP error mentioning `what`.
+async fn finnhub_json<T: serde::de::DeserializeOwned>(resp: reqwest::Response, what: &str) -> Result<T> {
+    let status = resp.status();
+    let body = resp.text().await?;
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS || body.contains("API limit reached") {
+        return Err(RateLimited.into());
+    }
+    if !status.is_success() {
+        return Err(anyhow::anyhow!("Failed to fetch {}: HTTP {}", what, status));
+    }
+    Ok(serde_json::from_str(&body)?)
+}
+
+/// Finnhub `/crypto/candle` response. `s` is "ok" or "no_data".
+#[derive(Debug, Deserialize)]
+struct FinnhubCandles {
+    #[serde(rename = "s")]
+    status: String,
+
+    #[serde(rename = "c", default)]
+    close: Vec<f64>,
+
+    #[serde(rename = "o", default)]
+    open: Vec<f64>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct WaybarOutput {
     pub text: String,
@@ -83,6 +136,8 @@ struct YahooQuote {
     #[serde(rename = "fiftyTwoWeekChangePercent")]
     fifty_two_week_change: Option<f64>,
 
+    currency: Option<String>,
+
     symbol: String,
 }
 // Global cache for the yahoo crumb to avoid re-fetching each request.
@@ -123,6 +178,9 @@ async fn get_yahoo_crumb(client: &reqwest::Client) -> Result<String> {
 /// Fetches search results from Yahoo Finance's search endpoint.
 /// Handles basic symbol search.
 pub async fn search_ticker(client: &reqwest::Client, query: &str) -> Result<Vec<YahooSearchResult>> {
+    if let Some(data) = FIXTURES.load_json::<YahooSearchResponse>(&fixtures::name(&["search", query])) {
+        return data.map(|d| d.quotes);
+    }
     let url = format!(
         "https://query2.finance.yahoo.com/v1/finance/search?q={}&lang=en-US",
         query
@@ -147,20 +205,25 @@ pub async fn search_ticker(client: &reqwest::Client, query: &str) -> Result<Vec<
 /// Fetches detailed metrics (P/E, Yield, etc.) from Yahoo's v7 endpoint.
 /// Handles the differences between Stocks (using Dividend Yield) and ETFs (using 12-Mo Yield).
 pub async fn fetch_details(client: &reqwest::Client, symbol: &str, _key: &str) -> Result<StockDetails> {
-    let crumb = get_yahoo_crumb(client).await?; 
-    
-    let url = format!(
-        "https://query1.finance.yahoo.com/v7/finance/quote?symbols={}&crumb={}",
-        symbol, crumb
-    );
-
-    let resp = client.get(&url).send().await?;
-    
-    if !resp.status().is_success() {
-        return Err(anyhow::anyhow!("Yahoo Error: {}", resp.status()));
-    }
+    let symbol = &yahoo_symbol(symbol);
+    let data: YahooQuoteResponse = if let Some(data) = FIXTURES.load_json(&fixtures::name(&["details", symbol])) {
+        data?
+    } else {
+        let crumb = get_yahoo_crumb(client).await?; 
+        
+        let url = format!(
+            "https://query1.finance.yahoo.com/v7/finance/quote?symbols={}&crumb={}",
+            symbol, crumb
+        );
+
+        let resp = client.get(&url).send().await?;
+        
+        if !resp.status().is_success() {
+            return Err(anyhow::anyhow!("Yahoo Error: {}", resp.status()));
+        }
 
-    let data: YahooQuoteResponse = resp.json().await?;
+        resp.json().await?
+    };
     
     if data.quote_response.result.is_empty() {
         return Err(anyhow::anyhow!("No data found"));
@@ -194,26 +257,110 @@ pub async fn fetch_details(client: &reqwest::Client, symbol: &str, _key: &str) -
         year_return: perf,
     })
 }
+/// Finnhub crypto symbols carry an exchange prefix, e.g. `BINANCE:BTCUSDT`.
+pub fn is_crypto(symbol: &str) -> bool {
+    symbol.contains(':')
+}
+
+/// Short label for the bar and watchlist: `BINANCE:BTCUSDT` -> `BTCUSDT`.
+pub fn display_symbol(symbol: &str) -> &str {
+    symbol.rsplit_once(':').map(|(_, pair)| pair).unwrap_or(symbol)
+}
+
+/// Maps a Finnhub crypto pair to the Yahoo ticker used for charts and details
+/// (`BINANCE:BTCUSDT` -> `BTC-USD`). Equities are returned unchanged.
+fn yahoo_symbol(symbol: &str) -> String {
+    if !is_crypto(symbol) {
+        return symbol.to_string();
+    }
+    let pair = display_symbol(symbol);
+    // Longest first so "USDT" wins over "USD". Stablecoins are close enough to USD for a chart.
+    for (suffix, quote) in [("USDT", "USD"), ("USDC", "USD"), ("BUSD", "USD"), ("USD", "USD"), ("EUR", "EUR"), ("GBP", "GBP"), ("BTC", "BTC"), ("ETH", "ETH")] {
+        if let Some(base) = pair.strip_suffix(suffix)
+            && !base.is_empty() {
+                return format!("{}-{}", base, quote);
+            }
+    }
+    pair.to_string()
+}
+
+/// Turns daily candles into a quote: last close, and its change against the previous
+/// close (or the day's open when only one candle came back).
+fn quote_from_candles(symbol: &str, candles: FinnhubCandles) -> Result<FinnhubQuote> {
+    if candles.status != "ok" {
+        return Err(anyhow::anyhow!("No crypto data for {}", symbol));
+    }
+    let price = *candles.close.last().context("Empty candle response")?;
+    let reference = match candles.close.len() {
+        n if n >= 2 => candles.close[n - 2],
+        _ => candles.open.last().copied().unwrap_or(price),
+    };
+    let percent = if reference != 0.0 { (price - reference) / reference * 100.0 } else { 0.0 };
+    Ok(FinnhubQuote { price, percent })
+}
+
+/// Fetches a crypto quote from Finnhub's candle endpoint (`/quote` only covers equities).
+/// Candles are premium-only on Finnhub, so a free key (HTTP 403) falls back to Yahoo.
+async fn fetch_crypto_quote(client: &reqwest::Client, symbol: &str, key: &str) -> Result<FinnhubQuote> {
+    if let Some(candles) = FIXTURES.load_json(&fixtures::name(&["crypto", symbol])) {
+        return quote_from_candles(symbol, candles?);
+    }
+    let to = OffsetDateTime::now_utc().unix_timestamp();
+    let from = to - 3 * 86_400;
+    let url = format!(
+        "https://finnhub.io/api/v1/crypto/candle?symbol={}&resolution=D&from={}&to={}&token={}",
+        symbol, from, to, key
+    );
+    let resp = client.get(&url).send().await?;
+    if resp.status() == reqwest::StatusCode::FORBIDDEN {
+        log::debug!("{}: Finnhub crypto candles need a premium key, using Yahoo", symbol);
+        let candles = fetch_yahoo_candles(symbol).await
+            .with_context(|| format!("Finnhub crypto candles need a premium key and Yahoo failed for {}", symbol))?;
+        return quote_from_candles(symbol, candles);
+    }
+    quote_from_candles(symbol, finnhub_json(resp, "crypto quote").await?)
+}
+
+/// Daily candles for the last few days from Yahoo, in Finnhub's shape.
+async fn fetch_yahoo_candles(symbol: &str) -> Result<FinnhubCandles> {
+    let provider = YahooConnector::new()?;
+    let response = provider.get_quote_range(&yahoo_symbol(symbol), "1d", "5d").await
+        .context("Yahoo API Error")?;
+    let quotes = response.quotes().context("No quotes in response")?;
+    Ok(FinnhubCandles {
+        status: if quotes.is_empty() { "no_data" } else { "ok" }.to_string(),
+        close: quotes.iter().map(|q| q.close).collect(),
+        open: quotes.iter().map(|q| q.open).collect(),
+    })
+}
 /// Fetches real-time stock quote from Finnhub API.
+/// Crypto pairs are routed to the candle endpoint.
 pub async fn fetch_quote(client: &reqwest::Client, symbol: &str, key: &str) -> Result<FinnhubQuote> {
+    if is_crypto(symbol) {
+        return fetch_crypto_quote(client, symbol, key).await;
+    }
+    if let Some(quote) = FIXTURES.load_json(&fixtures::name(&["quote", symbol])) {
+        return quote;
+    }
     let url = format!(
         "https://finnhub.io/api/v1/quote?symbol={}&token={}",
         symbol, key
     );
     let resp = client.get(&url).send().await?;
-    if !resp.status().is_success() {
-        return Err(anyhow::anyhow!("Failed to fetch quote: HTTP {}", resp.status()));
-    }
-    let quote: FinnhubQuote = resp.json().await?;
-    Ok(quote)
+    finnhub_json(resp, "quote").await
 }
 /// Fetches historical stock data from Yahoo Finance API.
 /// The data points are returned as a vector of (timestamp, close price) tuples.
 /// Used by the charting component.
-pub async fn fetch_history(_client: &reqwest::Client, symbol: &str, _key: &str) -> Result<Vec<(f64, f64)>> {
+pub async fn fetch_history(_client: &reqwest::Client, symbol: &str, _key: &str, days: i64) -> Result<Vec<(f64, f64)>> {
+    let symbol = &yahoo_symbol(symbol);
+    // Fixture format: [[timestamp, close], ...]
+    if let Some(points) = FIXTURES.load_json(&fixtures::name(&["history", symbol])) {
+        return points;
+    }
     let provider = YahooConnector::new()?;
     let end = OffsetDateTime::now_utc();
-    let start = end - time::Duration::days(365);
+    let start = end - time::Duration::days(days);
     let response = provider.get_quote_history(symbol, start, end).await
         .context("Yaho API Error")?;
     let quotes = response.quotes().context("No quotes in response")?;
@@ -225,6 +372,76 @@ pub async fn fetch_history(_client: &reqwest::Client, symbol: &str, _key: &str)
     }
     Ok(points)
 }
+/// Fires a desktop notification for a symbol that just crossed its alert threshold.
+fn notify_alert(symbol: &str, quote: &FinnhubQuote, alert: &AlertConfig, fx: &Converter) {
+    let direction = if quote.percent >= 0.0 { "up" } else { "down" };
+    let _ = Notification::new()
+        .summary(&format!("{} {} {:.2}%", display_symbol(symbol), direction, quote.percent.abs()))
+        .body(&format!("Now {} (alert at ±{:.2}%)", fx.format(symbol, quote.price), alert.percent.abs()))
+        .icon("dialog-information")
+        .show();
+}
+/// Number of intraday closes drawn in the bar sparkline.
+const SPARKLINE_POINTS: usize = 8;
+
+/// Fetches today's closes at 15 minute resolution from Yahoo, for the bar sparkline.
+async fn fetch_intraday(symbol: &str) -> Result<Vec<f64>> {
+    let symbol = &yahoo_symbol(symbol);
+    // Fixture format: [close, close, ...]
+    if let Some(closes) = FIXTURES.load_json(&fixtures::name(&["intraday", symbol])) {
+        return closes;
+    }
+    let provider = YahooConnector::new()?;
+    let response = provider.get_quote_range(symbol, "15m", "1d").await
+        .context("Yahoo API Error")?;
+    let closes: Vec<f64> = response.quotes().context("No quotes in response")?
+        .iter()
+        .map(|q| q.close)
+        .collect();
+    if closes.is_empty() {
+        return Err(anyhow::anyhow!("Intraday data is empty"));
+    }
+    Ok(closes)
+}
+
+/// Maps the last SPARKLINE_POINTS closes onto block glyphs, lowest to highest.
+fn sparkline(closes: &[f64]) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let points = &closes[closes.len().saturating_sub(SPARKLINE_POINTS)..];
+    let min = points.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = points.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    points.iter()
+        .map(|p| {
+            if max > min {
+                BLOCKS[(((p - min) / (max - min)) * 7.0).round() as usize]
+            } else {
+                BLOCKS[3] // Flat line
+            }
+        })
+        .collect()
+}
+/// Date of the `n`th Sunday of a month, for the US DST switch dates.
+fn nth_sunday(year: i32, month: Month, n: u8) -> Date {
+    let first = Date::from_calendar_date(year, month, 1).expect("day 1 is always valid");
+    let offset = (7 - first.weekday().number_days_from_sunday()) % 7 + 7 * (n - 1);
+    first + time::Duration::days(offset.into())
+}
+
+/// Whether US equities are in regular trading hours (9:30-16:00 New York time, weekdays).
+/// Worked out locally so the bar doesn't spend an API call on it. Exchange holidays are
+/// not tracked, so those read as open.
+fn us_market_open(now: OffsetDateTime) -> bool {
+    let now = now.to_offset(UtcOffset::UTC);
+    // DST runs from 2:00 local on the second Sunday of March (07:00 UTC) to 2:00 local on
+    // the first Sunday of November (06:00 UTC).
+    let dst_start = PrimitiveDateTime::new(nth_sunday(now.year(), Month::March, 2), time!(7:00)).assume_utc();
+    let dst_end = PrimitiveDateTime::new(nth_sunday(now.year(), Month::November, 1), time!(6:00)).assume_utc();
+    let hours = if now >= dst_start && now < dst_end { -4 } else { -5 };
+    let Ok(offset) = UtcOffset::from_hms(hours, 0, 0) else { return false };
+    let local = now.to_offset(offset);
+    !matches!(local.weekday(), Weekday::Saturday | Weekday::Sunday)
+        && (time!(9:30)..time!(16:00)).contains(&local.time())
+}
 /// Uses the Finnhub API to fetch real-time stock quotes for all symbols
 /// Outputs the data in Waybar-compatible JSON format.
 pub async fn run_waybar_mode(client: &reqwest::Client) -> Result<()> {
@@ -244,22 +461,85 @@ pub async fn run_waybar_mode(client: &reqwest::Client) -> Result<()> {
         }
     };
 
-    let futures: Vec<_> = config.stocks
-        .iter().filter(|s| s.sidebar)
+    let symbols = config.sidebar_symbols();
+    let fx = crate::fx::load(client, config.base_currency.as_deref(), &symbols).await;
+
+    // Only symbols without a fresh cached quote hit Finnhub, and none do while backing off.
+    let mut cache = quotes::read_cache();
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    let ttl = config.cache_ttl_secs.unwrap_or(quotes::DEFAULT_TTL_SECS);
+    let mut throttled = cache.is_rate_limited(now);
+    if throttled {
+        log::info!("Backing off from the Finnhub rate limit, serving cached quotes");
+    }
+    let (fresh, stale_symbols): (Vec<&String>, Vec<&String>) =
+        symbols.iter().partition(|s| throttled || cache.is_fresh(s, now, ttl));
+    log::debug!("Cached quotes (ttl {}s): {:?}", ttl, fresh);
+    log::debug!("Fetching quotes: {:?}", stale_symbols);
+    let futures: Vec<_> = stale_symbols
+        .into_iter()
         .map(|s| {
             let client = client.clone();
             let key = api_key.clone();
-            let sym = s.symbol.clone();
+            let sym = s.clone();
+            let spark = config.show_sparkline;
             async move {
                 let q = fetch_quote(&client, &sym, &key).await;
-                (sym, q)
+                // A failed sparkline just keeps the cached one
+                let closes = if spark { fetch_intraday(&sym).await.ok() } else { None };
+                (sym, (q, closes))
             }
         }).collect();
 
-    let results = join_all(futures).await;
+    let mut fetched: HashMap<String, (Result<FinnhubQuote>, Option<Vec<f64>>)> = join_all(futures).await.into_iter().collect();
+    let mut cache_changed = !fetched.is_empty();
     let mut text_parts = Vec::new();
     let mut tooltip_parts = Vec::new();
-    for (symbol, result) in results {
+    for symbol in symbols {
+        // Fresh -> cached quote. Fetched -> store it. Failed -> fall back to the stale one.
+        let (result, stale) = match fetched.remove(&symbol) {
+            None if throttled => match cache.get(&symbol) {
+                Some(quote) => (Ok(quote), !cache.is_fresh(&symbol, now, ttl)),
+                None => (Err(RateLimited.into()), false),
+            },
+            None => (cache.get(&symbol).context("Quote missing from cache"), false),
+            Some((Ok(quote), closes)) => {
+                cache.store(&symbol, &quote, now);
+                if let Some(closes) = closes {
+                    cache.store_closes(&symbol, closes);
+                }
+                (Ok(quote), false)
+            }
+            Some((Err(e), _)) => {
+                if is_rate_limited(&e) && !throttled {
+                    log::warn!("Finnhub rate limit reached, backing off for {}s", quotes::RATE_LIMIT_BACKOFF_SECS);
+                    cache.set_rate_limited(now);
+                    throttled = true;
+                }
+                match cache.get(&symbol) {
+                    Some(quote) => {
+                        log::debug!("{}: quote failed, using the cached one: {}", symbol, e);
+                        (Ok(quote), true)
+                    }
+                    None => {
+                        log::warn!("{}: quote failed: {}", symbol, e);
+                        (Err(e), false)
+                    }
+                }
+            }
+        };
+        // Alerts only look at live quotes; a stale price can't have "just" crossed anything.
+        if let Ok(quote) = &result
+            && !stale
+            && let Some(alert) = config.alerts.iter().find(|a| a.symbol == symbol || a.symbol == display_symbol(&symbol)) {
+                let past = quote.percent.abs() >= alert.percent.abs();
+                if cache.update_alert(&symbol, past) {
+                    cache_changed = true;
+                    if past {
+                        notify_alert(&symbol, quote, alert, &fx);
+                    }
+                }
+            }
         match result {
             Ok(quote) => {
                 let (color, icon) = if quote.percent >= 0.0 {
@@ -267,48 +547,136 @@ pub async fn run_waybar_mode(client: &reqwest::Client) -> Result<()> {
                 } else {
                     ("#f38ba8", "")
                 };
-                let part = format!(
+                let mut part = format!(
                     "<span color='{}'>{} {:.2} {}</span>",
-                    color, symbol, quote.price, icon
+                    color, display_symbol(&symbol), fx.convert(&symbol, quote.price), icon
                 );
+                let closes = cache.closes(&symbol);
+                if config.show_sparkline && !closes.is_empty() {
+                    part.push_str(&format!(" <span color='{}'>{}</span>", color, sparkline(closes)));
+                }
                 text_parts.push(part);
                 tooltip_parts.push(format!(
-                    "<span color='{}'>{}: ${:.2} ({:.2}%)</span>", 
-                    color, symbol, quote.price, quote.percent
+                    "<span color='{}'>{}: {} ({:.2}%){}</span>", 
+                    color, display_symbol(&symbol), fx.format(&symbol, quote.price), quote.percent,
+                    if stale { " (cached)" } else { "" }
                 ));
             }
+            Err(e) if is_rate_limited(&e) => {
+                text_parts.push(format!("<span color='#f9e2af'>{} ⏳</span>", display_symbol(&symbol)));
+            }
             Err(_) => {
-                text_parts.push(format!("<span color='#6c7086'>{} ???</span>", symbol));
+                text_parts.push(format!("<span color='#6c7086'>{} ???</span>", display_symbol(&symbol)));
             }
         }
     }
+    if cache_changed && let Err(e) = quotes::write_cache(&cache) {
+        log::warn!("Quote cache: {}", e);
+    }
+    // Outside trading hours Finnhub keeps serving the last session's close
+    let market_open = us_market_open(OffsetDateTime::now_utc());
+    tooltip_parts.push(if market_open {
+        "🟢 Market open".to_string()
+    } else {
+        "🔴 Market closed (prices from the last session)".to_string()
+    });
+    if throttled {
+        tooltip_parts.push("⏳ Finnhub rate limit reached, showing cached prices".to_string());
+    }
+    let class = if throttled {
+        "rate-limited"
+    } else if market_open {
+        "finance"
+    } else {
+        "market-closed"
+    };
     let output = WaybarOutput {
         text: text_parts.join(" "),
         tooltip: tooltip_parts.join("\n"),
-        class: "finance".to_string(),
+        class: class.to_string(),
     };
     println!("{}", serde_json::to_string(&output)?);
     Ok(())
 }
+/// Looks up the trading currency of each symbol (e.g. "USD", "EUR", "GBp") in one batch request.
+pub async fn fetch_currencies(client: &reqwest::Client, symbols: &[String]) -> Result<HashMap<String, String>> {
+    if symbols.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let data: YahooQuoteResponse = if let Some(data) = FIXTURES.load_json(&fixtures::name(&["currencies", &symbols.join(",")])) {
+        data?
+    } else {
+        let crumb = get_yahoo_crumb(client).await?;
+        let url = format!(
+            "https://query1.finance.yahoo.com/v7/finance/quote?symbols={}&crumb={}",
+            symbols.join(","), crumb
+        );
+        let resp = client.get(&url).send().await?;
+        if !resp.status().is_success() {
+            return Err(anyhow::anyhow!("Currency lookup failed: {}", resp.status()));
+        }
+        resp.json().await?
+    };
+    Ok(data.quote_response.result
+        .into_iter()
+        .filter_map(|q| q.currency.map(|c| (q.symbol, c)))
+        .collect())
+}
+
+/// Fetches FX rates into `base` for each currency using Yahoo's `{FROM}{TO}=X` pairs.
+/// Returns a map of currency -> multiplier (1 unit of currency = rate units of base).
+pub async fn fetch_fx_rates(client: &reqwest::Client, currencies: &[String], base: &str) -> Result<HashMap<String, f64>> {
+    if currencies.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let pairs: Vec<String> = currencies.iter().map(|c| format!("{}{}=X", c, base)).collect();
+    let data: YahooQuoteResponse = if let Some(data) = FIXTURES.load_json(&fixtures::name(&["fx", &pairs.join(",")])) {
+        data?
+    } else {
+        let crumb = get_yahoo_crumb(client).await?;
+        let url = format!(
+            "https://query1.finance.yahoo.com/v7/finance/quote?symbols={}&crumb={}",
+            pairs.join(","), crumb
+        );
+        let resp = client.get(&url).send().await?;
+        if !resp.status().is_success() {
+            return Err(anyhow::anyhow!("FX lookup failed: {}", resp.status()));
+        }
+        resp.json().await?
+    };
+    Ok(data.quote_response.result
+        .into_iter()
+        .filter_map(|q| {
+            let rate = q.regular_market_price?;
+            let from = q.symbol.strip_suffix("=X")?.get(..3)?.to_string();
+            Some((from, rate))
+        })
+        .collect())
+}
+
 /// Fetches market status including yields for 10Y, 5Y, and 3M Treasuries from Yahoo Finance.
 /// Used for displaying yield data and yield curve in app's top banner.
 pub async fn fetch_market_status(client: &reqwest::Client) -> Result<MarketStatus> {
-    // 1. Get Crumb
-    let crumb = get_yahoo_crumb(client).await?;
-
-    // 2. Batch Request
-    let url = format!(
-        "https://query1.finance.yahoo.com/v7/finance/quote?symbols=^TNX,^FVX,^IRX&crumb={}",
-        crumb
-    );
-
-    let resp = client.get(&url).send().await?;
-    
-    if !resp.status().is_success() {
-        return Err(anyhow::anyhow!("Yields Error: {}", resp.status()));
-    }
+    let data: YahooQuoteResponse = if let Some(data) = FIXTURES.load_json(&fixtures::name(&["market"])) {
+        data?
+    } else {
+        // 1. Get Crumb
+        let crumb = get_yahoo_crumb(client).await?;
+
+        // 2. Batch Request
+        let url = format!(
+            "https://query1.finance.yahoo.com/v7/finance/quote?symbols=^TNX,^FVX,^IRX&crumb={}",
+            crumb
+        );
+
+        let resp = client.get(&url).send().await?;
+        
+        if !resp.status().is_success() {
+            return Err(anyhow::anyhow!("Yields Error: {}", resp.status()));
+        }
 
-    let data: YahooQuoteResponse = resp.json().await?;
+        resp.json().await?
+    };
     let results = data.quote_response.result;
 
     // 3. Map results
@@ -333,3 +701,49 @@ pub async fn fetch_market_status(client: &reqwest::Client) -> Result<MarketStatu
         yield_3m: y3m,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{date, datetime};
+
+    #[test]
+    fn nth_sunday_finds_dst_switch_days() {
+        assert_eq!(nth_sunday(2025, Month::March, 2), date!(2025-03-09));
+        assert_eq!(nth_sunday(2025, Month::November, 1), date!(2025-11-02));
+        // Both months start on a Sunday in 2026, so that day itself counts as the first
+        assert_eq!(nth_sunday(2026, Month::March, 1), date!(2026-03-01));
+        assert_eq!(nth_sunday(2026, Month::March, 2), date!(2026-03-08));
+        assert_eq!(nth_sunday(2026, Month::November, 1), date!(2026-11-01));
+    }
+
+    #[test]
+    fn market_hours_follow_dst() {
+        // Friday before the March switch: still EST (UTC-5)
+        assert!(!us_market_open(datetime!(2026-03-06 14:00 UTC)));
+        assert!(us_market_open(datetime!(2026-03-06 14:30 UTC)));
+        // Monday after it: EDT (UTC-4), so 13:45 UTC is 9:45 and open
+        assert!(us_market_open(datetime!(2026-03-09 13:45 UTC)));
+        assert!(!us_market_open(datetime!(2026-03-09 20:00 UTC)));
+        // Friday before the November switch: still EDT
+        assert!(us_market_open(datetime!(2026-10-30 13:30 UTC)));
+        // Monday after it: back on EST, so 14:00 UTC is 9:00 and closed
+        assert!(!us_market_open(datetime!(2026-11-02 14:00 UTC)));
+        assert!(us_market_open(datetime!(2026-11-02 20:59 UTC)));
+        assert!(!us_market_open(datetime!(2026-11-02 21:00 UTC)));
+    }
+
+    #[test]
+    fn market_closed_on_weekends_in_new_york_time() {
+        assert!(!us_market_open(datetime!(2026-01-10 15:00 UTC)));
+        assert!(!us_market_open(datetime!(2026-01-11 15:00 UTC)));
+        // Other timezones are converted first: this is a Saturday in Tokyo but Friday 10:00 in New York
+        assert!(us_market_open(datetime!(2026-01-10 00:00 +9)));
+    }
+
+    #[test]
+    fn holidays_are_not_tracked() {
+        // Christmas 2026 is a Friday; the bar shows it as a regular session
+        assert!(us_market_open(datetime!(2026-12-25 15:00 UTC)));
+    }
+}
@@ -0,0 +1,112 @@
+//! Short-lived quote cache for Waybar mode.
+//!
+//! Every Waybar restart re-runs us and fires one Finnhub request per symbol, which the
+//! free tier rate-limits. Quotes are kept in `~/.cache/finance_quotes.json` with their
+//! fetch time; fresh ones are served without touching the network, and stale ones are
+//! still shown when a refresh fails. The cache also remembers which price alerts are
+//! currently tripped, so a notification fires once per crossing rather than every poll.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use anyhow::{Result, Context};
+use serde::{Serialize, Deserialize};
+use crate::network::FinnhubQuote;
+
+/// A bit under Waybar's 60s interval, so regular polls still get a fresh price.
+pub const DEFAULT_TTL_SECS: u64 = 50;
+/// Finnhub's free tier counts calls per minute.
+pub const RATE_LIMIT_BACKOFF_SECS: i64 = 60;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct CachedQuote {
+    price: f64,
+    percent: f64,
+    fetched_at: i64, // Unix timestamp
+    #[serde(default)]
+    closes: Vec<f64>, // Intraday closes for the sparkline (show_sparkline only)
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct QuoteCache {
+    quotes: HashMap<String, CachedQuote>,
+    #[serde(default)]
+    alerted: HashSet<String>, // Symbols whose alert threshold was past on the last run
+    #[serde(default)]
+    rate_limited_until: Option<i64>, // Unix timestamp; no Finnhub requests before it
+}
+
+fn get_cache_path() -> Result<PathBuf> {
+    let cache_dir = dirs::cache_dir().context("Could not find cache directory")?;
+    Ok(cache_dir.join("finance_quotes.json"))
+}
+
+/// Missing or corrupt caches start empty.
+pub fn read_cache() -> QuoteCache {
+    get_cache_path().ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+pub fn write_cache(cache: &QuoteCache) -> Result<()> {
+    let path = get_cache_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("Failed to create cache directory")?;
+    }
+    fs::write(path, serde_json::to_string(cache)?).context("Failed to write quote cache")?;
+    Ok(())
+}
+
+impl QuoteCache {
+    pub fn is_fresh(&self, symbol: &str, now: i64, ttl_secs: u64) -> bool {
+        self.quotes.get(symbol)
+            .is_some_and(|q| now - q.fetched_at < ttl_secs as i64)
+    }
+
+    pub fn get(&self, symbol: &str) -> Option<FinnhubQuote> {
+        self.quotes.get(symbol).map(|q| FinnhubQuote { price: q.price, percent: q.percent })
+    }
+
+    /// Stores a fresh quote, keeping the previous sparkline until a new one arrives.
+    pub fn store(&mut self, symbol: &str, quote: &FinnhubQuote, now: i64) {
+        let entry = self.quotes.entry(symbol.to_string()).or_insert(CachedQuote {
+            price: 0.0,
+            percent: 0.0,
+            fetched_at: now,
+            closes: Vec::new(),
+        });
+        entry.price = quote.price;
+        entry.percent = quote.percent;
+        entry.fetched_at = now;
+    }
+
+    pub fn store_closes(&mut self, symbol: &str, closes: Vec<f64>) {
+        if let Some(entry) = self.quotes.get_mut(symbol) {
+            entry.closes = closes;
+        }
+    }
+
+    pub fn closes(&self, symbol: &str) -> &[f64] {
+        self.quotes.get(symbol).map(|q| q.closes.as_slice()).unwrap_or_default()
+    }
+
+    /// True while backing off from a Finnhub rate limit.
+    pub fn is_rate_limited(&self, now: i64) -> bool {
+        self.rate_limited_until.is_some_and(|until| now < until)
+    }
+
+    pub fn set_rate_limited(&mut self, now: i64) {
+        self.rate_limited_until = Some(now + RATE_LIMIT_BACKOFF_SECS);
+    }
+
+    /// Records whether `symbol` is past its alert threshold.
+    /// Returns true if that changed since the last run.
+    pub fn update_alert(&mut self, symbol: &str, past: bool) -> bool {
+        if past {
+            self.alerted.insert(symbol.to_string())
+        } else {
+            self.alerted.remove(symbol)
+        }
+    }
+}
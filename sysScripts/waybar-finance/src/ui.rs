@@ -13,8 +13,9 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
-use crate::app::{App, InputMode, StockDetails, MarketStatus};
-use crate::network::{FinnhubQuote, YahooSearchResult};
+use crate::app::{App, ChartPeriod, InputMode, StockDetails, MarketStatus};
+use crate::network::{display_symbol, is_crypto, FinnhubQuote, YahooSearchResult};
+use crate::fx::Converter;
 
 /// Internal events for the application event loop.
 pub enum AppEvent {
@@ -24,6 +25,7 @@ pub enum AppEvent {
     Input(crossterm::event::Event),
     SearchResultsFetched(Vec<YahooSearchResult>),
     MarketFetched(Result<MarketStatus>),
+    FxLoaded(Converter),
     SaveConfig,
     Tick,
 }
@@ -102,6 +104,8 @@ pub async fn run_tui(client: &reqwest::Client, app: &mut App) -> Result<()> {
             }
         }
     });
+    // Exchange rates for base_currency (cached daily, so this is usually instant)
+    app.trigger_fx_load(&tx, client);
     // Wrapped in a block so cleanup always runs
     let result = async {
         loop {
@@ -119,11 +123,18 @@ pub async fn run_tui(client: &reqwest::Client, app: &mut App) -> Result<()> {
                     AppEvent::MarketFetched(Ok(status)) => {
                         app.market_status = Some(status);
                     }
+                    AppEvent::FxLoaded(fx) => {
+                        app.fx = fx;
+                    }
                     AppEvent::QuoteFetched(sym, Ok(q)) => {
-                        app.current_quote = Some(q);
                         app.message = format!("Updated {}", sym);
+                        app.current_quote = Some((sym, q));
                         app.message_color = Color::Cyan;
                     }
+                    AppEvent::QuoteFetched(sym, Err(e)) if crate::network::is_rate_limited(&e) => {
+                        app.message = format!("⏳ Rate limited, {} not updated. Try again in a minute.", sym);
+                        app.message_color = Color::Yellow;
+                    }
                     AppEvent::QuoteFetched(sym, Err(e)) => {
                         app.message = format!("Failed to fetch quote for {}: {}", sym, e);
                         app.message_color = Color::Red;
@@ -132,22 +143,22 @@ pub async fn run_tui(client: &reqwest::Client, app: &mut App) -> Result<()> {
                         app.search_results = results;
                         app.search_state.select(if app.search_results.is_empty() { None } else { Some(0) });
                     }
-                    AppEvent::Input(crossterm::event::Event::Key(key)) => {
-                        if key.kind == KeyEventKind::Press {
-                            handle_keys(app, key.code, &tx, &search_tx, client).await;
-                        }
+                    AppEvent::Input(crossterm::event::Event::Key(key))
+                        if key.kind == KeyEventKind::Press =>
+                    {
+                        handle_keys(app, key.code, &tx, &search_tx, client).await;
                     }
                     AppEvent::HistoryFetched(sym, Ok(h)) => {
-                        app.stock_history = Some(h);
                         app.message = format!("Loaded history for {}", sym);
+                        app.stock_history = Some((sym, h));
                     }
                     AppEvent::HistoryFetched(sym, Err(e)) => {
                         app.message = format!("Failed to fetch history for {}: {}", sym, e);
                         app.message_color = Color::Red;
                     }
                     AppEvent::DetailsFetched(sym, Ok(d)) => {
-                        app.details = Some(d);
                         app.message = format!("Loaded details for {}", sym);
+                        app.details = Some((sym, d));
                     }
                     AppEvent::DetailsFetched(sym, Err(e)) => {
                         app.message = format!("Failed to fetch details for {}: {}", sym, e);
@@ -201,6 +212,15 @@ async fn handle_keys(
             }
             KeyCode::Up => app.previous(),
             KeyCode::Down => app.next(),
+            KeyCode::Tab | KeyCode::BackTab => {
+                app.cycle_watchlist(if code == KeyCode::Tab { 1 } else { -1 });
+                app.trigger_fx_load(tx, client);
+            }
+            KeyCode::Char(c) => {
+                if let Some(period) = ChartPeriod::from_key(c) {
+                    app.set_chart_period(period, tx, client);
+                }
+            }
             _ => {}
         },
         InputMode::Editing => match code {
@@ -234,31 +254,53 @@ async fn handle_keys(
         }
     }
 }
-/// TUI layout helper: Create a centered rectangle with given percentage width and height
+/// TUI layout helper: Create a centered rectangle with given percentage width and height.
+/// Percentages are clamped and the math is done on absolute cells, so out-of-range input
+/// or a zero-sized area yields a smaller rect instead of underflowing.
 pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let percent_x = percent_x.clamp(0, 100);
-    let percent_y = percent_y.clamp(0, 100);
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(r);
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(popup_layout[1])[1]
+    let percent_x = u32::from(percent_x.min(100));
+    let percent_y = u32::from(percent_y.min(100));
+    let width = (u32::from(r.width) * percent_x / 100) as u16;
+    let height = (u32::from(r.height) * percent_y / 100) as u16;
+    Rect {
+        x: r.x + (r.width - width) / 2,
+        y: r.y + (r.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+/// Below this size the three-column layout and popups collapse into overlapping garbage,
+/// so we show a notice instead of rendering the dashboard.
+const MIN_WIDTH: u16 = 60;
+const MIN_HEIGHT: u16 = 15;
+
+/// Renders the "terminal too small" notice. Returns true if the frame was too small.
+fn render_too_small(frame: &mut ratatui::Frame) -> bool {
+    let area = frame.area();
+    if area.width >= MIN_WIDTH && area.height >= MIN_HEIGHT {
+        return false;
+    }
+    let text = format!(
+        "Terminal too small\n{}x{} (need {}x{})",
+        area.width, area.height, MIN_WIDTH, MIN_HEIGHT
+    );
+    let notice = Paragraph::new(text)
+        .style(Style::default().fg(Color::Yellow))
+        .alignment(ratatui::layout::Alignment::Center)
+        .wrap(ratatui::widgets::Wrap { trim: true });
+    // Vertically center when there is room for it; otherwise start at the top.
+    let notice_area = centered_rect(100, 50, area);
+    frame.render_widget(notice, if notice_area.height >= 4 { notice_area } else { area });
+    true
 }
 
 /// Renders the UI widgets using Ratatui.
 /// Uses a nested layout strategy (Vertical -> Horizontal -> Inner).
 pub fn ui(frame: &mut ratatui::Frame, app: &mut App) {
+    if render_too_small(frame) {
+        return;
+    }
     //verticle split for (banner | main | footer)
     let main_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -288,17 +330,25 @@ pub fn ui(frame: &mut ratatui::Frame, app: &mut App) {
         .stocks
         .iter()
         .map(|s| {
-            let line = if s.sidebar {
-                Line::from(vec![Span::raw("👁️ "), Span::raw(s.symbol.as_str())])
+            let mut spans = if s.sidebar {
+                vec![Span::raw("👁️ "), Span::raw(display_symbol(&s.symbol))]
             } else {
-                Line::from(vec![Span::raw("   "), Span::raw(s.symbol.as_str())])
+                vec![Span::raw("   "), Span::raw(display_symbol(&s.symbol))]
             };
-            line.into()
+            if is_crypto(&s.symbol) {
+                spans.push(Span::styled(" crypto", Style::default().fg(Color::DarkGray)));
+            }
+            Line::from(spans).into()
         })
         .collect();
+    let title = if app.watchlists.len() > 1 {
+        format!("Watchlist: {} ({}/{})", app.watchlist_name(), app.active_list + 1, app.watchlists.len())
+    } else {
+        "Watchlist".to_string()
+    };
     let list = List::new(watchlist)
         .block(Block::default()
-            .title("Watchlist")
+            .title(title)
             .borders(Borders::ALL))
         .highlight_style(Style::default().bg(Color::Blue))
         .highlight_symbol(">> ");
@@ -320,7 +370,12 @@ pub fn ui(frame: &mut ratatui::Frame, app: &mut App) {
     } else {
         frame.render_widget(Paragraph::new("Loading Market Data...").style(Style::default().fg(Color::DarkGray)), main_layout[0]);
     }
-    if let Some(history) = &app.stock_history {
+    // Prices are converted at render time so switching currency never needs a refetch.
+    // Each panel converts with the symbol its data belongs to, not the current selection.
+    if let Some((symbol, raw_history)) = &app.stock_history {
+        let history: Vec<(f64, f64)> = raw_history.iter()
+            .map(|(x, y)| (*x, app.fx.convert(symbol, *y)))
+            .collect();
         let first_price = history[0].1;
         let last_price = history.last().unwrap().1;
         let start_ts = history[0].0 as i64;
@@ -339,14 +394,14 @@ pub fn ui(frame: &mut ratatui::Frame, app: &mut App) {
                 .marker(ratatui::symbols::Marker::Braille)
                 .graph_type(GraphType::Line)
                 .style(Style::default().fg(chart_color))
-                .data(history),
+                .data(&history),
         ];
         //Find y axis bounds 
         let min_price = history.iter().map(|(_, y)| *y).fold(f64::INFINITY, |a, b| a.min(b));
         let max_price = history.iter().map(|(_, y)| *y).fold(f64::NEG_INFINITY, |a, b| a.max(b));
         //Create the chart
         let chart = Chart::new(datasets)
-            .block(Block::default().title("1 Year History").borders(Borders::ALL))
+            .block(Block::default().title(app.chart_period.title()).borders(Borders::ALL))
             .x_axis(Axis::default()
                 .title("Date")
                 .style(Style::default().fg(Color::Gray))
@@ -390,28 +445,28 @@ pub fn ui(frame: &mut ratatui::Frame, app: &mut App) {
         ])
         .split(details_area);
 
-    if let Some(details) = &app.details {
+    if let Some((symbol, details)) = &app.details {
         // Helper for N/A
         let fmt_num = |opt: Option<f64>, suffix: &str| -> String {
             opt.map(|v| format!("{:.2}{}", v, suffix)).unwrap_or("N/A".to_string())
         };
 
         // COLUMN 1: Price Action
-        let price_str = if let Some(q) = &app.current_quote {
-            format!("${:.2}", q.price)
+        let price_str = if let Some((quote_symbol, q)) = &app.current_quote {
+            app.fx.format(quote_symbol, q.price)
         } else {
             "N/A".to_string()
         };
 
         let col1_text = vec![
             Line::from(vec![Span::styled("Price:    ", Style::default().fg(Color::Gray)), Span::raw(price_str)]),
-            Line::from(vec![Span::styled("52W High: ", Style::default().fg(Color::Gray)), Span::styled(format!("${:.2}", details.high_52w), Style::default().fg(Color::Green))]),
-            Line::from(vec![Span::styled("52W Low:  ", Style::default().fg(Color::Gray)), Span::styled(format!("${:.2}", details.low_52w), Style::default().fg(Color::Red))]),
+            Line::from(vec![Span::styled("52W High: ", Style::default().fg(Color::Gray)), Span::styled(app.fx.format(symbol, details.high_52w), Style::default().fg(Color::Green))]),
+            Line::from(vec![Span::styled("52W Low:  ", Style::default().fg(Color::Gray)), Span::styled(app.fx.format(symbol, details.low_52w), Style::default().fg(Color::Red))]),
         ];
 
         // COLUMN 2: Valuation
         let col2_text = vec![
-            Line::from(vec![Span::styled("Mkt Cap:  ", Style::default().fg(Color::Gray)), Span::raw(format!("${:.2}B", details.market_cap as f64 / 1_000_000_000.0))]), // Billions
+            Line::from(vec![Span::styled("Mkt Cap:  ", Style::default().fg(Color::Gray)), Span::raw(format!("{}B", app.fx.format(symbol, details.market_cap as f64 / 1_000_000_000.0)))]), // Billions
             Line::from(vec![Span::styled("P/E Ratio:", Style::default().fg(Color::Gray)), Span::raw(fmt_num(details.pe_ratio, ""))]),
             Line::from(vec![Span::styled("Div Yield:", Style::default().fg(Color::Gray)), Span::raw(fmt_num(details.dividend_yield, "%"))]),
         ];
@@ -499,7 +554,7 @@ pub fn ui(frame: &mut ratatui::Frame, app: &mut App) {
 
     // 2. Key Hints (Right, Right-Aligned)
     let hints_text = match app.input_mode {
-        InputMode::Normal => "q:Quit  a:Add  d:Del  s:toggle sidebar view  ↓/↑:Nav  Enter:Select",
+        InputMode::Normal => "q:Quit  a:Add  d:Del  s:toggle sidebar view  Tab:list  ↓/↑:Nav  Enter:Select  1/3/6/y/5:Range",
         InputMode::Editing => "Enter:Confirm  Esc:Cancel",
         InputMode::KeyEntry => "Enter:Save  Esc:Quit",
     };
@@ -1,7 +1,7 @@
 //! Waybar Configuration Switcher
 //!
 //! A system utility that automatically detects the running Wayland compositor
-//! (Niri, Hyprland, or Sway) and hot-swaps the corresponding Waybar configuration file.
+//! (Niri, Sway, River, or Gnome) and hot-swaps the corresponding Waybar configuration file.
 //!
 //! This solves the problem of using a single status bar across multiple window managers
 //! where layout requirements (modules, workspaces) differ significantly.
@@ -33,6 +33,22 @@ struct WaybarSwitcherConfig {
     target_file: String, // The active config file read by Waybar
     niri_config: String, // Source file for Niri
     sway_config: String, // Source file for Sway
+    #[serde(default)]
+    river_config: Option<String>, // Source file for River (optional)
+    #[serde(default)]
+    gnome_config: Option<String>, // Source file for Gnome (optional)
+    #[serde(default)]
+    reload_mode: ReloadMode, // How the running Waybar picks up the new file
+}
+
+/// `restart` kills and respawns Waybar; `signal` sends SIGUSR2 so it reloads in place
+/// (no flicker, keeps runtime state).
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum ReloadMode {
+    #[default]
+    Restart,
+    Signal,
 }
 
 #[derive(Deserialize, Debug)]
@@ -82,23 +98,60 @@ fn get_compositor() -> Option<String> {
         if desktop.contains("sway") {
             return Some("sway".to_string());
         }
+        if desktop.contains("river") {
+            return Some("river".to_string());
+        }
+        // Gnome reports e.g. "GNOME" or "ubuntu:GNOME"
+        if desktop.contains("gnome") {
+            return Some("gnome".to_string());
+        }
+    }
+    // River has no IPC socket variable; some launchers only mark it in the session
+    // name or the Wayland socket name.
+    let river_hint = ["XDG_SESSION_DESKTOP", "DESKTOP_SESSION", "WAYLAND_DISPLAY"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .any(|v| v.to_lowercase().contains("river"));
+    if river_hint {
+        return Some("river".to_string());
     }
     None
 }
 fn main() -> Result<()> {
+    // `--quiet` drops the progress lines so this can run from keybinds/scripts silently.
+    let quiet = std::env::args().any(|arg| arg == "--quiet");
     // 1. Load User Preferences
     let global_config = load_config()?;
     let config = global_config.waybar_switcher;
     //2.Detect Environment
     let compositor = get_compositor().unwrap_or_else(|| "unknown".to_string());
-    println!("Detected compositor: {}", compositor);
+    if !quiet {
+        println!("Detected compositor: {}", compositor);
+    }
     // 3. Select Config Source
     // I map the detected environment to the specific source file defined in config.toml.
-    let source_path_str = match compositor.as_str() {
-        "niri" => &config.niri_config,
-        "sway" => &config.sway_config,
+    let source_path_str = match (
+        compositor.as_str(),
+        &config.river_config,
+        &config.gnome_config,
+    ) {
+        ("niri", _, _) => &config.niri_config,
+        ("sway", _, _) => &config.sway_config,
+        ("river", Some(river), _) => river,
+        ("gnome", _, Some(gnome)) => gnome,
+        ("river" | "gnome", _, _) => {
+            eprintln!(
+                "Warning: no {}_config set in [waybar_switcher], defaulting to Niri config.",
+                compositor
+            );
+            &config.niri_config
+        }
         _ => {
-            println!("Unknown compositor, defaulting to Niri config.");
+            // Always warn (even with --quiet): the bar is probably about to look wrong.
+            eprintln!(
+                "Warning: could not detect the compositor (checked NIRI_SOCKET, SWAYSOCK, \
+                 XDG_CURRENT_DESKTOP, XDG_SESSION_DESKTOP), defaulting to Niri config."
+            );
             &config.niri_config
         }
     };
@@ -106,10 +159,12 @@ fn main() -> Result<()> {
     let source_path = expand_path(source_path_str);
     let target_path = expand_path(&config.target_file);
 
-    println!(
-        "Copying config:\n  From: {:?}\n  To:   {:?}",
-        source_path, target_path
-    );
+    if !quiet {
+        println!(
+            "Copying config:\n  From: {:?}\n  To:   {:?}",
+            source_path, target_path
+        );
+    }
 
     // 4. Overwrite Active Configuration
     // We overwrite the target file rather than symlinking to avoid issues
@@ -117,8 +172,30 @@ fn main() -> Result<()> {
     fs::copy(&source_path, &target_path)
         .with_context(|| format!("Failed to copy {:?} to {:?}", source_path, target_path))?;
 
-    // 5. Restart Waybar Process
-    println!("Restarting Waybar...");
+    // 5a. Reload in place (signal mode)
+    // pkill exits non-zero when nothing matched, i.e. no Waybar to reload: restart instead.
+    if config.reload_mode == ReloadMode::Signal {
+        let signalled = Command::new("pkill")
+            .arg("-SIGUSR2")
+            .arg("-x")
+            .arg("waybar")
+            .status()
+            .is_ok_and(|s| s.success());
+        if signalled {
+            if !quiet {
+                println!("Waybar reloaded (SIGUSR2).");
+            }
+            return Ok(());
+        }
+        if !quiet {
+            println!("No running Waybar to signal, starting one...");
+        }
+    }
+
+    // 5b. Restart Waybar Process
+    if !quiet {
+        println!("Restarting Waybar...");
+    }
     // Kill existing instances to prevent duplicates or zombie processes.
     // We ignore the result because it fails if Waybar isn't running, which is fine.
     let _ = Command::new("pkill").arg("-x").arg("waybar").status();
@@ -131,6 +208,8 @@ fn main() -> Result<()> {
         .spawn()
         .context("Failed to spawn new waybar process")?;
 
-    println!("Waybar restarted successfully.");
+    if !quiet {
+        println!("Waybar restarted successfully.");
+    }
     Ok(())
 }
@@ -1,33 +1,185 @@
 //! Waybar Weather Module
 //! 
 //! A focused, asynchronous utility that:
-//! 1. Determines the user's geolocation using `geoclue` (via the `where-am-i` utility).
+//! 1. Determines the user's geolocation using `geoclue` (via the `where-am-i` utility),
+//!    unless fixed coordinates are configured in `[waybar_weather.location]`.
 //! 2. Caches location data to minimize GPS polling latency on subsequent runs.
-//! 3. Fetches real-time weather and forecast data from OpenWeatherMap.
-//! 4. Performs reverse geocoding via OpenStreetMap (Nominatim) to display city/state.
+//! 3. Fetches real-time weather and forecast data from OpenWeatherMap (or keyless Open-Meteo).
+//! 4. Performs reverse geocoding via OpenStreetMap (Nominatim) to display city/state,
+//!    cached until the location moves, per Nominatim's usage policy.
 //! 5. Outputs a JSON payload formatted for Waybar custom modules, including Pango markup for tooltips.
+//!
+//! `--check` runs the same pipeline as a troubleshooting aid: the output is validated and
+//! pretty-printed to stderr, and any failed request (bad API key, no network) exits nonzero.
+//! `-v`/`-vv` log the location strategy and requests to stderr.
 
 use std::fs;
-use std::path::PathBuf;
-use std::time::SystemTime;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, SystemTime};
 use serde::{Deserialize, Serialize};
 use regex::Regex;
 use anyhow::{Context, Result};
-use chrono::{DateTime, FixedOffset};
+use common::fixtures::Fixtures;
+use chrono::{DateTime, FixedOffset, NaiveDate, Timelike};
+use std::collections::BTreeMap;
 use std::sync::OnceLock;
 use tokio::process::Command;
 
+
 // Compile regular expressions once for performance optimization.
 static LAT_RE: OnceLock<Regex> = OnceLock::new();
 static LON_RE: OnceLock<Regex> = OnceLock::new();
 static ACC_RE: OnceLock<Regex> = OnceLock::new();
 static PANGO_RE: OnceLock<Regex> = OnceLock::new();
 
+// Canned responses under `$RUST_DOTFILES_FIXTURES/waybar-weather/` (see docs/FIXTURES.md).
+const FIXTURES: Fixtures = Fixtures::new("waybar-weather");
+
+// Where distros ship geoclue's demo client. Tried in order after `where_am_i_path`.
+const WHERE_AM_I_PATHS: &[&str] = &[
+    "/usr/lib/geoclue-2.0/demos/where-am-i",
+    "/usr/libexec/geoclue-2.0/demos/where-am-i",
+    "/usr/lib64/geoclue-2.0/demos/where-am-i",
+    "/usr/lib/x86_64-linux-gnu/geoclue-2.0/demos/where-am-i",
+    "/usr/lib/aarch64-linux-gnu/geoclue-2.0/demos/where-am-i",
+];
+
 // --- Configuration ---
 
 #[derive(Deserialize, Debug)]
 struct WaybarWeatherConfig {
+    // Not needed when provider = "open-meteo".
+    #[serde(default)]
     owm_api_key: String,
+    // "owm" (OpenWeatherMap, default) or "open-meteo" (no API key).
+    #[serde(default)]
+    provider: Provider,
+    // Overrides the search for geoclue's `where-am-i` demo binary.
+    #[serde(default)]
+    where_am_i_path: Option<String>,
+    // "imperial" (°F, mph), "metric" (°C, m/s) or "standard" (K, m/s).
+    #[serde(default)]
+    units: Units,
+    // Only fixes more accurate than this are written to the location cache.
+    #[serde(default = "default_cache_max_accuracy")]
+    cache_max_accuracy_meters: f64,
+    // A cached fix younger than this is used as-is, skipping geoclue entirely.
+    #[serde(default = "default_cache_max_age")]
+    cache_max_age_secs: u64,
+    // After resume geoclue can take 30s+ to get a fix; give up and use the cache instead.
+    #[serde(default = "default_geoclue_timeout")]
+    geoclue_timeout_secs: u64,
+    // Per-condition glyph overrides for non-Nerd-Font icon sets.
+    #[serde(default)]
+    icons: WeatherIcons,
+    // Fixed coordinates for machines that never move (skips geoclue and the cache).
+    #[serde(default)]
+    location: Option<FixedLocation>,
+    // "Feels like" thresholds (in the configured units) for the "cold"/"hot" CSS classes.
+    // Default to 32/95°F, 0/35°C or 273.15/308.15K depending on `units`.
+    #[serde(default)]
+    cold_threshold: Option<f64>,
+    #[serde(default)]
+    hot_threshold: Option<f64>,
+    // Chance of precipitation (%) in the next few forecast intervals that adds "rainy-soon".
+    #[serde(default = "default_rain_threshold")]
+    rain_threshold: f64,
+}
+/// Optional `[waybar_weather.icons]` table. Any key left out keeps the Nerd Font default.
+#[derive(Deserialize, Debug, Default)]
+struct WeatherIcons {
+    thunderstorm: Option<String>,
+    drizzle: Option<String>,
+    rain: Option<String>,
+    snow: Option<String>,
+    atmosphere: Option<String>,
+    clear_day: Option<String>,
+    clear_night: Option<String>,
+    clouds_day: Option<String>,
+    clouds_night: Option<String>,
+}
+/// Escapes user-provided text for the Pango tooltip.
+fn escape_markup(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+/// Optional `[waybar_weather.location]` table. With a `label` the tooltip shows it instead
+/// of reverse geocoding the coordinates.
+#[derive(Deserialize, Debug)]
+struct FixedLocation {
+    latitude: f64,
+    longitude: f64,
+    #[serde(default)]
+    label: Option<String>,
+}
+fn default_cache_max_accuracy() -> f64 {
+    1500.0
+}
+fn default_cache_max_age() -> u64 {
+    3600
+}
+fn default_geoclue_timeout() -> u64 {
+    8
+}
+fn default_rain_threshold() -> f64 {
+    50.0
+}
+// Forecast intervals checked for "rainy-soon": two 3-hour steps, roughly the next 6 hours.
+const RAIN_SOON_INTERVALS: usize = 2;
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+enum Provider {
+    #[default]
+    #[serde(rename = "owm")]
+    Owm,
+    #[serde(rename = "open-meteo")]
+    OpenMeteo,
+}
+/// OpenWeatherMap unit systems. Imperial is the default so older configs keep working.
+#[derive(Deserialize, Debug, Default, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+enum Units {
+    #[default]
+    Imperial,
+    Metric,
+    Standard,
+}
+impl Units {
+    /// Value of the `units=` query parameter.
+    fn api_param(self) -> &'static str {
+        match self {
+            Units::Imperial => "imperial",
+            Units::Metric => "metric",
+            Units::Standard => "standard",
+        }
+    }
+    fn temp_symbol(self) -> &'static str {
+        match self {
+            Units::Imperial => "°F",
+            Units::Metric => "°C",
+            Units::Standard => "K",
+        }
+    }
+    fn wind_unit(self) -> &'static str {
+        match self {
+            Units::Imperial => "mph",
+            Units::Metric | Units::Standard => "m/s",
+        }
+    }
+    /// Default (cold, hot) "feels like" thresholds in this unit system.
+    fn default_thresholds(self) -> (f64, f64) {
+        match self {
+            Units::Imperial => (32.0, 95.0),
+            Units::Metric => (0.0, 35.0),
+            Units::Standard => (273.15, 308.15),
+        }
+    }
+    /// OWM always reports visibility in meters regardless of `units`.
+    fn visibility(self, meters: f64) -> (f64, &'static str) {
+        match self {
+            Units::Imperial => (meters / 1609.34, "mi"),
+            Units::Metric | Units::Standard => (meters / 1000.0, "km"),
+        }
+    }
 }
 #[derive(Deserialize, Debug)]
 struct GlobalConfig {
@@ -118,36 +270,153 @@ struct ForecastItem {
 struct Forecast {
     list: Vec<ForecastItem>,
 }
+/// One local calendar day folded out of the 3-hour forecast list.
+#[derive(Debug)]
+struct DailySummary {
+    date: NaiveDate,
+    temp_min: f64,
+    temp_max: f64,
+    condition_id: u32, // From the interval closest to local noon
+    pop: f64,          // Highest chance of precipitation that day
+}
+/// Groups forecast intervals by local date (using the location's UTC offset, so day
+/// boundaries match the wall clock there) and returns the days after `today`.
+fn daily_summaries(list: &[ForecastItem], tz: FixedOffset, today: NaiveDate) -> Vec<DailySummary> {
+    // Per day: the summary so far plus how far (in hours) its condition is from noon
+    let mut days: BTreeMap<NaiveDate, (DailySummary, u32)> = BTreeMap::new();
+    for item in list {
+        let Some(dt) = DateTime::from_timestamp(item.dt, 0) else { continue };
+        let Some(weather) = item.weather.first() else { continue };
+        let local = dt.with_timezone(&tz);
+        let date = local.date_naive();
+        if date <= today {
+            continue;
+        }
+        let noon_distance = local.hour().abs_diff(12);
+        let (day, best_distance) = days.entry(date).or_insert_with(|| (
+            DailySummary { date, temp_min: f64::MAX, temp_max: f64::MIN, condition_id: weather.id, pop: 0.0 },
+            noon_distance,
+        ));
+        day.temp_min = day.temp_min.min(item.main.temp_min);
+        day.temp_max = day.temp_max.max(item.main.temp_max);
+        day.pop = day.pop.max(item.pop);
+        if noon_distance < *best_distance {
+            day.condition_id = weather.id;
+            *best_distance = noon_distance;
+        }
+    }
+    days.into_values().map(|(day, _)| day).collect()
+}
 // --- Geolocation Logic ---
 
+/// Resolves the `where-am-i` binary: the configured path first, then the known distro locations.
+fn find_where_am_i(configured: Option<&str>) -> Result<PathBuf> {
+    let configured = configured.map(|p| PathBuf::from(shellexpand::tilde(p).as_ref()));
+    let candidates: Vec<PathBuf> = configured
+        .into_iter()
+        .chain(WHERE_AM_I_PATHS.iter().map(PathBuf::from))
+        .collect();
+    candidates
+        .iter()
+        .find(|p| p.is_file())
+        .cloned()
+        .with_context(|| {
+            let tried: Vec<String> = candidates.iter().map(|p| p.display().to_string()).collect();
+            format!(
+                "geoclue 'where-am-i' not found (tried: {}). Install geoclue or set `where_am_i_path` in [waybar_weather]",
+                tried.join(", ")
+            )
+        })
+}
+
 /// Executes the `where-am-i` system utility to get fresh coordinates.
 /// 
 /// This is preferable to using a raw IP-based geolocation API because:
 /// 1. It uses Wi-Fi triangulation/GPS (more accurate).
 /// 2. It respects system privacy settings via Geoclue.
-async fn run_where_am_i() -> Result<Location> {
-    let output = Command::new("/usr/lib/geoclue-2.0/demos/where-am-i")
-        .output()
+///
+/// The run is bounded by `timeout`; a hung child is killed rather than left running.
+async fn run_where_am_i(binary: &Path, timeout: Duration) -> Result<Location> {
+    let child = Command::new(binary)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to run '{}'", binary.display()))?;
+    // On timeout the wait future (and the child it owns) is dropped, which kills the process.
+    let output = tokio::time::timeout(timeout, child.wait_with_output())
         .await
-        .context("Failed to run 'where-am-i' command, Is geoclue installed?")?;
+        .with_context(|| format!("'where-am-i' timed out after {}s", timeout.as_secs()))?
+        .with_context(|| format!("Failed to run '{}'", binary.display()))?;
     if !output.status.success() {
         anyhow::bail!("'where-am-i' command failed: {}", String::from_utf8_lossy(&output.stderr));
     }
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    parse_where_am_i(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn parse_where_am_i(stdout: &str) -> Result<Location> {
     // Parse the output using regex to extract coordinates and accuracy
     let lat_re = LAT_RE.get_or_init(|| Regex::new(r"Latitude:\s*(-?\d+\.\d+)").unwrap());
     let lon_re = LON_RE.get_or_init(|| Regex::new(r"Longitude:\s*(-?\d+\.\d+)").unwrap());
     let acc_re = ACC_RE.get_or_init(|| Regex::new(r"Accuracy:\s*(\d+\.?\d*)\s*meters").unwrap());
-    let lat_str = lat_re.captures(&stdout).context("Failed to parse Latitude")?[1].to_string();
-    let lon_str = lon_re.captures(&stdout).context("Failed to parse Longitude")?[1].to_string();
-    let acc_str = acc_re.captures(&stdout).context("Failed to parse Accuracy")?[1].to_string();
+    let lat_str = lat_re.captures(stdout).context("Failed to parse Latitude")?[1].to_string();
+    let lon_str = lon_re.captures(stdout).context("Failed to parse Longitude")?[1].to_string();
+    let acc_str = acc_re.captures(stdout).context("Failed to parse Accuracy")?[1].to_string();
     Ok(Location {
         latitude: lat_str.parse()?,
         longitude: lon_str.parse()?,
         accuracy: acc_str.parse()?,
     })
 }
+/// ip-api.com response (only the fields we ask for).
+#[derive(Deserialize, Debug)]
+struct IpApiResponse {
+    status: String,
+    message: Option<String>,
+    lat: Option<f64>,
+    lon: Option<f64>,
+}
+
+// IP geolocation is roughly city-level; reported so the tooltip and cache logic treat it as coarse.
+const IP_LOCATION_ACCURACY: f64 = 25_000.0;
+
+/// Last-resort location from the public IP (ip-api.com, free, no key).
+/// Only used when geoclue and the cache both fail, e.g. first run without a geoclue backend.
+async fn fetch_ip_location(client: &reqwest::Client) -> Result<Location> {
+    let response = match FIXTURES.load_json::<IpApiResponse>("ip-location.json") {
+        Some(fixture) => fixture?,
+        None => client.get("http://ip-api.com/json/?fields=status,message,lat,lon")
+            .send()
+            .await?
+            .json::<IpApiResponse>()
+            .await?,
+    };
+    if response.status != "success" {
+        anyhow::bail!("IP geolocation failed: {}", response.message.unwrap_or(response.status));
+    }
+    Ok(Location {
+        latitude: response.lat.context("IP geolocation returned no latitude")?,
+        longitude: response.lon.context("IP geolocation returned no longitude")?,
+        accuracy: IP_LOCATION_ACCURACY,
+    })
+}
 // --- Cache Management ---
+
+/// A cached fix plus when it was taken. Caches written before the timestamp
+/// existed deserialize with `timestamp: 0` and are simply treated as stale.
+#[derive(Serialize, Deserialize, Debug)]
+struct CachedLocation {
+    #[serde(flatten)]
+    location: Location,
+    #[serde(default)]
+    timestamp: u64,
+}
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
 fn get_cache_path() -> Result<PathBuf> {
     let mut path = dirs::cache_dir().context("Failed to find cache directory")?;
     path.push("weather_location.json");
@@ -155,34 +424,119 @@ fn get_cache_path() -> Result<PathBuf> {
 }
 fn write_to_cache(location: &Location) -> Result<()> {
     let path = get_cache_path()?;
-    let json_data = serde_json::to_string(location)?;
+    let cached = CachedLocation { location: location.clone(), timestamp: unix_now() };
+    let json_data = serde_json::to_string(&cached)?;
     fs::write(path, json_data)?;
     Ok(())
 }
-fn read_from_cache() -> Result<Location> {
+/// Reads the cached fix. With `max_age_secs`, an older entry is an error so the
+/// caller refreshes; without it, any cached fix is accepted (last-resort fallback).
+fn read_from_cache(max_age_secs: Option<u64>) -> Result<Location> {
     let path = get_cache_path()?;
     let json_data = fs::read_to_string(path)?;
-    Ok(serde_json::from_str(&json_data)?)
+    let cached: CachedLocation = serde_json::from_str(&json_data)?;
+    if let Some(max_age) = max_age_secs {
+        let age = unix_now().saturating_sub(cached.timestamp);
+        if age > max_age {
+            anyhow::bail!("Cached location is {}s old (max {}s)", age, max_age);
+        }
+    }
+    Ok(cached.location)
+}
+
+// Public IPs (and with them the city) change when the network does, so an IP fix is
+// kept for a few hours, apart from the geoclue cache whose fixes are far more precise.
+const IP_LOCATION_TTL_SECS: u64 = 6 * 3600;
+fn get_ip_cache_path() -> Result<PathBuf> {
+    let mut path = dirs::cache_dir().context("Failed to find cache directory")?;
+    path.push("weather_ip_location.json");
+    Ok(path)
+}
+fn read_ip_cache() -> Option<Location> {
+    let json_data = fs::read_to_string(get_ip_cache_path().ok()?).ok()?;
+    let cached: CachedLocation = serde_json::from_str(&json_data).ok()?;
+    let fresh = unix_now().saturating_sub(cached.timestamp) < IP_LOCATION_TTL_SECS;
+    fresh.then_some(cached.location)
+}
+fn write_ip_cache(location: &Location) -> Result<()> {
+    let cached = CachedLocation { location: location.clone(), timestamp: unix_now() };
+    fs::write(get_ip_cache_path()?, serde_json::to_string(&cached)?)?;
+    Ok(())
+}
+/// IP geolocation through its own cache, so a machine without a geoclue backend doesn't
+/// query ip-api.com on every bar refresh.
+async fn ip_location(client: &reqwest::Client) -> Result<Location> {
+    if let Some(cached) = read_ip_cache() {
+        log::debug!("Using cached IP location");
+        return Ok(cached);
+    }
+    let location = fetch_ip_location(client).await?;
+    if let Err(e) = write_ip_cache(&location) {
+        log::warn!("Failed to write IP location cache: {}", e);
+    }
+    Ok(location)
+}
+
+/// Reverse-geocoded place for a rounded coordinate. Nominatim allows one request per
+/// second, so a bar polling every few minutes must not ask it on every run.
+#[derive(Serialize, Deserialize, Debug)]
+struct CachedPlace {
+    key: String,
+    city: String,
+    state: String,
+    timestamp: u64,
+}
+// City names don't change; only a move to a new rounded coordinate re-queries.
+const PLACE_CACHE_TTL_SECS: u64 = 30 * 24 * 3600;
+/// ~1 km grid (2 decimals), well below the city-level zoom Nominatim is asked for.
+fn place_key(loc: &Location) -> String {
+    format!("{:.2},{:.2}", loc.latitude, loc.longitude)
+}
+fn get_place_cache_path() -> Result<PathBuf> {
+    let mut path = dirs::cache_dir().context("Failed to find cache directory")?;
+    path.push("weather_place.json");
+    Ok(path)
+}
+fn read_place_cache(loc: &Location) -> Option<(String, String)> {
+    let json_data = fs::read_to_string(get_place_cache_path().ok()?).ok()?;
+    let cached: CachedPlace = serde_json::from_str(&json_data).ok()?;
+    let fresh = unix_now().saturating_sub(cached.timestamp) < PLACE_CACHE_TTL_SECS;
+    (fresh && cached.key == place_key(loc)).then_some((cached.city, cached.state))
+}
+fn write_place_cache(loc: &Location, city: &str, state: &str) -> Result<()> {
+    let cached = CachedPlace {
+        key: place_key(loc),
+        city: city.to_string(),
+        state: state.to_string(),
+        timestamp: unix_now(),
+    };
+    fs::write(get_place_cache_path()?, serde_json::to_string(&cached)?)?;
+    Ok(())
 }
 /// Maps OpenWeatherMap condition IDs to Nerd Font weather icons.
 /// Handles day/night variants for Clear and Cloudy conditions.
-fn get_weather_icon(condition_id: u32, is_day: bool) -> &'static str {
-    match condition_id {
-        200..=299 => "󰖓", // Thunderstorm
-        300..=399 => "󰖖", // Drizzle
-        500..=599 => "󰖖", // Rain
-        600..=699 => "󰖘", // Snow
-        700..=799 => "󰖑", // Atmosphere
-        800 => if is_day { "󰖙" } else { "󰖔" }, // Clear
-        801..=804 => if is_day { "󰖐" } else { "󰖑" }, // Clouds
-        _ => "󰖐", // Default
-    }
+/// Glyphs set in `icons` replace the defaults for their condition.
+fn get_weather_icon(icons: &WeatherIcons, condition_id: u32, is_day: bool) -> &str {
+    let (custom, default) = match condition_id {
+        200..=299 => (&icons.thunderstorm, "󰖓"), // Thunderstorm
+        300..=399 => (&icons.drizzle, "󰖖"), // Drizzle
+        500..=599 => (&icons.rain, "󰖖"), // Rain
+        600..=699 => (&icons.snow, "󰖘"), // Snow
+        700..=799 => (&icons.atmosphere, "󰖑"), // Atmosphere
+        800 => if is_day { (&icons.clear_day, "󰖙") } else { (&icons.clear_night, "󰖔") }, // Clear
+        801..=804 => if is_day { (&icons.clouds_day, "󰖐") } else { (&icons.clouds_night, "󰖑") }, // Clouds
+        _ => (&icons.clouds_day, "󰖐"), // Default
+    };
+    custom.as_deref().unwrap_or(default)
 }
 // --- Network Functions ---
-async fn fetch_weather(client: &reqwest::Client, loc: &Location, api_key: &str) -> Result<CurrentWeather> {
+async fn fetch_weather(client: &reqwest::Client, loc: &Location, api_key: &str, units: Units) -> Result<CurrentWeather> {
+    if let Some(weather) = FIXTURES.load_json("weather.json") {
+        return weather;
+    }
     let url = format!(
-        "https://api.openweathermap.org/data/2.5/weather?lat={}&lon={}&appid={}&units=imperial",
-        loc.latitude, loc.longitude, api_key
+        "https://api.openweathermap.org/data/2.5/weather?lat={}&lon={}&appid={}&units={}",
+        loc.latitude, loc.longitude, api_key, units.api_param()
     );
     let response = client.get(&url)
         .send()
@@ -192,30 +546,50 @@ async fn fetch_weather(client: &reqwest::Client, loc: &Location, api_key: &str)
     Ok(response)
 }
 /// Performs reverse geocoding to convert coords -> "City, State".
-/// Uses OpenStreetMap (Nominatim).
+/// Uses OpenStreetMap (Nominatim), cached per rounded coordinate (see `CachedPlace`).
 async fn get_city_state(client: &reqwest::Client, loc: &Location) -> Result<(String, String)> {
     let url = format!(
         "https://nominatim.openstreetmap.org/reverse?format=json&lat={}&lon={}&zoom=10",
         loc.latitude, loc.longitude
     );
-    let response = client.get(&url)
-        .send()
-        .await?
-        .json::<NominatimResponse>()
-        .await?;
+    let fixture = FIXTURES.load_json::<NominatimResponse>("reverse-geocode.json");
+    let from_network = fixture.is_none();
+    if from_network
+        && let Some(place) = read_place_cache(loc)
+    {
+        log::debug!("Using cached place for {}", place_key(loc));
+        return Ok(place);
+    }
+    let response = match fixture {
+        Some(fixture) => fixture?,
+        None => {
+            log::debug!("Reverse geocoding {}", place_key(loc));
+            client.get(&url)
+                .send()
+                .await?
+                .json::<NominatimResponse>()
+                .await?
+        }
+    };
     let addr = response.address;
     // Fallback logic: prefer City -> Town -> Village
     let city = addr.city.or(addr.town).or(addr.village)
         .unwrap_or_else(|| "Unknown City".to_string());
     let state = addr.state
         .unwrap_or_else(|| "Unknown State".to_string());
+    if from_network && let Err(e) = write_place_cache(loc, &city, &state) {
+        log::warn!("Failed to write place cache: {}", e);
+    }
     Ok((city, state))
 }
 
-async fn fetch_forecast(client: &reqwest::Client, loc: &Location, api_key: &str) -> Result<Forecast> {
+async fn fetch_forecast(client: &reqwest::Client, loc: &Location, api_key: &str, units: Units) -> Result<Forecast> {
+    if let Some(forecast) = FIXTURES.load_json("forecast.json") {
+        return forecast;
+    }
     let url = format!(
-        "https://api.openweathermap.org/data/2.5/forecast?lat={}&lon={}&appid={}&units=imperial",
-        loc.latitude, loc.longitude, api_key
+        "https://api.openweathermap.org/data/2.5/forecast?lat={}&lon={}&appid={}&units={}",
+        loc.latitude, loc.longitude, api_key, units.api_param()
     );
 
     let response = client.get(&url)
@@ -226,12 +600,337 @@ async fn fetch_forecast(client: &reqwest::Client, loc: &Location, api_key: &str)
     Ok(response)
 }
 
+// --- Air Quality & UV ---
+
+/// Air quality on OWM's 1 (Good) - 5 (Very Poor) scale, plus UV when available.
+#[derive(Debug)]
+struct AirQuality {
+    aqi: u8,
+    pm2_5: f64,
+    uv_index: Option<f64>,
+}
+#[derive(Deserialize, Debug)]
+struct OwmPollutionComponents {
+    pm2_5: f64,
+}
+#[derive(Deserialize, Debug)]
+struct OwmPollutionMain {
+    aqi: u8,
+}
+#[derive(Deserialize, Debug)]
+struct OwmPollutionItem {
+    main: OwmPollutionMain,
+    components: OwmPollutionComponents,
+}
+#[derive(Deserialize, Debug)]
+struct OwmPollution {
+    list: Vec<OwmPollutionItem>,
+}
+#[derive(Deserialize, Debug)]
+struct OwmOneCallCurrent {
+    uvi: f64,
+}
+#[derive(Deserialize, Debug)]
+struct OwmOneCall {
+    current: OwmOneCallCurrent,
+}
+#[derive(Deserialize, Debug)]
+struct OpenMeteoAirCurrent {
+    european_aqi: Option<f64>,
+    pm2_5: Option<f64>,
+    uv_index: Option<f64>,
+}
+#[derive(Deserialize, Debug)]
+struct OpenMeteoAir {
+    current: OpenMeteoAirCurrent,
+}
+
+fn aqi_label(aqi: u8) -> &'static str {
+    match aqi {
+        1 => "Good",
+        2 => "Fair",
+        3 => "Moderate",
+        4 => "Poor",
+        _ => "Very Poor",
+    }
+}
+fn uv_label(uv: f64) -> &'static str {
+    match uv {
+        u if u < 3.0 => "Low",
+        u if u < 6.0 => "Moderate",
+        u if u < 8.0 => "High",
+        u if u < 11.0 => "Very High",
+        _ => "Extreme",
+    }
+}
+
+async fn fetch_uv_owm(client: &reqwest::Client, loc: &Location, api_key: &str) -> Result<f64> {
+    if let Some(fixture) = FIXTURES.load_json::<OwmOneCall>("uv.json") {
+        return fixture.map(|o| o.current.uvi);
+    }
+    let url = format!(
+        "https://api.openweathermap.org/data/3.0/onecall?lat={}&lon={}&exclude=minutely,hourly,daily,alerts&appid={}",
+        loc.latitude, loc.longitude, api_key
+    );
+    let response = client.get(&url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<OwmOneCall>()
+        .await?;
+    Ok(response.current.uvi)
+}
+
+/// OWM: Air Pollution endpoint for AQI/PM2.5. UV comes from One Call 3.0, which needs a
+/// separate (free tier) subscription, so a failure there only logs a warning and drops
+/// the UV line. Open-Meteo: one keyless call to its air-quality API returns all three.
+async fn fetch_air_quality(client: &reqwest::Client, loc: &Location, api_key: &str, provider: Provider) -> Result<AirQuality> {
+    match provider {
+        Provider::Owm => {
+            let pollution = match FIXTURES.load_json::<OwmPollution>("air-quality.json") {
+                Some(fixture) => fixture?,
+                None => {
+                    let url = format!(
+                        "https://api.openweathermap.org/data/2.5/air_pollution?lat={}&lon={}&appid={}",
+                        loc.latitude, loc.longitude, api_key
+                    );
+                    client.get(&url)
+                        .send()
+                        .await?
+                        .error_for_status()?
+                        .json::<OwmPollution>()
+                        .await?
+                }
+            };
+            let item = pollution.list.first().context("Air Pollution returned no data")?;
+            let uv_index = match fetch_uv_owm(client, loc, api_key).await {
+                Ok(uv) => Some(uv),
+                Err(e) => {
+                    log::warn!("UV index unavailable (needs a One Call 3.0 subscription for this key): {}", e);
+                    None
+                }
+            };
+            Ok(AirQuality {
+                aqi: item.main.aqi,
+                pm2_5: item.components.pm2_5,
+                uv_index,
+            })
+        }
+        Provider::OpenMeteo => {
+            let air = match FIXTURES.load_json::<OpenMeteoAir>("openmeteo-air-quality.json") {
+                Some(fixture) => fixture?,
+                None => {
+                    let url = format!(
+                        "https://air-quality-api.open-meteo.com/v1/air-quality?latitude={}&longitude={}&current=european_aqi,pm2_5,uv_index",
+                        loc.latitude, loc.longitude
+                    );
+                    client.get(&url)
+                        .send()
+                        .await?
+                        .error_for_status()?
+                        .json::<OpenMeteoAir>()
+                        .await?
+                }
+            };
+            let eaqi = air.current.european_aqi.context("Open-Meteo returned no AQI")?;
+            // European AQI bands are 20 wide: 0-20 Good ... 80-100 Very Poor (and beyond).
+            let aqi = ((eaqi / 20.0).floor() as u8 + 1).clamp(1, 5);
+            Ok(AirQuality {
+                aqi,
+                pm2_5: air.current.pm2_5.unwrap_or(0.0),
+                uv_index: air.current.uv_index,
+            })
+        }
+    }
+}
+
+// --- Open-Meteo Backend ---
+// Open-Meteo needs no API key. Its responses are mapped into the OpenWeatherMap structs
+// above so the tooltip code doesn't care which provider answered.
+
+#[derive(Deserialize, Debug)]
+struct OpenMeteoCurrent {
+    time: i64,
+    temperature_2m: f64,
+    apparent_temperature: f64,
+    relative_humidity_2m: f64,
+    pressure_msl: f64,
+    weather_code: u32,
+    wind_speed_10m: f64,
+    wind_direction_10m: Option<f64>,
+    visibility: Option<f64>,
+}
+#[derive(Deserialize, Debug)]
+struct OpenMeteoDaily {
+    temperature_2m_max: Vec<f64>,
+    temperature_2m_min: Vec<f64>,
+    sunrise: Vec<i64>,
+    sunset: Vec<i64>,
+}
+#[derive(Deserialize, Debug)]
+struct OpenMeteoHourly {
+    time: Vec<i64>,
+    temperature_2m: Vec<f64>,
+    weather_code: Vec<u32>,
+    precipitation_probability: Vec<Option<f64>>,
+}
+#[derive(Deserialize, Debug)]
+struct OpenMeteoResponse {
+    utc_offset_seconds: i64,
+    current: Option<OpenMeteoCurrent>,
+    daily: Option<OpenMeteoDaily>,
+    hourly: Option<OpenMeteoHourly>,
+}
+
+/// Maps a WMO weather code to the closest OpenWeatherMap condition ID and a description,
+/// so `get_weather_icon` and the tooltip work unchanged.
+fn wmo_to_owm(code: u32) -> (u32, &'static str) {
+    match code {
+        0 => (800, "clear sky"),
+        1 => (801, "mainly clear"),
+        2 => (802, "partly cloudy"),
+        3 => (804, "overcast"),
+        45 | 48 => (741, "fog"),
+        51 | 53 | 55 => (300, "drizzle"),
+        56 | 57 => (311, "freezing drizzle"),
+        61 => (500, "light rain"),
+        63 => (501, "moderate rain"),
+        65 => (502, "heavy rain"),
+        66 | 67 => (511, "freezing rain"),
+        71 => (600, "light snow"),
+        73 => (601, "snow"),
+        75 => (602, "heavy snow"),
+        77 => (600, "snow grains"),
+        80..=82 => (521, "rain showers"),
+        85 | 86 => (621, "snow showers"),
+        95 => (211, "thunderstorm"),
+        96 | 99 => (202, "thunderstorm with hail"),
+        _ => (803, "cloudy"),
+    }
+}
+
+/// Open-Meteo unit parameters. "standard" has no Kelvin option, so we ask for
+/// Celsius and shift the temperatures ourselves.
+fn openmeteo_units(units: Units) -> (&'static str, &'static str, f64) {
+    match units {
+        Units::Imperial => ("fahrenheit", "mph", 0.0),
+        Units::Metric => ("celsius", "ms", 0.0),
+        Units::Standard => ("celsius", "ms", 273.15),
+    }
+}
+
+async fn fetch_openmeteo(client: &reqwest::Client, loc: &Location, units: Units, fields: &str, days: u8, fixture: &str) -> Result<OpenMeteoResponse> {
+    if let Some(response) = FIXTURES.load_json(fixture) {
+        return response;
+    }
+    let (temp_unit, wind_unit, _) = openmeteo_units(units);
+    let url = format!(
+        "https://api.open-meteo.com/v1/forecast?latitude={}&longitude={}&{}&temperature_unit={}&wind_speed_unit={}&timezone=auto&timeformat=unixtime&forecast_days={}",
+        loc.latitude, loc.longitude, fields, temp_unit, wind_unit, days
+    );
+    let response = client.get(&url)
+        .send()
+        .await?
+        .json::<OpenMeteoResponse>()
+        .await?;
+    Ok(response)
+}
+
+async fn fetch_weather_openmeteo(client: &reqwest::Client, loc: &Location, units: Units) -> Result<CurrentWeather> {
+    let fields = "current=temperature_2m,apparent_temperature,relative_humidity_2m,pressure_msl,weather_code,wind_speed_10m,wind_direction_10m,visibility\
+                  &daily=temperature_2m_max,temperature_2m_min,sunrise,sunset";
+    let response = fetch_openmeteo(client, loc, units, fields, 2, "openmeteo-weather.json").await?;
+    let current = response.current.context("Open-Meteo returned no current weather")?;
+    let daily = response.daily.context("Open-Meteo returned no daily data")?;
+    let (_, _, offset) = openmeteo_units(units);
+    let (id, description) = wmo_to_owm(current.weather_code);
+    Ok(CurrentWeather {
+        weather: vec![Weather { id, description: description.to_string() }],
+        main: Main {
+            temp: current.temperature_2m + offset,
+            feels_like: current.apparent_temperature + offset,
+            humidity: current.relative_humidity_2m,
+            pressure: current.pressure_msl,
+            temp_min: daily.temperature_2m_min.first().copied().unwrap_or(current.temperature_2m) + offset,
+            temp_max: daily.temperature_2m_max.first().copied().unwrap_or(current.temperature_2m) + offset,
+        },
+        sys: Sys {
+            sunrise: daily.sunrise.first().copied().unwrap_or_default(),
+            sunset: daily.sunset.first().copied().unwrap_or_default(),
+        },
+        wind: Wind { speed: current.wind_speed_10m, deg: current.wind_direction_10m },
+        visibility: current.visibility,
+        dt: current.time,
+        timezone: response.utc_offset_seconds,
+    })
+}
+
+/// Open-Meteo forecasts are hourly; every third hour from now mirrors OWM's 3-hour steps.
+/// Five days matches OWM's forecast window, enough for the daily summary.
+async fn fetch_forecast_openmeteo(client: &reqwest::Client, loc: &Location, units: Units) -> Result<Forecast> {
+    let fields = "hourly=temperature_2m,weather_code,precipitation_probability";
+    let response = fetch_openmeteo(client, loc, units, fields, 5, "openmeteo-forecast.json").await?;
+    let hourly = response.hourly.context("Open-Meteo returned no hourly data")?;
+    let (_, _, offset) = openmeteo_units(units);
+    let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?.as_secs() as i64;
+    let list = hourly.time.iter().enumerate()
+        .filter(|(_, dt)| **dt > now)
+        .step_by(3)
+        .filter_map(|(i, dt)| {
+            let temp = hourly.temperature_2m.get(i)? + offset;
+            let (id, description) = wmo_to_owm(*hourly.weather_code.get(i)?);
+            let pop = hourly.precipitation_probability.get(i).copied().flatten().unwrap_or(0.0) / 100.0;
+            Some(ForecastItem {
+                dt: *dt,
+                main: Main { temp, feels_like: temp, humidity: 0.0, pressure: 0.0, temp_min: temp, temp_max: temp },
+                weather: vec![Weather { id, description: description.to_string() }],
+                pop,
+            })
+        })
+        .collect();
+    Ok(Forecast { list })
+}
+
+/// The fields Waybar needs from a custom module: a non-empty `text` (empty hides the module),
+/// a `tooltip` string, and `class` as a string or list of strings.
+fn validate_output(output: &serde_json::Value) -> Result<()> {
+    let text = output.get("text").and_then(|v| v.as_str())
+        .context("\"text\" is missing or not a string")?;
+    if text.trim().is_empty() {
+        anyhow::bail!("\"text\" is empty, so Waybar hides the module");
+    }
+    output.get("tooltip").and_then(|v| v.as_str())
+        .context("\"tooltip\" is missing or not a string")?;
+    match output.get("class") {
+        Some(serde_json::Value::String(_)) => {}
+        Some(serde_json::Value::Array(items)) if items.iter().all(|v| v.is_string()) => {}
+        _ => anyhow::bail!("\"class\" must be a string or a list of strings"),
+    }
+    Ok(())
+}
+
+/// Prints the module output: one compact line for Waybar, or checked and pretty on stderr.
+fn emit(output: &serde_json::Value, check: bool) -> Result<()> {
+    if !check {
+        println!("{}", output);
+        return Ok(());
+    }
+    eprintln!("{}", serde_json::to_string_pretty(output)?);
+    validate_output(output).context("Invalid Waybar output")?;
+    eprintln!("✓ Output is valid Waybar JSON");
+    Ok(())
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    common::version::exit_if_requested(env!("CARGO_BIN_NAME"), env!("CARGO_PKG_VERSION"));
+    let check = std::env::args().any(|a| a == "--check");
+    common::logging::init(module_path!(), common::logging::verbosity_from_args());
     //Initialize Config & Client
     let global_config = load_config()?;
-    let api_key = global_config.waybar_weather.owm_api_key;
+    let api_key = global_config.waybar_weather.owm_api_key.clone();
+    let units = global_config.waybar_weather.units;
+    let deg = units.temp_symbol();
     // Nominatim uses a strict User-Agent policy to avoid blocking.
     const NOMINATIM_USER_AGENT: &str = "WaybarWeatherScript/2.0-owm (Repo: github.com/Mccalabrese/Arch-multi-session-dot-files)"; 
     let http_client = reqwest::Client::builder()
@@ -239,31 +938,92 @@ async fn main() -> Result<()> {
         .build()?;
 
     // Obtain Location (with Caching Strategy)
-    // Strategy: Try to get a fresh, high-accuracy GPS fix. 
-    // If that fails (or takes too long/is inaccurate), fall back to the last known good cached location.
-    let location = match run_where_am_i().await {
-        Ok(fresh) => {
-            // Only update cache if the fix is reasonably accurate (< 1500m)
-            if fresh.accuracy < 1500.0 {
-                let _ = write_to_cache(&fresh);
-                fresh
-            } else {
-                   read_from_cache().unwrap_or(fresh) 
+    // Strategy: A configured [waybar_weather.location] is used as-is. Otherwise a recent cached
+    // fix wins outright, then a fresh, high-accuracy fix, and if that fails (or is too coarse)
+    // the last known good cached location.
+    let weather_config = &global_config.waybar_weather;
+    let mut via_ip = false;
+    let fixed_location = weather_config.location.as_ref();
+    let location = if let Some(fixed) = fixed_location {
+        log::debug!("Using the configured location");
+        Location { latitude: fixed.latitude, longitude: fixed.longitude, accuracy: 0.0 }
+    } else {
+        match read_from_cache(Some(weather_config.cache_max_age_secs)) {
+            Ok(cached) => {
+                log::debug!("Using cached location ({:.0} m)", cached.accuracy);
+                cached
+            }
+            Err(e) => {
+                log::debug!("Location cache not usable: {}", e);
+                let fresh_location = if let Some(stdout) = FIXTURES.load("where-am-i.txt") {
+                    stdout.and_then(|s| parse_where_am_i(&s))
+                } else {
+                    match find_where_am_i(weather_config.where_am_i_path.as_deref()) {
+                        Ok(binary) => run_where_am_i(&binary, Duration::from_secs(weather_config.geoclue_timeout_secs)).await,
+                        Err(e) => Err(e),
+                    }
+                };
+                match fresh_location {
+                    Ok(fresh) => {
+                        log::info!("Fresh geoclue fix ({:.0} m)", fresh.accuracy);
+                        // Only update cache if the fix is reasonably accurate
+                        if fresh.accuracy < weather_config.cache_max_accuracy_meters {
+                            if let Err(e) = write_to_cache(&fresh) {
+                                log::warn!("Failed to write location cache: {}", e);
+                            }
+                            fresh
+                        } else {
+                            log::debug!("Fix too coarse to cache, preferring the cached location");
+                            read_from_cache(None).unwrap_or(fresh)
+                        }
+                    }
+                    Err(e) => {
+                        log::warn!("'where-am-i' failed: {}. Trying cache...", e);
+                        match read_from_cache(None) {
+                            Ok(cached) => cached,
+                            Err(_) => {
+                                log::warn!("No cached location. Trying IP geolocation...");
+                                via_ip = true;
+                                ip_location(&http_client).await
+                                    .context("Failed to get fresh location, read cache AND geolocate by IP")?
+                            }
+                        }
+                    }
+                }
             }
-        }
-        Err(e) => {
-            eprintln!("'where-am-i' failed: {}. Trying cache...", e);
-            read_from_cache().context("Failed to get fresh location AND failed to read cache")?
         }
     };
 
     // Parallel Network Requests
     // I use tokio::join! to fetch Weather, Geo-data, and Forecast simultaneously
     // to minimize the total runtime of the script.
-    let (weather_res, geo_res, forecast_res) = tokio::join!(
-        fetch_weather(&http_client, &location, &api_key),
-        get_city_state(&http_client, &location),
-        fetch_forecast(&http_client, &location, &api_key)
+    let provider = global_config.waybar_weather.provider;
+    log::debug!("Fetching {:?} weather for {:.3}, {:.3}", provider, location.latitude, location.longitude);
+    let weather_fut = async {
+        match provider {
+            Provider::Owm => fetch_weather(&http_client, &location, &api_key, units).await,
+            Provider::OpenMeteo => fetch_weather_openmeteo(&http_client, &location, units).await,
+        }
+    };
+    let forecast_fut = async {
+        match provider {
+            Provider::Owm => fetch_forecast(&http_client, &location, &api_key, units).await,
+            Provider::OpenMeteo => fetch_forecast_openmeteo(&http_client, &location, units).await,
+        }
+    };
+    // A configured label replaces reverse geocoding
+    let label = fixed_location.and_then(|fixed| fixed.label.as_deref());
+    let geo_fut = async {
+        match label {
+            Some(label) => Ok((label.to_string(), String::new())),
+            None => get_city_state(&http_client, &location).await,
+        }
+    };
+    let (weather_res, geo_res, forecast_res, air_res) = tokio::join!(
+        weather_fut,
+        geo_fut,
+        forecast_fut,
+        fetch_air_quality(&http_client, &location, &api_key, provider)
     );
 
     // Handle Results & Build Output
@@ -271,48 +1031,81 @@ async fn main() -> Result<()> {
         Ok(data) => data,
         Err(e) => {
             // Output a valid JSON error state for Waybar so the bar doesn't crash
-            println!("{}", serde_json::json!({
+            emit(&serde_json::json!({
                 "text": "󰖕 API?",
                 "tooltip": format!("Failed to fetch weather: {}", e),
                 "class": "error"
-            }));
+            }), check)?;
+            if check {
+                anyhow::bail!("Failed to fetch weather: {:#}", e);
+            }
             return Ok(());
         }
     };
 
+    // The optional requests fail quietly in the bar; --check reports them
+    let problems: Vec<String> = [
+        ("Reverse geocoding", geo_res.as_ref().err()),
+        ("Forecast", forecast_res.as_ref().err()),
+        ("Air quality", air_res.as_ref().err()),
+    ]
+    .into_iter()
+    .filter_map(|(what, err)| err.map(|e| format!("{} failed: {:#}", what, e)))
+    .collect();
+
     let (city, state) = geo_res.unwrap_or(("Unknown".to_string(), "".to_string()));
     let forecast_data = forecast_res.ok();
+    let rainy_soon = forecast_data.as_ref().is_some_and(|forecast| {
+        forecast.list.iter()
+            .take(RAIN_SOON_INTERVALS)
+            .any(|item| item.pop * 100.0 >= global_config.waybar_weather.rain_threshold)
+    });
     // Calculate Timings (Day/Night)
     let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?.as_secs() as i64;
     let is_day = now >= weather_data.sys.sunrise && now <= weather_data.sys.sunset;
-    let icon = get_weather_icon(weather_data.weather[0].id, is_day);
+    let icon = get_weather_icon(&global_config.waybar_weather.icons, weather_data.weather[0].id, is_day);
 
     // Build Tooltip (Pango Markup)
     let mut tooltip_lines = Vec::new();
+    if fixed_location.is_some() {
+        let place = if state.is_empty() { city.clone() } else { format!("{}, {}", city, state) };
+        tooltip_lines.push(format!("<b>{}</b> (fixed location)", escape_markup(&place)));
+    } else if via_ip {
+        tooltip_lines.push(format!("<b>{}, {}</b> (approx via IP)", city, state));
+    } else {
+        tooltip_lines.push(format!(
+            "<b>{}, {}</b> (Acc: ~{:.0}m)",
+            city, state, location.accuracy
+        ));
+    }
     tooltip_lines.push(format!(
-        "<b>{}, {}</b> (Acc: ~{:.0}m)",
-        city, state, location.accuracy
-    ));
-    tooltip_lines.push(format!(
-        "<span size=\"large\">{:.0}°F</span> {} <b>{}</b>",
-        weather_data.main.temp, icon, weather_data.weather[0].description
+        "<span size=\"large\">{:.0}{}</span> {} <b>{}</b>",
+        weather_data.main.temp, deg, icon, weather_data.weather[0].description
     ));
     tooltip_lines.push(format!(
-        "<small>Feels like {:.0}°F</small>",
-        weather_data.main.feels_like
+        "<small>Feels like {:.0}{}</small>",
+        weather_data.main.feels_like, deg
     ));
     tooltip_lines.push(format!(
-        "Low {:.0}°F / High {:.0}°F",
-        weather_data.main.temp_min, weather_data.main.temp_max
+        "Low {:.0}{} / High {:.0}{}",
+        weather_data.main.temp_min, deg, weather_data.main.temp_max, deg
     ));
     tooltip_lines.push(String::new()); // Separator
     // Add Wind/Pressure/Vis details                                   // 
     let wind_dir = weather_data.wind.deg.map(|d| format!("({:.0}°)", d)).unwrap_or_default();
-    tooltip_lines.push(format!("󰖝 Wind: {:.1} mph {}", weather_data.wind.speed, wind_dir));
+    tooltip_lines.push(format!("󰖝 Wind: {:.1} {} {}", weather_data.wind.speed, units.wind_unit(), wind_dir));
     tooltip_lines.push(format!("󰖌 Humidity: {:.0}%", weather_data.main.humidity));
     tooltip_lines.push(format!("󰥡 Pressure: {:.0} hPa", weather_data.main.pressure));
     if let Some(vis) = weather_data.visibility {
-        tooltip_lines.push(format!("󰖑 Visibility: {:.1} mi", vis / 1609.34));
+        let (distance, unit) = units.visibility(vis);
+        tooltip_lines.push(format!("󰖑 Visibility: {:.1} {}", distance, unit));
+    }
+    // Optional extras: skipped silently if the endpoint failed
+    if let Ok(air) = &air_res {
+        if let Some(uv) = air.uv_index {
+            tooltip_lines.push(format!("󰖨 UV Index: {:.1} ({})", uv, uv_label(uv)));
+        }
+        tooltip_lines.push(format!("󰌪 Air Quality: {} (PM2.5 {:.1} µg/m³)", aqi_label(air.aqi), air.pm2_5));
     }
 
     // Append Forecast (Next 3 intervals)
@@ -321,6 +1114,9 @@ async fn main() -> Result<()> {
         // Calculate timezone offset for correct local time display
         let tz_offset = FixedOffset::east_opt(weather_data.timezone as i32)
             .unwrap_or(FixedOffset::east_opt(0).unwrap());
+        let today = DateTime::from_timestamp(now, 0)
+            .map(|dt| dt.with_timezone(&tz_offset).date_naive())
+            .unwrap_or_default();
         for item in forecast.list.iter().take(4) {
             if let Some(dt) = DateTime::from_timestamp(item.dt, 0) {
                 let local_time = dt.with_timezone(&tz_offset);
@@ -328,12 +1124,24 @@ async fn main() -> Result<()> {
                 let time_clean = time_str.strip_prefix('0').unwrap_or(&time_str);
                 //Calculate day/night for forecast icon
                 let is_fc_day = item.dt >= weather_data.sys.sunrise && item.dt <= weather_data.sys.sunset;
-                let fc_icon = get_weather_icon(item.weather[0].id, is_fc_day);
+                let fc_icon = get_weather_icon(&global_config.waybar_weather.icons, item.weather[0].id, is_fc_day);
                 let pop_percent = item.pop * 100.0;
 
                 tooltip_lines.push(format!(
-                    "{}: {:.0}°F {} (󰖗 {:.0}%)",
-                    time_clean, item.main.temp, fc_icon, pop_percent
+                    "{}: {:.0}{} {} (󰖗 {:.0}%)",
+                    time_clean, item.main.temp, deg, fc_icon, pop_percent
+                ));
+            }
+        }
+        // Daily highs/lows for planning ahead (today is already covered above)
+        let days = daily_summaries(&forecast.list, tz_offset, today);
+        if !days.is_empty() {
+            tooltip_lines.push("\n--- Next 3 Days ---".to_string());
+            for day in days.iter().take(3) {
+                let day_icon = get_weather_icon(&global_config.waybar_weather.icons, day.condition_id, true);
+                tooltip_lines.push(format!(
+                    "{}: {:.0}{} / {:.0}{} {} (󰖗 {:.0}%)",
+                    day.date.format("%a"), day.temp_min, deg, day.temp_max, deg, day_icon, day.pop * 100.0
                 ));
             }
         }
@@ -346,14 +1154,75 @@ async fn main() -> Result<()> {
     if let Some(cache_dir) = dirs::cache_dir() {
         let _ = fs::write(cache_dir.join(".weather_cache"), cleaned_tooltip);
     }
+    // Severity class from "feels like" so CSS can flag extremes.
+    // "weather" always stays first for existing selectors.
+    let (default_cold, default_hot) = units.default_thresholds();
+    let cold = global_config.waybar_weather.cold_threshold.unwrap_or(default_cold);
+    let hot = global_config.waybar_weather.hot_threshold.unwrap_or(default_hot);
+    let mut classes = vec!["weather"];
+    if weather_data.main.feels_like < cold {
+        classes.push("cold");
+    } else if weather_data.main.feels_like > hot {
+        classes.push("hot");
+    }
+    if rainy_soon {
+        classes.push("rainy-soon");
+    }
     // Final Output
     let output_json = serde_json::json!({
-        "text": format!("{:.0}°F {}", weather_data.main.temp, icon),
+        "text": format!("{:.0}{} {}", weather_data.main.temp, deg, icon),
         "tooltip": tooltip,
-        "class": "weather"
+        "class": classes
     });
 
-    println!("{}", output_json);
-    
+    emit(&output_json, check)?;
+    if check && !problems.is_empty() {
+        for problem in &problems {
+            eprintln!("✗ {}", problem);
+        }
+        anyhow::bail!("{} optional request(s) failed", problems.len());
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 3-hour OWM forecast for a UTC-5 location, from 19:00 local on 2026-01-10 ("today")
+    // through 19:00 local on 2026-01-12. See the comments in the assertions for the traps.
+    const FORECAST: &str = include_str!("../tests/fixtures/forecast-utc-minus-5.json");
+
+    fn summaries() -> Vec<DailySummary> {
+        let forecast: Forecast = serde_json::from_str(FORECAST).unwrap();
+        let tz = FixedOffset::west_opt(5 * 3600).unwrap();
+        let today = NaiveDate::from_ymd_opt(2026, 1, 10).unwrap();
+        daily_summaries(&forecast.list, tz, today)
+    }
+
+    #[test]
+    fn daily_summaries_buckets_by_local_date() {
+        let days = summaries();
+        let dates: Vec<NaiveDate> = days.iter().map(|d| d.date).collect();
+        // Today's evening intervals (-10/90) are dropped, not folded into the first day
+        assert_eq!(dates, [
+            NaiveDate::from_ymd_opt(2026, 1, 11).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 1, 12).unwrap(),
+        ]);
+        // 22:00 local on the 11th is already the 12th in UTC; it must count for the 11th
+        assert_eq!(days[0].temp_min, 31.0);
+        assert_eq!(days[0].temp_max, 58.0);
+        assert_eq!(days[1].temp_min, 33.0);
+        assert_eq!(days[1].temp_max, 50.0);
+    }
+
+    #[test]
+    fn daily_summaries_takes_condition_nearest_noon_and_max_pop() {
+        let days = summaries();
+        // 13:00 local is the closest interval to noon (10:00 is two hours off)
+        assert_eq!(days[0].condition_id, 500);
+        assert_eq!(days[1].condition_id, 801);
+        assert_eq!(days[0].pop, 0.7);
+        assert_eq!(days[1].pop, 0.2);
+    }
+}